@@ -67,6 +67,23 @@ impl tailcall::core::Cache for Cache {
         unimplemented!("Not needed for this bench")
     }
 
+    async fn delete<'a>(&'a self, _: &'a Self::Key) -> Result<(), cache::Error> {
+        unimplemented!("Not needed for this bench")
+    }
+
+    async fn tag<'a>(
+        &'a self,
+        _: &'a Self::Key,
+        _: &'a [String],
+        _: NonZeroU64,
+    ) -> Result<(), cache::Error> {
+        unimplemented!("Not needed for this bench")
+    }
+
+    async fn purge_by_tag<'a>(&'a self, _: &'a str) -> Result<usize, cache::Error> {
+        unimplemented!("Not needed for this bench")
+    }
+
     fn hit_rate(&self) -> Option<f64> {
         unimplemented!("Not needed for this bench")
     }
@@ -1,7 +1,12 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::{Parser, Subcommand, ValueEnum};
 use strum_macros::Display;
 use tailcall_version::VERSION;
 
+use crate::core::config::Source;
+
 const ABOUT: &str = r"
    __        _ __           ____
   / /_____ _(_) /________ _/ / /
@@ -25,6 +30,37 @@ pub enum Command {
         #[arg(required = true)]
         file_paths: Vec<String>,
 
+        /// Records the requests to the upstream and their responses to
+        /// `<dir>/upstream.yml`, in the format of the mocks of the execution
+        /// specs. The request headers and the cookies set by the upstream
+        /// aren't recorded
+        #[arg(long, value_name = "dir", conflicts_with = "replay")]
+        record: Option<PathBuf>,
+
+        /// Serves the responses recorded with `--record` instead of calling
+        /// the upstream
+        #[arg(long, value_name = "dir")]
+        replay: Option<PathBuf>,
+
+        /// Controls SSL/TLS certificate verification for remote config files
+        /// Set to false to skip certificate verification (not recommended for
+        /// production)
+        #[arg(short, long, action = clap::ArgAction::Set, default_value_t = true)]
+        verify_ssl: bool,
+    },
+
+    /// Starts the GraphQL server with synthetic data instead of calling the
+    /// upstream, as if `@server(mock: true)` was set
+    Mock {
+        /// Path for the configuration files or http(s) link to config files
+        /// separated by spaces if more than one
+        #[arg(required = true)]
+        file_paths: Vec<String>,
+
+        /// Seed of the generator of the data, overrides `@server(mockSeed)`
+        #[arg(long)]
+        seed: Option<u64>,
+
         /// Controls SSL/TLS certificate verification for remote config files
         /// Set to false to skip certificate verification (not recommended for
         /// production)
@@ -47,6 +83,28 @@ pub enum Command {
         #[arg(short, long)]
         schema: bool,
 
+        /// Display the OpenAPI document of the @rest endpoints
+        #[arg(long)]
+        openapi: bool,
+
+        /// Display the types of the schema and of the linked operations as
+        /// TypeScript or JSON Schema
+        #[arg(long, value_enum)]
+        emit: Option<EmitFormat>,
+
+        /// Lint the configuration
+        #[arg(long)]
+        lint: bool,
+
+        /// Path of the lint settings, defaults to `.tailcalllint.yml` if it
+        /// exists
+        #[arg(long, requires = "lint")]
+        lint_config: Option<String>,
+
+        /// Format of the lint report
+        #[arg(long, value_enum, default_value_t = LintFormat::Text, requires = "lint")]
+        format: LintFormat,
+
         /// Controls SSL/TLS certificate verification for remote config files
         /// Set to false to skip certificate verification (not recommended for
         /// production)
@@ -66,5 +124,90 @@ pub enum Command {
         /// Path of the configuration file
         #[arg(required = true)]
         file_path: String,
+
+        /// Regenerates the configuration when the inputs change
+        #[arg(long)]
+        watch: bool,
+
+        /// Keeps the edits made by hand to the existing output, such as
+        /// `@cache`, `@protected` or renamed fields, instead of overwriting it.
+        /// The generated output is kept in a hidden file next to it, so that
+        /// the items that aren't generated anymore can be removed
+        #[arg(long)]
+        merge: bool,
     },
+
+    /// Reports the changes between two configurations, classified as
+    /// breaking, dangerous or safe
+    Diff {
+        /// Path or http(s) link of the old configuration
+        old: String,
+
+        /// Path or http(s) link of the new configuration
+        new: String,
+
+        /// Exits with code 1 when a change of this severity, or a more
+        /// severe one, is found, and with 78 when a configuration is invalid
+        #[arg(long, value_enum, default_value_t = FailOn::Breaking)]
+        fail_on: FailOn,
+
+        /// Controls SSL/TLS certificate verification for remote config files
+        /// Set to false to skip certificate verification (not recommended for
+        /// production)
+        #[arg(short, long, action = clap::ArgAction::Set, default_value_t = true)]
+        verify_ssl: bool,
+    },
+
+    /// Formats configuration files, or converts them to another format
+    Fmt {
+        /// Path of the configuration files separated by spaces if more than
+        /// one
+        #[arg(required = true)]
+        file_paths: Vec<String>,
+
+        /// Format to convert the files to: graphql, json or yml. The
+        /// converted files are written next to the original ones
+        #[arg(long, value_parser = Source::from_str)]
+        to: Option<Source>,
+
+        /// Fails if the files aren't formatted, without writing them
+        #[arg(long, conflicts_with = "to")]
+        check: bool,
+    },
+
+    /// Runs the spec files of a directory against the configuration, with
+    /// the upstream replaced by the mocks of the specs
+    Test {
+        /// Path of a spec file, or of a directory of spec files
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// Writes the responses to the snapshots instead of comparing them
+        #[arg(long)]
+        update: bool,
+    },
+}
+
+/// Least severity of the changes that fail `tailcall diff`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    Breaking,
+    Dangerous,
+    Never,
+}
+
+/// Format of the types displayed by `tailcall check --emit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EmitFormat {
+    Ts,
+    #[value(name = "jsonschema")]
+    JsonSchema,
+}
+
+/// Format of the report of `tailcall check --lint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LintFormat {
+    Text,
+    Json,
+    Sarif,
 }
@@ -93,6 +93,12 @@ pub enum Source<Status = UnResolved> {
     Config {
         src: Location<Status>,
     },
+    Har {
+        src: Location<Status>,
+    },
+    Postman {
+        src: Location<Status>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -236,6 +242,8 @@ impl Source<UnResolved> {
                 let resolved_path = src.into_resolved(parent_dir);
                 Ok(Source::Config { src: resolved_path })
             }
+            Source::Har { src } => Ok(Source::Har { src: src.into_resolved(parent_dir) }),
+            Source::Postman { src } => Ok(Source::Postman { src: src.into_resolved(parent_dir) }),
         }
     }
 }
@@ -10,9 +10,9 @@ use tailcall_valid::{ValidateInto, Validator};
 use super::config::{Config, LLMConfig, Resolved, Source};
 use super::source::ConfigSource;
 use crate::cli::llm::InferTypeName;
-use crate::core::config::transformer::{Preset, RenameTypes};
+use crate::core::config::transformer::{PreserveEdits, Preset, RenameTypes};
 use crate::core::config::{self, ConfigModule, ConfigReaderContext};
-use crate::core::generator::{Generator as ConfigGenerator, Input};
+use crate::core::generator::{from_har, from_postman, Generator as ConfigGenerator, Input};
 use crate::core::proto_reader::ProtoReader;
 use crate::core::resource_reader::{Resource, ResourceReader};
 use crate::core::runtime::TargetRuntime;
@@ -24,26 +24,58 @@ pub struct Generator {
     /// path of config file.
     config_path: String,
     runtime: TargetRuntime,
+    /// keeps the edits made by hand to the existing output.
+    merge: bool,
+    /// overwrites the existing output without asking.
+    overwrite: bool,
 }
 
 impl Generator {
     pub fn new(config_path: &str, runtime: TargetRuntime) -> Self {
-        Self { config_path: config_path.to_string(), runtime }
+        Self {
+            config_path: config_path.to_string(),
+            runtime,
+            merge: false,
+            overwrite: false,
+        }
+    }
+
+    pub fn merge(mut self, merge: bool) -> Self {
+        self.merge = merge;
+        self
+    }
+
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
     }
 
-    /// Writes the configuration to the output file if allowed.
-    async fn write(self, graphql_config: &ConfigModule, output_path: &str) -> anyhow::Result<()> {
+    /// Writes the configuration to the output file if allowed. In merge mode
+    /// the generated configuration, before the edits are carried over, is
+    /// kept alongside to tell the edits apart on the next run.
+    async fn write(
+        self,
+        graphql_config: &ConfigModule,
+        generated: &ConfigModule,
+        output_path: &str,
+    ) -> anyhow::Result<()> {
         let output_source = config::Source::detect(output_path)?;
         let config = match output_source {
             config::Source::GraphQL => graphql_config.to_sdl(),
             _ => return Err(anyhow!("Only graphql output format is currently supported")),
         };
 
-        if self.should_overwrite(output_path)? {
+        if self.overwrite || self.should_overwrite(output_path)? {
             self.runtime
                 .file
                 .write(output_path, config.as_bytes())
                 .await?;
+            if self.merge {
+                self.runtime
+                    .file
+                    .write(&generated_path(output_path), generated.to_sdl().as_bytes())
+                    .await?;
+            }
 
             tracing::info!("Config successfully generated at {output_path}");
         }
@@ -89,6 +121,68 @@ impl Generator {
         config.into_resolved(config_path)
     }
 
+    /// Returns the local files the configuration is generated from, including
+    /// the configuration of the generator itself and the proto imports.
+    pub async fn input_paths(&self) -> anyhow::Result<Vec<String>> {
+        let config = self.read().await?;
+        let reader = ResourceReader::cached(self.runtime.clone());
+        let proto_reader = ProtoReader::init(reader, self.runtime.clone());
+        let mut paths = vec![self.config_path.clone()];
+
+        for input in config.inputs {
+            let input_paths = match input.source {
+                Source::Curl { src, .. } => vec![src.0],
+                Source::Proto { src, proto_paths, .. } => {
+                    let proto_paths =
+                        proto_paths.map(|paths| paths.into_iter().map(|l| l.0).collect::<Vec<_>>());
+                    // the imports are watched too, unless the file can't be read yet
+                    proto_reader
+                        .local_paths(&src.0, proto_paths.as_deref())
+                        .await
+                        .unwrap_or_else(|_| vec![src.0])
+                }
+                Source::Config { src } => vec![src.0],
+                Source::Har { src } => vec![src.0],
+                Source::Postman { src } => vec![src.0],
+            };
+            // the inputs read over http(s) can't be watched
+            for path in input_paths {
+                if Path::new(&path).is_file() && !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Carries the edits made to the existing output over to the generated
+    /// configuration.
+    async fn preserve_edits(
+        &self,
+        config: ConfigModule,
+        output_path: &str,
+    ) -> anyhow::Result<ConfigModule> {
+        if !self.merge || !is_exists(output_path) {
+            return Ok(config);
+        }
+
+        let content = self.runtime.file.read(output_path).await?;
+        let edited = config::Config::from_sdl(&content).to_result()?;
+        let mut preserve_edits = PreserveEdits::new(edited);
+        let previous_path = generated_path(output_path);
+        if is_exists(&previous_path) {
+            let content = self.runtime.file.read(&previous_path).await?;
+            preserve_edits =
+                preserve_edits.previous(config::Config::from_sdl(&content).to_result()?);
+        }
+        let cfg = preserve_edits
+            .transform(config.config().to_owned())
+            .to_result()?;
+
+        Ok(ConfigModule::from(cfg))
+    }
+
     /// performs all the i/o's required in the config file and generates
     /// concrete vec containing data for generator.
     pub async fn resolve_io(&self, config: Config<Resolved>) -> anyhow::Result<Vec<Input>> {
@@ -151,6 +245,14 @@ impl Generator {
                     let schema = reader.read_file(path).await?.content;
                     input_samples.push(Input::Config { schema, source });
                 }
+                Source::Har { src } => {
+                    let content = reader.read_file(src.0).await?.content;
+                    input_samples.extend(from_har(&content)?);
+                }
+                Source::Postman { src } => {
+                    let content = reader.read_file(src.0).await?.content;
+                    input_samples.extend(from_postman(&content)?);
+                }
             }
         }
 
@@ -191,7 +293,9 @@ impl Generator {
             }
         }
 
-        self.write(&config, &path).await?;
+        let generated = config.clone();
+        let config = self.preserve_edits(config, &path).await?;
+        self.write(&config, &generated, &path).await?;
         Ok(config)
     }
 }
@@ -201,6 +305,19 @@ fn is_exists(path: &str) -> bool {
     fs::metadata(path).is_ok()
 }
 
+/// Returns the path the generated configuration is kept at in merge mode, a
+/// hidden file next to the output.
+fn generated_path(output_path: &str) -> String {
+    let path = Path::new(output_path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{file_name}.generated"))
+        .to_string_lossy()
+        .to_string()
+}
+
 /// Expects both paths to be absolute and returns a relative path from `from` to
 /// `to`. expects `from`` to be directory.
 fn to_relative_path(from: &Path, to: &str) -> Option<String> {
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::create_header_map;
+use crate::core::HttpIO;
+
+#[derive(Deserialize)]
+struct FetchRequest {
+    url: String,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FetchResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+/// Executes the request serialized by the `fetch` shim and returns the
+/// serialized response. The request is sent with the runtime's `HttpIO`, so
+/// that the `@upstream` settings apply to it as well.
+pub async fn fetch(
+    http: &dyn HttpIO,
+    request: &str,
+    timeout: Option<Duration>,
+) -> anyhow::Result<String> {
+    let request: FetchRequest = serde_json::from_str(request)?;
+    let method = reqwest::Method::from_str(request.method.as_deref().unwrap_or("GET"))?;
+
+    let mut req = reqwest::Request::new(method, request.url.parse()?);
+    *req.headers_mut() = create_header_map(request.headers)?;
+    if let Some(body) = request.body {
+        req.body_mut().replace(body.into());
+    }
+
+    let response = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, http.execute(req)).await??,
+        None => http.execute(req).await?,
+    };
+
+    let headers = response
+        .headers
+        .iter()
+        .filter_map(|(key, value)| Some((key.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+
+    let response = FetchResponse {
+        status: response.status.as_u16(),
+        headers,
+        body: String::from_utf8_lossy(&response.body).to_string(),
+    };
+
+    Ok(serde_json::to_string(&response)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::body::Bytes;
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::http::Response;
+
+    struct EchoHttp;
+
+    #[async_trait::async_trait]
+    impl HttpIO for EchoHttp {
+        async fn execute(&self, request: reqwest::Request) -> anyhow::Result<Response<Bytes>> {
+            let body = json!({
+                "method": request.method().as_str(),
+                "url": request.url().as_str(),
+                "auth": request.headers().get("authorization").and_then(|v| v.to_str().ok()),
+                "body": request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .map(|body| String::from_utf8_lossy(body).to_string()),
+            });
+            Ok(Response::default().body(Bytes::from(body.to_string())))
+        }
+    }
+
+    struct SlowHttp;
+
+    #[async_trait::async_trait]
+    impl HttpIO for SlowHttp {
+        async fn execute(&self, _: reqwest::Request) -> anyhow::Result<Response<Bytes>> {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(Response::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let request = json!({
+            "url": "http://jsonplaceholder.typicode.com/users",
+            "method": "POST",
+            "headers": {"authorization": "Bearer token"},
+            "body": "{\"name\":\"test\"}"
+        });
+
+        let response = fetch(&EchoHttp, &request.to_string(), None).await.unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        let body: serde_json::Value =
+            serde_json::from_str(response["body"].as_str().unwrap()).unwrap();
+
+        assert_eq!(response["status"], 200);
+        assert_eq!(
+            body,
+            json!({
+                "method": "POST",
+                "url": "http://jsonplaceholder.typicode.com/users",
+                "auth": "Bearer token",
+                "body": "{\"name\":\"test\"}"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_timeout() {
+        let request = json!({ "url": "http://jsonplaceholder.typicode.com/users" });
+
+        let error = fetch(
+            &SlowHttp,
+            &request.to_string(),
+            Some(Duration::from_millis(100)),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.is::<tokio::time::error::Elapsed>());
+    }
+}
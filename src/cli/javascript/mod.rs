@@ -5,17 +5,21 @@ use http::header::{HeaderName, HeaderValue};
 
 pub mod codec;
 
+mod fetch;
 mod runtime;
 
 pub use runtime::Runtime;
 
-use crate::core::{blueprint, WorkerIO};
+use crate::core::{blueprint, HttpIO, WorkerIO};
 
-pub fn init_worker_io<T, V>(script: blueprint::Script) -> Arc<dyn WorkerIO<T, V> + Send + Sync>
+pub fn init_worker_io<T, V>(
+    script: blueprint::Script,
+    http: Arc<dyn HttpIO>,
+) -> Arc<dyn WorkerIO<T, V> + Send + Sync>
 where
     Runtime: WorkerIO<T, V>,
 {
-    (Arc::new(Runtime::new(script))) as _
+    (Arc::new(Runtime::new(script, http))) as _
 }
 
 fn create_header_map(headers: BTreeMap<String, String>) -> anyhow::Result<headers::HeaderMap> {
@@ -1,18 +1,69 @@
-use std::cell::{OnceCell, RefCell};
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 use async_graphql_value::ConstValue;
-use rquickjs::{Context, Ctx, FromJs, Function, IntoJs, Value};
+use rquickjs::loader::{BuiltinLoader, Resolver};
+use rquickjs::{Context, Ctx, FromJs, Function, IntoJs, Module, Object, Promise, Value};
+use tokio::sync::{mpsc, Mutex};
 
+use super::fetch;
+use crate::core::config::ScriptModule;
 use crate::core::worker::{Command, Event};
-use crate::core::{blueprint, worker, WorkerIO};
+use crate::core::{blueprint, worker, HttpIO, WorkerIO};
+
+/// Global object that holds the namespaces of the linked modules by their id.
+const MODULES: &str = "__tailcall_modules";
+
+/// Global object that holds the promises returned by the calls that are
+/// waiting for them to settle, by the id of the call.
+const PENDING_CALLS: &str = "__tailcall_pending_calls";
+
+/// Response of a request sent by the `fetch` shim, by the id the shim gave
+/// to the request.
+type FetchResult = (u32, Result<String, String>);
+
+/// Responses of the requests sent by the scripts. They're delivered to the
+/// scripts by the calls waiting for their promises to settle.
+struct Fetches {
+    sender: mpsc::UnboundedSender<FetchResult>,
+    receiver: Arc<Mutex<mpsc::UnboundedReceiver<FetchResult>>>,
+}
+
+impl Fetches {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self { sender, receiver: Arc::new(Mutex::new(receiver)) }
+    }
+}
 
 struct LocalRuntime(Context);
 
 thread_local! {
     // Practically only one JS runtime is created for every Runtime because tokio_runtime is single threaded.
   static LOCAL_RUNTIME: RefCell<OnceCell<LocalRuntime>> = const { RefCell::new(OnceCell::new()) };
+  // Instant after which the call that is currently executed is interrupted.
+  static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+  static FETCHES: Fetches = Fetches::new();
+  static NEXT_CALL_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Returns the time left for the call that is currently executed.
+fn remaining_time() -> Option<Duration> {
+    DEADLINE
+        .get()
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+fn is_timed_out() -> bool {
+    DEADLINE
+        .get()
+        .is_some_and(|deadline| Instant::now() >= deadline)
 }
 
 #[rquickjs::function]
@@ -25,24 +76,148 @@ fn qjs_print(msg: String, is_err: bool) {
 }
 
 static CONSOLE_JS: &[u8] = include_bytes!("shim/console.js");
+static FETCH_JS: &[u8] = include_bytes!("shim/fetch.js");
 
-fn setup_builtins(ctx: &Ctx<'_>) -> rquickjs::Result<()> {
+fn setup_builtins<'js>(ctx: &Ctx<'js>, http: Arc<dyn HttpIO>) -> rquickjs::Result<()> {
     ctx.globals().set("__qjs_print", js_qjs_print)?;
     let _: Value = ctx.eval(CONSOLE_JS)?;
 
+    // The request is sent in the background, and its response is delivered
+    // to the script by `__qjs_settle_fetch` once received.
+    let qjs_fetch = Function::new(ctx.clone(), move |id: u32, request: String| {
+        let http = http.clone();
+        let timeout = remaining_time();
+        let sender = FETCHES.with(|fetches| fetches.sender.clone());
+        tokio::spawn(async move {
+            let response = fetch::fetch(http.as_ref(), &request, timeout)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = sender.send((id, response));
+        });
+    })?;
+    ctx.globals().set("__qjs_fetch", qjs_fetch)?;
+    let _: Value = ctx.eval(FETCH_JS)?;
+    ctx.globals()
+        .set(PENDING_CALLS, Object::new(ctx.clone())?)?;
+
     Ok(())
 }
 
-impl TryFrom<blueprint::Script> for LocalRuntime {
-    type Error = anyhow::Error;
+/// Resolves the imports between the linked modules. A module can be imported
+/// either by its id or by its path relative to the importing module.
+struct ModuleResolver {
+    ids: HashMap<String, String>,
+}
+
+impl ModuleResolver {
+    fn new(modules: &[ScriptModule]) -> Self {
+        Self {
+            ids: modules
+                .iter()
+                .map(|module| (module.id.clone(), module.path.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl Resolver for ModuleResolver {
+    fn resolve<'js>(&mut self, _: &Ctx<'js>, base: &str, name: &str) -> rquickjs::Result<String> {
+        if let Some(path) = self.ids.get(name) {
+            return Ok(path.clone());
+        }
+
+        let path = normalize(&Path::new(base).parent().unwrap_or(Path::new("")).join(name));
+        self.ids
+            .values()
+            .find(|p| normalize(Path::new(p)) == path)
+            .cloned()
+            .ok_or_else(|| rquickjs::Error::new_resolving(base, name))
+    }
+}
 
-    fn try_from(script: blueprint::Script) -> Result<Self, Self::Error> {
-        let source = script.source;
+/// Removes the `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .fold(PathBuf::new(), |mut path, component| {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    path.pop();
+                }
+                component => path.push(component),
+            }
+            path
+        })
+}
+
+/// Formats the error thrown by the script with its message and stack, so that
+/// it points to the file and line where it was thrown.
+fn format_error(ctx: &Ctx<'_>, error: rquickjs::Error) -> String {
+    if !error.is_exception() {
+        return error.to_string();
+    }
+
+    let value = ctx.catch();
+    match value.as_exception() {
+        Some(exception) => {
+            let message = exception.message().unwrap_or_default();
+            match exception.stack() {
+                Some(stack) if !stack.is_empty() => format!("{message}\n{}", stack.trim_end()),
+                _ => message,
+            }
+        }
+        None => format!("{value:?}"),
+    }
+}
+
+/// Imports the linked modules and keeps their namespaces in a global object.
+fn import_modules(ctx: &Ctx<'_>, modules: &[ScriptModule]) -> rquickjs::Result<()> {
+    let namespaces = Object::new(ctx.clone())?;
+    for module in modules {
+        let namespace: Object = Module::import(ctx, module.id.as_str())?.finish()?;
+        namespaces.set(module.id.as_str(), namespace)?;
+    }
+    ctx.globals().set(MODULES, namespaces)
+}
+
+/// Finds the function by its name. Names in the form of `<id>.<function>`
+/// reference an export of the module linked with that id.
+fn get_function<'js>(ctx: &Ctx<'js>, name: &str) -> Result<Function<'js>, worker::Error> {
+    let value = match name.split_once('.') {
+        Some((id, function)) => ctx
+            .globals()
+            .get::<_, Object>(MODULES)
+            .and_then(|modules| modules.get::<_, Object>(id))
+            .and_then(|module| module.get::<_, Value>(function)),
+        None => ctx.globals().get::<_, Value>(name),
+    }
+    .map_err(|e| worker::Error::GlobalThisNotInitialised(e.to_string()))?;
+
+    value
+        .into_function()
+        .ok_or(worker::Error::InvalidFunction(name.to_string()))
+}
+
+impl LocalRuntime {
+    fn try_new(script: blueprint::Script, http: Arc<dyn HttpIO>) -> anyhow::Result<Self> {
         let js_runtime = rquickjs::Runtime::new()?;
+        // Interrupts the scripts that run longer than the configured timeout.
+        js_runtime.set_interrupt_handler(Some(Box::new(is_timed_out)));
+
+        let loader = script
+            .modules
+            .iter()
+            .fold(BuiltinLoader::default(), |loader, module| {
+                loader.with_module(module.path.as_str(), module.source.as_str())
+            });
+        js_runtime.set_loader(ModuleResolver::new(&script.modules), loader);
+
         let context = Context::full(&js_runtime)?;
-        let _: () = context.with(|ctx| {
-            setup_builtins(&ctx)?;
-            ctx.eval(source)
+        context.with(|ctx| {
+            setup_builtins(&ctx, http)
+                .and_then(|_| ctx.eval::<(), _>(script.source))
+                .and_then(|_| import_modules(&ctx, &script.modules))
+                .map_err(|e| anyhow::anyhow!(format_error(&ctx, e)))
         })?;
 
         tracing::debug!("JS Runtime created: {:?}", thread::current().name());
@@ -52,6 +227,7 @@ impl TryFrom<blueprint::Script> for LocalRuntime {
 
 pub struct Runtime {
     script: blueprint::Script,
+    http: Arc<dyn HttpIO>,
     // Single threaded JS runtime, that's shared across all tokio workers.
     tokio_runtime: Option<tokio::runtime::Runtime>,
 }
@@ -63,13 +239,14 @@ impl Debug for Runtime {
 }
 
 impl Runtime {
-    pub fn new(script: blueprint::Script) -> Self {
+    pub fn new(script: blueprint::Script, http: Arc<dyn HttpIO>) -> Self {
         let tokio_runtime = tokio::runtime::Builder::new_multi_thread()
             .worker_threads(1)
+            .enable_all()
             .build()
             .expect("JS runtime not initialized");
 
-        Self { script, tokio_runtime: Some(tokio_runtime) }
+        Self { script, http, tokio_runtime: Some(tokio_runtime) }
     }
 }
 
@@ -88,12 +265,14 @@ impl Drop for Runtime {
 impl WorkerIO<Event, Command> for Runtime {
     async fn call(&self, name: &str, event: Event) -> Result<Option<Command>, worker::Error> {
         let script = self.script.clone();
+        let http = self.http.clone();
         let name = name.to_string(); // TODO
         if let Some(runtime) = &self.tokio_runtime {
             runtime
                 .spawn(async move {
-                    init_rt(script)?;
-                    call(name, event)
+                    let timeout = script.timeout;
+                    init_rt(script, http)?;
+                    with_timeout(timeout, |deadline| call(name, event, deadline)).await
                 })
                 .await?
         } else {
@@ -110,13 +289,18 @@ impl WorkerIO<ConstValue, ConstValue> for Runtime {
         input: ConstValue,
     ) -> Result<Option<ConstValue>, worker::Error> {
         let script = self.script.clone();
+        let http = self.http.clone();
         let name = name.to_string();
         let value = serde_json::to_string(&input)?;
         if let Some(runtime) = &self.tokio_runtime {
             runtime
                 .spawn(async move {
-                    init_rt(script)?;
-                    execute_inner(name, value).map(Some)
+                    let timeout = script.timeout;
+                    init_rt(script, http)?;
+                    with_timeout(timeout, |deadline| async move {
+                        execute_inner(name, value, deadline).await.map(Some)
+                    })
+                    .await
                 })
                 .await?
         } else {
@@ -125,12 +309,12 @@ impl WorkerIO<ConstValue, ConstValue> for Runtime {
     }
 }
 
-fn init_rt(script: blueprint::Script) -> anyhow::Result<()> {
+fn init_rt(script: blueprint::Script, http: Arc<dyn HttpIO>) -> anyhow::Result<()> {
     // initialize runtime if this is the first call
     // exit if failed to initialize
     LOCAL_RUNTIME.with(move |cell| {
         if cell.borrow().get().is_none() {
-            LocalRuntime::try_from(script).and_then(|runtime| {
+            LocalRuntime::try_new(script, http).and_then(|runtime| {
                 cell.borrow().set(runtime).map_err(|_| {
                     anyhow::anyhow!("trying to reinitialize an already initialized QuickJS runtime")
                 })
@@ -141,6 +325,132 @@ fn init_rt(script: blueprint::Script) -> anyhow::Result<()> {
     })
 }
 
+/// Runs the call with its deadline, so that it's interrupted once the
+/// timeout is exceeded, be it while running the script or waiting for its
+/// promises.
+async fn with_timeout<A, F: Future<Output = Result<A, worker::Error>>>(
+    timeout: Duration,
+    call: impl FnOnce(Instant) -> F,
+) -> Result<A, worker::Error> {
+    let deadline = Instant::now() + timeout;
+    match tokio::time::timeout(timeout, call(deadline)).await {
+        Ok(result) if Instant::now() < deadline => result,
+        _ => Err(worker::Error::Timeout(timeout.as_millis() as u64)),
+    }
+}
+
+/// Runs the closure in the context of the JS runtime of the thread, with the
+/// deadline of the call that is executed.
+fn with_context<A>(
+    deadline: Instant,
+    f: impl FnOnce(&Ctx<'_>) -> Result<A, worker::Error>,
+) -> Result<A, worker::Error> {
+    LOCAL_RUNTIME.with_borrow_mut(|cell| {
+        let runtime = cell.get_mut().ok_or(worker::Error::RuntimeNotInitialized)?;
+        DEADLINE.set(Some(deadline));
+        let result = runtime.0.with(|ctx| f(&ctx));
+        DEADLINE.set(None);
+        result
+    })
+}
+
+fn pending_calls<'js>(ctx: &Ctx<'js>) -> Result<Object<'js>, worker::Error> {
+    ctx.globals()
+        .get(PENDING_CALLS)
+        .map_err(|e| worker::Error::GlobalThisNotInitialised(e.to_string()))
+}
+
+/// Promise of a call that is waiting for it to settle. It's dropped from the
+/// pending calls along with the call, e.g. once it times out.
+struct PendingCall(u32);
+
+impl Drop for PendingCall {
+    fn drop(&mut self) {
+        let _ = LOCAL_RUNTIME.try_with(|cell| {
+            if let Some(runtime) = cell.try_borrow().ok().as_ref().and_then(|cell| cell.get()) {
+                runtime.0.with(|ctx| {
+                    let _ = pending_calls(&ctx).map(|calls| calls.remove(self.0));
+                });
+            }
+        });
+    }
+}
+
+/// Settles the call once its promise has settled, after running the jobs of
+/// the promises that are ready.
+fn poll_call<'js, A>(
+    ctx: &Ctx<'js>,
+    id: u32,
+    settle: &impl Fn(&Ctx<'js>, rquickjs::Result<Value<'js>>) -> Result<A, worker::Error>,
+) -> Result<Option<A>, worker::Error> {
+    while ctx.execute_pending_job() {}
+
+    let promise: Promise = pending_calls(ctx)?
+        .get(id)
+        .map_err(|e| worker::Error::Rquickjs(e.to_string()))?;
+    promise
+        .result::<Value>()
+        .map(|result| settle(ctx, result))
+        .transpose()
+}
+
+/// Delivers the response of a request sent by the `fetch` shim to the
+/// script.
+fn deliver_fetch(ctx: &Ctx<'_>, (id, response): FetchResult) -> Result<(), worker::Error> {
+    let (response, error) = match response {
+        Ok(response) => (Some(response), None),
+        Err(error) => (None, Some(error)),
+    };
+    ctx.globals()
+        .get::<_, Function>("__qjs_settle_fetch")
+        .and_then(|settle| settle.call::<_, ()>((id, response, error)))
+        .map_err(|e| worker::Error::Rquickjs(format_error(ctx, e)))
+}
+
+/// Calls the function of the script and settles its result. When the
+/// function returns a promise, the responses of the requests sent by the
+/// scripts are delivered as they're received until the promise settles, so
+/// that the thread isn't blocked while they're in flight. The errors of
+/// `invoke` are returned as is, while the result of the call, or of its
+/// promise, is passed to `settle`.
+async fn run<A>(
+    deadline: Instant,
+    invoke: impl for<'js> FnOnce(&Ctx<'js>) -> Result<rquickjs::Result<Value<'js>>, worker::Error>,
+    settle: impl for<'js> Fn(&Ctx<'js>, rquickjs::Result<Value<'js>>) -> Result<A, worker::Error>,
+) -> Result<A, worker::Error> {
+    let id = NEXT_CALL_ID.get();
+    NEXT_CALL_ID.set(id.wrapping_add(1));
+
+    let settled = with_context(deadline, |ctx| match invoke(ctx)? {
+        Ok(value) if value.is_promise() => {
+            pending_calls(ctx)?
+                .set(id, value)
+                .map_err(|e| worker::Error::Rquickjs(e.to_string()))?;
+            Ok(None)
+        }
+        result => settle(ctx, result).map(Some),
+    })?;
+    if let Some(settled) = settled {
+        return Ok(settled);
+    }
+
+    let _pending = PendingCall(id);
+    loop {
+        // The responses are received by one call at a time, which delivers
+        // them to the script whichever call sent them. The promise is checked
+        // once the receiver is acquired, as it may have been settled by the
+        // responses delivered meanwhile.
+        let receiver = FETCHES.with(|fetches| fetches.receiver.clone());
+        let mut receiver = receiver.lock_owned().await;
+        if let Some(settled) = with_context(deadline, |ctx| poll_call(ctx, id, &settle))? {
+            return Ok(settled);
+        }
+
+        let response = receiver.recv().await.ok_or(worker::Error::Communication)?;
+        with_context(deadline, |ctx| deliver_fetch(ctx, response))?;
+    }
+}
+
 fn prepare_args<'js, T: IntoJs<'js>>(
     ctx: &Ctx<'js>,
     label: &str,
@@ -151,51 +461,204 @@ fn prepare_args<'js, T: IntoJs<'js>>(
     Ok((object.into_value(),))
 }
 
-fn call(name: String, event: Event) -> Result<Option<Command>, worker::Error> {
-    LOCAL_RUNTIME.with_borrow_mut(|cell| {
-        let runtime = cell.get_mut().ok_or(worker::Error::RuntimeNotInitialized)?;
-        runtime.0.with(|ctx| {
-            let fn_as_value = ctx
-                .globals()
-                .get::<&str, Function>(name.as_str())
-                .map_err(|e| worker::Error::GlobalThisNotInitialised(e.to_string()))?;
-
-            let function = fn_as_value
-                .as_function()
-                .ok_or(worker::Error::InvalidFunction(name))?;
-
+async fn call(
+    name: String,
+    event: Event,
+    deadline: Instant,
+) -> Result<Option<Command>, worker::Error> {
+    run(
+        deadline,
+        |ctx| {
+            let function = get_function(ctx, &name)?;
             let args = match event {
-                Event::Request(req) => prepare_args(&ctx, "request", req)
+                Event::Request(req) => prepare_args(ctx, "request", req)
                     .map_err(|e| worker::Error::Rquickjs(e.to_string()))?,
-                Event::Response(resp) => prepare_args(&ctx, "response", resp)
+                Event::Response(resp) => prepare_args(ctx, "response", resp)
                     .map_err(|e| worker::Error::Rquickjs(e.to_string()))?,
             };
-
-            let command: Option<Value> = function.call(args).ok();
-            command
-                .map(|output| Command::from_js(&ctx, output))
-                .transpose()
+            Ok(function.call(args))
+        },
+        |ctx, command| {
+            let command = command.map_err(|e| worker::Error::Rquickjs(format_error(ctx, e)))?;
+            Command::from_js(ctx, command)
+                .map(Some)
                 .map_err(|e| worker::Error::DeserializeFailed(e.to_string()))
-        })
-    })
+        },
+    )
+    .await
 }
 
-fn execute_inner(name: String, value: String) -> Result<ConstValue, worker::Error> {
-    LOCAL_RUNTIME.with_borrow_mut(|cell| {
-        let runtime = cell.get_mut().ok_or(worker::Error::RuntimeNotInitialized)?;
-        runtime.0.with(|ctx| {
-            let fn_as_value = ctx
-                .globals()
-                .get::<_, rquickjs::Function>(&name)
-                .map_err(|e| worker::Error::GlobalThisNotInitialised(e.to_string()))?;
-
-            let function = fn_as_value
-                .as_function()
-                .ok_or(worker::Error::InvalidFunction(name.clone()))?;
-            let val: String = function
-                .call((value,))
-                .map_err(|e| worker::Error::FunctionValueParseError(e.to_string(), name))?;
-            Ok::<_, worker::Error>(serde_json::from_str(&val)?)
-        })
-    })
+async fn execute_inner(
+    name: String,
+    value: String,
+    deadline: Instant,
+) -> Result<ConstValue, worker::Error> {
+    run(
+        deadline,
+        |ctx| Ok(get_function(ctx, &name)?.call((value,))),
+        |ctx, val| {
+            let val: Value = val.map_err(|e| worker::Error::Rquickjs(format_error(ctx, e)))?;
+            let val: String = val
+                .get()
+                .map_err(|e| worker::Error::FunctionValueParseError(e.to_string(), name.clone()))?;
+            Ok(serde_json::from_str(&val)?)
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::body::Bytes;
+
+    use super::*;
+    use crate::core::http::Response;
+    use crate::core::worker::WorkerRequest;
+
+    struct EchoHttp;
+
+    #[async_trait::async_trait]
+    impl HttpIO for EchoHttp {
+        async fn execute(&self, request: reqwest::Request) -> anyhow::Result<Response<Bytes>> {
+            Ok(Response {
+                body: Bytes::from(format!(r#"{{"path":"{}"}}"#, request.url().path())),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn runtime(source: &str) -> Runtime {
+        runtime_with_modules(source, vec![])
+    }
+
+    fn runtime_with_modules(source: &str, modules: Vec<ScriptModule>) -> Runtime {
+        let script = blueprint::Script {
+            source: source.to_string(),
+            modules,
+            timeout: Duration::from_millis(100),
+        };
+        Runtime::new(script, Arc::new(EchoHttp))
+    }
+
+    fn module(id: &str, path: &str, source: &str) -> ScriptModule {
+        ScriptModule {
+            id: id.to_string(),
+            path: path.to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    async fn call_js(runtime: &Runtime, name: &str) -> Result<Option<ConstValue>, worker::Error> {
+        WorkerIO::<ConstValue, ConstValue>::call(runtime, name, ConstValue::Null).await
+    }
+
+    #[tokio::test]
+    async fn test_resolved_promise() {
+        let runtime = runtime(r#"async function hello() { return JSON.stringify("hello") }"#);
+
+        let value = call_js(&runtime, "hello").await.unwrap();
+        assert_eq!(value, Some(ConstValue::String("hello".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let runtime = runtime(
+            r#"
+            async function user() {
+              const response = await fetch("http://localhost/users/1")
+              const user = await response.json()
+              return JSON.stringify(user.path)
+            }
+            "#,
+        );
+
+        let value = call_js(&runtime, "user").await.unwrap();
+        assert_eq!(value, Some(ConstValue::String("/users/1".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let runtime = runtime(
+            r#"
+            function spin() { while (true) {} }
+            function wait() { return new Promise(() => {}) }
+            "#,
+        );
+
+        let error = call_js(&runtime, "spin").await.unwrap_err();
+        assert!(matches!(error, worker::Error::Timeout(100)));
+        let error = call_js(&runtime, "wait").await.unwrap_err();
+        assert!(matches!(error, worker::Error::Timeout(100)));
+    }
+
+    #[tokio::test]
+    async fn test_module_imports() {
+        let runtime = runtime_with_modules(
+            "",
+            vec![
+                module("math", "lib/math.js", "export const double = (x) => x * 2"),
+                module(
+                    "utils",
+                    "lib/utils.js",
+                    r#"import { double } from "./math.js"
+                    export const quadruple = (x) => double(double(x))"#,
+                ),
+                module(
+                    "orders",
+                    "orders.js",
+                    r#"import { quadruple } from "utils"
+                    export function total(value) {
+                      return JSON.stringify(quadruple(JSON.parse(value)))
+                    }"#,
+                ),
+            ],
+        );
+
+        let value = WorkerIO::<ConstValue, ConstValue>::call(
+            &runtime,
+            "orders.total",
+            ConstValue::from_json(serde_json::json!(2)).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            value,
+            Some(ConstValue::from_json(serde_json::json!(8)).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_location() {
+        let runtime = runtime_with_modules(
+            "",
+            vec![
+                module("users", "users.js", "export const name = () => 'user'"),
+                module(
+                    "orders",
+                    "orders.js",
+                    "export function total() {\n  throw new Error('boom')\n}",
+                ),
+            ],
+        );
+
+        let error = call_js(&runtime, "orders.total").await.unwrap_err();
+        let worker::Error::Rquickjs(message) = error else {
+            panic!("unexpected error: {error:?}");
+        };
+        assert!(message.contains("boom"), "{message}");
+        assert!(message.contains("orders.js:2"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn test_handler_error() {
+        let runtime = runtime(r#"async function onRequest() { throw new Error("boom") }"#);
+        let request =
+            reqwest::Request::new(reqwest::Method::GET, "http://localhost/".parse().unwrap());
+        let event = Event::Request(WorkerRequest::try_from(&request).unwrap());
+
+        let error = WorkerIO::<Event, Command>::call(&runtime, "onRequest", event)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, worker::Error::Rquickjs(message) if message.contains("boom")));
+    }
 }
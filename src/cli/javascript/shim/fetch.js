@@ -0,0 +1,50 @@
+class Response {
+  constructor({status, headers, body}) {
+    this.status = status
+    this.ok = status >= 200 && status < 300
+    this.headers = headers
+    this.body = body
+  }
+
+  text() {
+    return Promise.resolve(this.body)
+  }
+
+  json() {
+    return this.text().then((body) => JSON.parse(body))
+  }
+}
+
+const pendingFetches = new Map()
+let nextFetchId = 0
+
+function fetch(url, options = {}) {
+  return new Promise((resolve, reject) => {
+    const request = {
+      url: String(url),
+      method: options.method ?? "GET",
+      headers: options.headers ?? {},
+      body: options.body ?? null,
+    }
+    const id = nextFetchId++
+    pendingFetches.set(id, {resolve, reject})
+    globalThis.__qjs_fetch(id, JSON.stringify(request))
+  })
+}
+
+// Called by the runtime once the request sent by `__qjs_fetch` completes.
+function settleFetch(id, response, error) {
+  const pending = pendingFetches.get(id)
+  if (pending === undefined) {
+    return
+  }
+  pendingFetches.delete(id)
+  if (error == null) {
+    pending.resolve(new Response(JSON.parse(response)))
+  } else {
+    pending.reject(new Error(error))
+  }
+}
+
+globalThis.fetch = fetch
+globalThis.__qjs_settle_fetch = settleFetch
@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Result};
 
+use crate::core::metrics;
 use crate::core::runtime::TargetRuntime;
 
 fn cache_metrics(runtime: &TargetRuntime) -> Result<()> {
@@ -28,6 +29,7 @@ fn process_resources_metrics() -> Result<()> {
 pub fn init_metrics(runtime: &TargetRuntime) -> Result<()> {
     cache_metrics(runtime)?;
     process_resources_metrics()?;
+    metrics::enable();
 
     Ok(())
 }
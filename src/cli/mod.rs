@@ -10,4 +10,5 @@ pub mod server;
 mod tc;
 pub mod telemetry;
 pub(crate) mod update_checker;
+pub use tc::diff::exit_code;
 pub use tc::run::run;
@@ -1,15 +1,17 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use http_cache_reqwest::{Cache, CacheMode, HttpCache, HttpCacheOptions};
 use hyper::body::Bytes;
 use once_cell::sync::Lazy;
-use opentelemetry::metrics::Counter;
+use opentelemetry::metrics::{Counter, Histogram, Unit};
 use opentelemetry::trace::SpanKind;
 use opentelemetry::KeyValue;
 use opentelemetry_http::HeaderInjector;
 use opentelemetry_semantic_conventions::trace::{
-    HTTP_REQUEST_METHOD, HTTP_RESPONSE_STATUS_CODE, NETWORK_PROTOCOL_VERSION, URL_FULL,
+    HTTP_REQUEST_METHOD, HTTP_RESPONSE_STATUS_CODE, NETWORK_PROTOCOL_VERSION, SERVER_ADDRESS,
+    URL_FULL,
 };
 use reqwest::Client;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
@@ -18,7 +20,7 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use super::HttpIO;
 use crate::core::blueprint::telemetry::Telemetry;
-use crate::core::blueprint::Upstream;
+use crate::core::blueprint::{Tls, Upstream};
 use crate::core::http::Response;
 
 static HTTP_CLIENT_REQUEST_COUNT: Lazy<Counter<u64>> = Lazy::new(|| {
@@ -30,9 +32,22 @@ static HTTP_CLIENT_REQUEST_COUNT: Lazy<Counter<u64>> = Lazy::new(|| {
         .init()
 });
 
+static HTTP_CLIENT_REQUEST_DURATION: Lazy<Histogram<f64>> = Lazy::new(|| {
+    let meter = opentelemetry::global::meter("http_request");
+
+    meter
+        .f64_histogram("http.client.request.duration")
+        .with_description("Duration of the outgoing requests")
+        .with_unit(Unit::new("s"))
+        .init()
+});
+
 #[derive(Default)]
 struct RequestCounter {
     attributes: Option<Vec<KeyValue>>,
+    // Low cardinality attributes used for the duration histogram.
+    duration_attributes: Vec<KeyValue>,
+    started_at: Option<Instant>,
 }
 
 impl RequestCounter {
@@ -47,14 +62,35 @@ impl RequestCounter {
             KeyValue::new(NETWORK_PROTOCOL_VERSION, format!("{:?}", request.version())),
         ];
 
-        Self { attributes: Some(attributes) }
+        let duration_attributes = vec![
+            KeyValue::new(
+                SERVER_ADDRESS,
+                request.url().host_str().unwrap_or_default().to_string(),
+            ),
+            KeyValue::new(HTTP_REQUEST_METHOD, request.method().to_string()),
+        ];
+
+        Self {
+            attributes: Some(attributes),
+            duration_attributes,
+            started_at: Some(Instant::now()),
+        }
     }
 
     fn update(&mut self, response: &reqwest_middleware::Result<reqwest::Response>) {
         if let Some(ref mut attributes) = self.attributes {
-            attributes.push(get_response_status(response));
+            let status = get_response_status(response);
+            attributes.push(status.clone());
 
             HTTP_CLIENT_REQUEST_COUNT.add(1, attributes);
+
+            if let Some(started_at) = self.started_at {
+                self.duration_attributes.push(status);
+                HTTP_CLIENT_REQUEST_DURATION.record(
+                    started_at.elapsed().as_secs_f64(),
+                    &self.duration_attributes,
+                );
+            }
         }
     }
 }
@@ -70,6 +106,7 @@ fn get_response_status(response: &reqwest_middleware::Result<reqwest::Response>)
 #[derive(Clone)]
 pub struct NativeHttp {
     client: ClientWithMiddleware,
+    host_clients: HashMap<String, ClientWithMiddleware>,
     http2_only: bool,
     enable_telemetry: bool,
 }
@@ -78,6 +115,7 @@ impl Default for NativeHttp {
     fn default() -> Self {
         Self {
             client: ClientBuilder::new(Client::new()).build(),
+            host_clients: HashMap::new(),
             http2_only: false,
             enable_telemetry: false,
         }
@@ -86,6 +124,21 @@ impl Default for NativeHttp {
 
 impl NativeHttp {
     pub fn init(upstream: &Upstream, telemetry: &Telemetry) -> Self {
+        let host_clients = upstream
+            .host_tls
+            .iter()
+            .map(|(host, tls)| (host.clone(), Self::build_client(upstream, tls)))
+            .collect();
+
+        Self {
+            client: Self::build_client(upstream, &upstream.tls),
+            host_clients,
+            http2_only: upstream.http2_only,
+            enable_telemetry: telemetry.export.is_some(),
+        }
+    }
+
+    fn build_client(upstream: &Upstream, tls: &Tls) -> ClientWithMiddleware {
         let mut builder = Client::builder()
             .tcp_keepalive(Some(Duration::from_secs(upstream.tcp_keep_alive)))
             .timeout(Duration::from_secs(upstream.timeout))
@@ -111,6 +164,23 @@ impl NativeHttp {
             );
         }
 
+        // Add client certificate for mutual TLS
+        if let Some(ref identity) = tls.identity {
+            builder = builder.identity(
+                reqwest::Identity::from_pem(identity.as_bytes())
+                    .expect("Failed to set client certificate in http client"),
+            );
+        }
+
+        // Add trusted CA certificates
+        for ca in tls.ca_certs.iter() {
+            let certs = reqwest::Certificate::from_pem_bundle(ca.as_bytes())
+                .expect("Failed to set CA certificate in http client");
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
         let mut client = ClientBuilder::new(builder.build().expect("Failed to build client"));
 
         if upstream.http_cache > 0 {
@@ -120,11 +190,16 @@ impl NativeHttp {
                 options: HttpCacheOptions::default(),
             }))
         }
-        Self {
-            client: client.build(),
-            http2_only: upstream.http2_only,
-            enable_telemetry: telemetry.export.is_some(),
-        }
+
+        client.build()
+    }
+
+    fn client_for(&self, request: &reqwest::Request) -> &ClientWithMiddleware {
+        request
+            .url()
+            .host_str()
+            .and_then(|host| self.host_clients.get(host))
+            .unwrap_or(&self.client)
     }
 }
 
@@ -166,7 +241,7 @@ impl HttpIO for NativeHttp {
             request.version()
         );
         tracing::debug!("request: {:?}", request);
-        let response = self.client.execute(request).await;
+        let response = self.client_for(&request).execute(request).await;
         tracing::debug!("response: {:?}", response);
 
         req_counter.update(&response);
@@ -176,15 +251,29 @@ impl HttpIO for NativeHttp {
             tracing::Span::current().set_attribute(status_code.key, status_code.value);
         }
 
-        Ok(Response::from_reqwest(
-            response?
-                .error_for_status()
-                .map_err(|err| err.without_url())?,
-        )
-        .await?)
+        let response = response?;
+        let error = response
+            .error_for_status_ref()
+            .err()
+            .map(|err| err.without_url());
+        let response = Response::from_reqwest(response).await?;
+        match error {
+            Some(error) => Err(StatusError { response, error }.into()),
+            None => Ok(response),
+        }
     }
 }
 
+/// Error of the responses with a 4xx or 5xx status. It keeps the response, for
+/// the wrappers of the client that need more than the status, e.g. to record
+/// the upstream traffic.
+#[derive(Debug, thiserror::Error)]
+#[error("{error}")]
+pub(super) struct StatusError {
+    pub(super) response: Response<Bytes>,
+    error: reqwest::Error,
+}
+
 #[cfg(test)]
 mod tests {
     use reqwest::Method;
@@ -1,6 +1,7 @@
 mod env;
 mod file;
 mod http;
+mod traffic;
 
 use std::fs;
 use std::hash::Hash;
@@ -8,6 +9,8 @@ use std::sync::Arc;
 
 pub use http::NativeHttp;
 use inquire::{Confirm, Select};
+pub use traffic::Traffic;
+pub(crate) use traffic::{Exchange, ReplayHttp};
 
 use crate::core::blueprint::Blueprint;
 use crate::core::cache::InMemoryCache;
@@ -27,24 +30,26 @@ fn init_file() -> Arc<dyn FileIO> {
 
 fn init_http_worker_io(
     script: Option<blueprint::Script>,
+    http: Arc<dyn HttpIO>,
 ) -> Option<Arc<dyn WorkerIO<Event, Command>>> {
     #[cfg(feature = "js")]
-    return Some(super::javascript::init_worker_io(script?));
+    return Some(super::javascript::init_worker_io(script?, http));
     #[cfg(not(feature = "js"))]
     {
-        let _ = script;
+        let _ = (script, http);
         None
     }
 }
 
 fn init_resolver_worker_io(
     script: Option<blueprint::Script>,
+    http: Arc<dyn HttpIO>,
 ) -> Option<Arc<dyn WorkerIO<async_graphql::Value, async_graphql::Value>>> {
     #[cfg(feature = "js")]
-    return Some(super::javascript::init_worker_io(script?));
+    return Some(super::javascript::init_worker_io(script?, http));
     #[cfg(not(feature = "js"))]
     {
-        let _ = script;
+        let _ = (script, http);
         None
     }
 }
@@ -70,18 +75,44 @@ fn init_in_memory_cache<K: Hash + Eq, V: Clone>() -> InMemoryCache<K, V> {
 }
 
 pub fn init(blueprint: &Blueprint) -> TargetRuntime {
+    init_runtime(blueprint, init_http(blueprint), init_http2_only(blueprint))
+}
+
+/// Initializes the runtime with clients of the upstream that record, or
+/// replay, the upstream traffic.
+pub fn init_with_traffic(
+    blueprint: &Blueprint,
+    traffic: &Traffic,
+) -> anyhow::Result<TargetRuntime> {
+    let (http, http2_only) = traffic.init_http(init_http(blueprint), init_http2_only(blueprint))?;
+    Ok(init_runtime(blueprint, http, http2_only))
+}
+
+/// Initializes the runtime with the given client of the upstream.
+pub fn init_with_http(blueprint: &Blueprint, http: Arc<dyn HttpIO>) -> TargetRuntime {
+    init_runtime(blueprint, http.clone(), http)
+}
+
+fn init_runtime(
+    blueprint: &Blueprint,
+    http: Arc<dyn HttpIO>,
+    http2_only: Arc<dyn HttpIO>,
+) -> TargetRuntime {
     #[cfg(not(feature = "js"))]
     tracing::warn!("JS capabilities are disabled in this build");
 
+    let cmd_worker = init_http_worker_io(blueprint.server.script.clone(), http.clone());
+    let worker = init_resolver_worker_io(blueprint.server.script.clone(), http.clone());
+
     TargetRuntime {
-        http: init_http(blueprint),
-        http2_only: init_http2_only(blueprint),
+        http,
+        http2_only,
         env: init_env(),
         file: init_file(),
         cache: Arc::new(init_in_memory_cache()),
         extensions: Arc::new(vec![]),
-        cmd_worker: init_http_worker_io(blueprint.server.script.clone()),
-        worker: init_resolver_worker_io(blueprint.server.script.clone()),
+        cmd_worker,
+        worker,
     }
 }
 
@@ -0,0 +1,531 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use hyper::body::Bytes;
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE, SET_COOKIE};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::http::StatusError;
+use crate::core::generator::strip_credentials;
+use crate::core::http::Response;
+use crate::core::HttpIO;
+
+/// File of the directory passed to `--record` and `--replay` that holds the
+/// upstream traffic.
+const TRAFFIC_FILE: &str = "upstream.yml";
+
+/// Interval at which the recorded exchanges are written to the file.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Captures the upstream traffic to a directory, or serves it back from one.
+#[derive(Clone, Debug)]
+pub enum Traffic {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+impl Traffic {
+    /// Wraps the clients of the upstream, so that their traffic is recorded
+    /// to, or replayed from, a single file.
+    pub(super) fn init_http(
+        &self,
+        http: Arc<dyn HttpIO>,
+        http2_only: Arc<dyn HttpIO>,
+    ) -> Result<(Arc<dyn HttpIO>, Arc<dyn HttpIO>)> {
+        match self {
+            Traffic::Record(dir) => {
+                std::fs::create_dir_all(dir)?;
+                let recorder = Recorder::start(dir.join(TRAFFIC_FILE));
+                Ok((
+                    Arc::new(RecordingHttp { http, recorder: recorder.clone() }),
+                    Arc::new(RecordingHttp { http: http2_only, recorder }),
+                ))
+            }
+            Traffic::Replay(dir) => {
+                let replay: Arc<dyn HttpIO> = Arc::new(ReplayHttp::load(dir)?);
+                Ok((replay.clone(), replay))
+            }
+        }
+    }
+}
+
+/// Exchange with the upstream, in the format of the `@mock` blocks of the
+/// execution specs, so that the recordings can be pasted into them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Exchange {
+    request: RecordedRequest,
+    response: RecordedResponse,
+    #[serde(default = "default_hits", skip_serializing_if = "is_one")]
+    expected_hits: usize,
+}
+
+/// The headers of the requests aren't recorded, as they usually carry
+/// credentials, and neither are the query params that carry them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    body: Option<RecordedBody>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    body: Option<RecordedBody>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum RecordedBody {
+    #[serde(rename = "body")]
+    Json(Value),
+    /// Bytes that aren't JSON, with the escapes of the execution specs.
+    #[serde(rename = "textBody")]
+    Text(String),
+}
+
+fn default_hits() -> usize {
+    1
+}
+
+fn is_one(hits: &usize) -> bool {
+    *hits == 1
+}
+
+impl RecordedRequest {
+    fn new(request: &reqwest::Request) -> Self {
+        Self {
+            method: request.method().to_string(),
+            url: strip_credentials(request.url().clone()).to_string(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .filter(|body| !body.is_empty())
+                .map(RecordedBody::new),
+        }
+    }
+
+    /// Requests are matched on their method, url and body.
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method && self.url == other.url && self.body == other.body
+    }
+}
+
+impl RecordedResponse {
+    fn new(response: &Response<Bytes>) -> Self {
+        Self {
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter(|(name, _)| *name != CONTENT_TYPE && *name != SET_COOKIE)
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: (!response.body.is_empty()).then(|| RecordedBody::new(&response.body)),
+        }
+    }
+
+    fn to_response(&self) -> Result<Response<Bytes>> {
+        let mut response = Response {
+            status: reqwest::StatusCode::from_u16(self.status)?,
+            ..Default::default()
+        };
+        for (name, value) in self.headers.iter() {
+            response
+                .headers
+                .insert(HeaderName::from_str(name)?, HeaderValue::from_str(value)?);
+        }
+        if let Some(body) = self.body.as_ref() {
+            response.body = Bytes::from(body.to_bytes()?);
+        }
+
+        Ok(response)
+    }
+}
+
+impl RecordedBody {
+    fn new(bytes: &[u8]) -> Self {
+        // JSON is kept readable only when it's written back to the same bytes,
+        // since the execution specs compare the bodies byte by byte
+        match serde_json::from_slice::<Value>(bytes) {
+            Ok(value) if serde_json::to_vec(&value).ok().as_deref() == Some(bytes) => {
+                RecordedBody::Json(value)
+            }
+            _ => RecordedBody::Text(escape(bytes)),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            RecordedBody::Json(value) => Ok(serde_json::to_vec(value)?),
+            RecordedBody::Text(text) => unescape(text),
+        }
+    }
+}
+
+/// Escapes the bytes that aren't printable ASCII, e.g. the frames of gRPC.
+fn escape(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            0 => text.push_str("\\0"),
+            b'\n' => text.push_str("\\n"),
+            b'\t' => text.push_str("\\t"),
+            b'\r' => text.push_str("\\r"),
+            b'\\' => text.push_str("\\\\"),
+            b'"' => text.push_str("\\\""),
+            byte if byte.is_ascii_graphic() || byte == b' ' => text.push(byte as char),
+            byte => text.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(char.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('0') => bytes.push(0),
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('r') => bytes.push(b'\r'),
+            Some('\\') => bytes.push(b'\\'),
+            Some('"') => bytes.push(b'"'),
+            Some('x') => {
+                let hex = chars.by_ref().take(2).collect::<String>();
+                bytes.push(u8::from_str_radix(&hex, 16)?);
+            }
+            escape => return Err(anyhow!("Unsupported escape sequence: \\{escape:?}")),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Exchanges recorded so far. They are kept in memory and written to the file
+/// in the background, so that recording doesn't slow down the upstream calls,
+/// and once more when the server is interrupted or the recorder is dropped.
+struct Recorder {
+    path: PathBuf,
+    exchanges: Mutex<Vec<Exchange>>,
+    // Whether exchanges were recorded since the last write.
+    dirty: AtomicBool,
+    // Held while writing, so that the writes don't interleave.
+    file: tokio::sync::Mutex<()>,
+}
+
+impl Recorder {
+    /// Creates a recorder that writes its exchanges to the file every
+    /// [FLUSH_INTERVAL], for as long as it's in use, and before exiting on
+    /// Ctrl+C.
+    fn start(path: PathBuf) -> Arc<Self> {
+        let recorder = Arc::new(Self::new(path));
+        let weak = Arc::downgrade(&recorder);
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            if let Some(recorder) = weak.upgrade() {
+                if let Err(err) = recorder.flush().await {
+                    tracing::warn!("Failed to record the upstream traffic: {err}");
+                }
+            }
+            std::process::exit(exitcode::OK);
+        });
+
+        let weak = Arc::downgrade(&recorder);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(recorder) = weak.upgrade() else {
+                    break;
+                };
+                if let Err(err) = recorder.flush().await {
+                    tracing::warn!("Failed to record the upstream traffic: {err}");
+                }
+            }
+        });
+
+        recorder
+    }
+
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            exchanges: Mutex::new(vec![]),
+            dirty: AtomicBool::new(false),
+            file: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn record(&self, request: RecordedRequest, response: RecordedResponse) {
+        let mut exchanges = self.exchanges.lock().unwrap();
+        match exchanges
+            .iter_mut()
+            .find(|exchange| exchange.request == request && exchange.response == response)
+        {
+            Some(exchange) => exchange.expected_hits += 1,
+            None => exchanges.push(Exchange { request, response, expected_hits: 1 }),
+        }
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn content(&self) -> Result<String> {
+        Ok(serde_yaml_ng::to_string(&*self.exchanges.lock().unwrap())?)
+    }
+
+    /// Writes the exchanges to the file, if any was recorded since the last
+    /// write.
+    async fn flush(&self) -> Result<()> {
+        let _file = self.file.lock().await;
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        tokio::fs::write(&self.path, self.content()?).await?;
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if !self.dirty.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let result = self
+            .content()
+            .and_then(|content| Ok(std::fs::write(&self.path, content)?));
+        if let Err(err) = result {
+            tracing::warn!("Failed to record the upstream traffic: {err}");
+        }
+    }
+}
+
+/// Calls the upstream and records the exchanges, including the ones that
+/// failed with a 4xx or 5xx status.
+struct RecordingHttp {
+    http: Arc<dyn HttpIO>,
+    recorder: Arc<Recorder>,
+}
+
+#[async_trait::async_trait]
+impl HttpIO for RecordingHttp {
+    async fn execute(&self, request: reqwest::Request) -> Result<Response<Bytes>> {
+        let recorded_request = RecordedRequest::new(&request);
+        let result = self.http.execute(request).await;
+
+        let response = match &result {
+            Ok(response) => Some(response),
+            Err(err) => err.downcast_ref::<StatusError>().map(|err| &err.response),
+        };
+        if let Some(response) = response {
+            self.recorder
+                .record(recorded_request, RecordedResponse::new(response));
+        }
+
+        result
+    }
+}
+
+/// Serves the recorded responses instead of calling the upstream.
+pub(crate) struct ReplayHttp {
+    exchanges: Vec<Exchange>,
+    hits: Vec<AtomicUsize>,
+}
+
+impl ReplayHttp {
+    pub(crate) fn new(exchanges: Vec<Exchange>) -> Self {
+        let hits = exchanges.iter().map(|_| AtomicUsize::new(0)).collect();
+        Self { exchanges, hits }
+    }
+
+    /// Describes the exchanges that weren't served as many times as expected.
+    pub(crate) fn unexpected_hits(&self) -> Vec<String> {
+        self.exchanges
+            .iter()
+            .zip(self.hits.iter())
+            .filter_map(|(exchange, hits)| {
+                let hits = hits.load(Ordering::Relaxed);
+                (hits != exchange.expected_hits).then(|| {
+                    format!(
+                        "{} {} was requested {hits} time(s), expected {}",
+                        exchange.request.method, exchange.request.url, exchange.expected_hits
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(TRAFFIC_FILE);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|err| anyhow!("Failed to read {}: {err}", path.display()))?;
+        let exchanges: Vec<Exchange> = serde_yaml_ng::from_str(&content)?;
+        tracing::info!(
+            "Replaying {} upstream exchange(s) from {}",
+            exchanges.len(),
+            path.display()
+        );
+
+        Ok(Self::new(exchanges))
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpIO for ReplayHttp {
+    async fn execute(&self, request: reqwest::Request) -> Result<Response<Bytes>> {
+        let request = RecordedRequest::new(&request);
+        let index = self
+            .exchanges
+            .iter()
+            .position(|exchange| exchange.request.matches(&request))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No recorded response for request: {} {}",
+                    request.method,
+                    request.url
+                )
+            })?;
+        self.hits[index].fetch_add(1, Ordering::Relaxed);
+
+        let response = self.exchanges[index].response.to_response()?;
+        // fail like the upstream client does
+        if response.status.is_client_error() || response.status.is_server_error() {
+            return Err(anyhow!("Upstream responded with {}", response.status));
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::runtime::NativeHttp;
+
+    struct Upstream;
+
+    #[async_trait::async_trait]
+    impl HttpIO for Upstream {
+        async fn execute(&self, request: reqwest::Request) -> Result<Response<Bytes>> {
+            let mut response = Response {
+                body: Bytes::from(format!(r#"{{"path":"{}"}}"#, request.url().path())),
+                ..Default::default()
+            };
+            response
+                .headers
+                .insert(SET_COOKIE, HeaderValue::from_static("session=secret"));
+            response
+                .headers
+                .insert("x-request-id", HeaderValue::from_static("1"));
+            Ok(response)
+        }
+    }
+
+    fn request(url: &str) -> reqwest::Request {
+        reqwest::Request::new(reqwest::Method::GET, url.parse().unwrap())
+    }
+
+    #[test]
+    fn test_escape() {
+        let bytes = b"\0\0\0\0\x05\n\x03abc \"\\";
+        assert_eq!(escape(bytes), r#"\0\0\0\0\x05\n\x03abc \"\\"#);
+        assert_eq!(unescape(&escape(bytes)).unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = Arc::new(Recorder::new(dir.path().join(TRAFFIC_FILE)));
+        let http = RecordingHttp { http: Arc::new(Upstream), recorder: recorder.clone() };
+        for url in [
+            "http://localhost/a",
+            "http://localhost/b",
+            "http://localhost/a",
+        ] {
+            let mut request = request(url);
+            request
+                .headers_mut()
+                .insert("authorization", HeaderValue::from_static("Bearer secret"));
+            http.execute(request).await.unwrap();
+        }
+        recorder.flush().await.unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(TRAFFIC_FILE)).unwrap();
+        assert!(!content.contains("secret"));
+        let exchanges: Vec<Exchange> = serde_yaml_ng::from_str(&content).unwrap();
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0].expected_hits, 2);
+        assert_eq!(
+            exchanges[0].response.headers,
+            BTreeMap::from([("x-request-id".to_string(), "1".to_string())])
+        );
+
+        let replay = ReplayHttp::load(dir.path()).unwrap();
+        let response = replay.execute(request("http://localhost/b")).await.unwrap();
+        assert_eq!(response.body, Bytes::from(r#"{"path":"/b"}"#));
+        assert!(replay.execute(request("http://localhost/c")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_failed_exchange() {
+        let server = httpmock::MockServer::start();
+        server.mock(|when, then| {
+            when.path("/missing");
+            then.status(404).body("not found");
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = Arc::new(Recorder::new(dir.path().join(TRAFFIC_FILE)));
+        let http = RecordingHttp {
+            http: Arc::new(NativeHttp::init(&Default::default(), &Default::default())),
+            recorder: recorder.clone(),
+        };
+        let url = format!("http://localhost:{}/missing", server.port());
+        let request = request(&format!("{url}?page=1&api_key=secret"));
+        assert!(http.execute(request).await.is_err());
+
+        let exchanges = recorder.exchanges.lock().unwrap();
+        assert_eq!(exchanges.len(), 1);
+        assert_eq!(exchanges[0].request.url, format!("{url}?page=1"));
+        assert_eq!(exchanges[0].response.status, 404);
+        assert_eq!(
+            exchanges[0].response.body,
+            Some(RecordedBody::Text("not found".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recorder_flushes_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TRAFFIC_FILE);
+        let recorder = Recorder::new(path.clone());
+        recorder.record(
+            RecordedRequest::new(&request("http://localhost/a")),
+            RecordedResponse::new(&Response::default()),
+        );
+        drop(recorder);
+
+        let content = std::fs::read_to_string(path).unwrap();
+        let exchanges: Vec<Exchange> = serde_yaml_ng::from_str(&content).unwrap();
+        assert_eq!(exchanges.len(), 1);
+    }
+}
@@ -1,18 +1,26 @@
 #![allow(clippy::too_many_arguments)]
 use std::sync::Arc;
+use std::time::Duration;
 
-use hyper::server::conn::AddrIncoming;
-use hyper::service::{make_service_fn, service_fn};
-use hyper::Server;
-use hyper_rustls::TlsAcceptor;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request};
+use rustls::server::WebPkiClientVerifier;
+use rustls::RootCertStore;
 use rustls_pki_types::CertificateDer;
+use tokio::net::TcpListener;
 use tokio::sync::oneshot;
+use tokio_rustls::TlsAcceptor;
 
 use super::server_config::ServerConfig;
 use crate::core::async_graphql_hyper::{GraphQLBatchRequest, GraphQLRequest};
+use crate::core::auth::client_cert::ClientIdentity;
 use crate::core::config::PrivateKey;
 use crate::core::http::handle_request;
-use crate::core::Errata;
+
+/// Longest wait before accepting connections again after a failure, e.g. when
+/// the process ran out of file descriptors.
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
 
 pub async fn start_http_2(
     sc: Arc<ServerConfig>,
@@ -21,30 +29,8 @@ pub async fn start_http_2(
     server_up_sender: Option<oneshot::Sender<()>>,
 ) -> anyhow::Result<()> {
     let addr = sc.addr();
-    let incoming = AddrIncoming::bind(&addr)?;
-    let acceptor = TlsAcceptor::builder()
-        .with_single_cert(cert, key.into_inner())?
-        .with_http2_alpn()
-        .with_incoming(incoming);
-    let make_svc_single_req = make_service_fn(|_conn| {
-        let state = Arc::clone(&sc);
-        async move {
-            Ok::<_, anyhow::Error>(service_fn(move |req| {
-                handle_request::<GraphQLRequest>(req, state.app_ctx.clone())
-            }))
-        }
-    });
-
-    let make_svc_batch_req = make_service_fn(|_conn| {
-        let state = Arc::clone(&sc);
-        async move {
-            Ok::<_, anyhow::Error>(service_fn(move |req| {
-                handle_request::<GraphQLBatchRequest>(req, state.app_ctx.clone())
-            }))
-        }
-    });
-
-    let builder = Server::builder(acceptor).http2_only(true);
+    let listener = TcpListener::bind(&addr).await?;
+    let acceptor = TlsAcceptor::from(Arc::new(tls_config(sc.as_ref(), cert, key)?));
 
     super::log_launch(sc.as_ref());
 
@@ -54,14 +40,97 @@ pub async fn start_http_2(
             .or(Err(anyhow::anyhow!("Failed to send message")))?;
     }
 
-    let server: std::prelude::v1::Result<(), hyper::Error> =
-        if sc.blueprint.server.enable_batch_requests {
-            builder.serve(make_svc_batch_req).await
-        } else {
-            builder.serve(make_svc_single_req).await
+    let mut backoff = Duration::ZERO;
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => {
+                backoff = Duration::ZERO;
+                stream
+            }
+            Err(e) => {
+                // The errors usually persist for a while, so retrying right away
+                // would only spin.
+                backoff = (backoff * 2).clamp(Duration::from_millis(5), MAX_ACCEPT_BACKOFF);
+                tracing::error!("Failed to accept connection: {}", e);
+                tokio::time::sleep(backoff).await;
+                continue;
+            }
         };
+        let acceptor = acceptor.clone();
+        let sc = Arc::clone(&sc);
+
+        tokio::spawn(async move {
+            // The handshake is completed before serving the connection, so that the
+            // verified client certificate is available to every request.
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::debug!("TLS handshake failed: {}", e);
+                    return;
+                }
+            };
+
+            let client_identity = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(|cert| ClientIdentity::from_der(cert));
+
+            let service = service_fn(move |mut req: Request<Body>| {
+                if let Some(ref identity) = client_identity {
+                    req.extensions_mut().insert(identity.clone());
+                }
+
+                let app_ctx = sc.app_ctx.clone();
+                let enable_batch_requests = sc.blueprint.server.enable_batch_requests;
+                async move {
+                    if enable_batch_requests {
+                        handle_request::<GraphQLBatchRequest>(req, app_ctx).await
+                    } else {
+                        handle_request::<GraphQLRequest>(req, app_ctx).await
+                    }
+                }
+            });
+
+            if let Err(e) = Http::new()
+                .http2_only(true)
+                .serve_connection(stream, service)
+                .await
+            {
+                tracing::debug!("Failed to serve connection: {}", e);
+            }
+        });
+    }
+}
+
+fn tls_config(
+    sc: &ServerConfig,
+    cert: Vec<CertificateDer<'static>>,
+    key: PrivateKey,
+) -> anyhow::Result<rustls::ServerConfig> {
+    let builder = rustls::ServerConfig::builder();
+    let builder = match sc.blueprint.server.client_auth {
+        Some(ref client_auth) => {
+            let mut roots = RootCertStore::empty();
+            for ca in client_auth.ca.iter().cloned() {
+                roots.add(ca)?;
+            }
+
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+            let verifier = if client_auth.required {
+                verifier
+            } else {
+                verifier.allow_unauthenticated()
+            };
+
+            builder.with_client_cert_verifier(verifier.build()?)
+        }
+        None => builder.with_no_client_auth(),
+    };
 
-    let result = server.map_err(Errata::from);
+    let mut config = builder.with_single_cert(cert, key.into_inner())?;
+    config.alpn_protocols = vec![b"h2".to_vec()];
 
-    Ok(result?)
+    Ok(config)
 }
@@ -7,6 +7,7 @@ use tokio::sync::oneshot::{self};
 use super::http_1::start_http_1;
 use super::http_2::start_http_2;
 use super::server_config::ServerConfig;
+use crate::cli::runtime::Traffic;
 use crate::cli::telemetry::init_opentelemetry;
 use crate::core::blueprint::{Blueprint, Http};
 use crate::core::config::ConfigModule;
@@ -15,11 +16,18 @@ use crate::core::Errata;
 pub struct Server {
     config_module: ConfigModule,
     server_up_sender: Option<oneshot::Sender<()>>,
+    traffic: Option<Traffic>,
 }
 
 impl Server {
     pub fn new(config_module: ConfigModule) -> Self {
-        Self { config_module, server_up_sender: None }
+        Self { config_module, server_up_sender: None, traffic: None }
+    }
+
+    /// Records the upstream traffic to a directory, or replays it from one.
+    pub fn traffic(mut self, traffic: Option<Traffic>) -> Self {
+        self.traffic = traffic;
+        self
     }
 
     pub fn server_up_receiver(&mut self) -> oneshot::Receiver<()> {
@@ -34,7 +42,9 @@ impl Server {
     pub async fn start(self) -> Result<()> {
         let blueprint = Blueprint::try_from(&self.config_module).map_err(Errata::from)?;
         let endpoints = self.config_module.extensions().endpoint_set.clone();
-        let server_config = Arc::new(ServerConfig::new(blueprint.clone(), endpoints).await?);
+        let server_config = Arc::new(
+            ServerConfig::with_traffic(blueprint.clone(), endpoints, self.traffic.as_ref()).await?,
+        );
 
         init_opentelemetry(blueprint.telemetry.clone(), &server_config.app_ctx.runtime)?;
 
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use async_graphql_extension_apollo_tracing::ApolloTracing;
 
-use crate::cli::runtime::init;
+use crate::cli::runtime::{init, init_with_traffic, Traffic};
 use crate::core::app_context::AppContext;
 use crate::core::blueprint::telemetry::TelemetryExporter;
 use crate::core::blueprint::{Blueprint, Http};
@@ -20,7 +20,20 @@ impl ServerConfig {
         blueprint: Blueprint,
         endpoints: EndpointSet<Unchecked>,
     ) -> anyhow::Result<Self> {
-        let mut rt = init(&blueprint);
+        Self::with_traffic(blueprint, endpoints, None).await
+    }
+
+    /// Creates the config of a server whose upstream traffic is recorded or
+    /// replayed.
+    pub async fn with_traffic(
+        blueprint: Blueprint,
+        endpoints: EndpointSet<Unchecked>,
+        traffic: Option<&Traffic>,
+    ) -> anyhow::Result<Self> {
+        let mut rt = match traffic {
+            Some(traffic) => init_with_traffic(&blueprint, traffic)?,
+            None => init(&blueprint),
+        };
 
         let mut extensions = vec![];
 
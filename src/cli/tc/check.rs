@@ -1,6 +1,8 @@
 use anyhow::Result;
 
-use super::helpers::{display_schema, log_endpoint_set};
+use super::helpers::{display_client_types, display_openapi, display_schema, log_endpoint_set};
+use super::lint::lint_command;
+use crate::cli::command::{EmitFormat, LintFormat};
 use crate::cli::fmt::Fmt;
 use crate::core::blueprint::Blueprint;
 use crate::core::config::reader::ConfigReader;
@@ -11,11 +13,27 @@ pub(super) struct CheckParams {
     pub(super) file_paths: Vec<String>,
     pub(super) n_plus_one_queries: bool,
     pub(super) schema: bool,
+    pub(super) openapi: bool,
+    pub(super) emit: Option<EmitFormat>,
+    pub(super) lint: Option<LintParams>,
     pub(super) runtime: TargetRuntime,
 }
 
+pub(super) struct LintParams {
+    pub(super) config: Option<String>,
+    pub(super) format: LintFormat,
+}
+
 pub(super) async fn check_command(params: CheckParams, config_reader: &ConfigReader) -> Result<()> {
-    let CheckParams { file_paths, n_plus_one_queries, schema, runtime } = params;
+    let CheckParams {
+        file_paths,
+        n_plus_one_queries,
+        schema,
+        openapi,
+        emit,
+        lint,
+        runtime,
+    } = params;
 
     let config_module = (config_reader.read_all(&file_paths)).await?;
     log_endpoint_set(&config_module.extensions().endpoint_set);
@@ -26,15 +44,25 @@ pub(super) async fn check_command(params: CheckParams, config_reader: &ConfigRea
             tracing::info!("Config {} ... ok", file_paths.join(", "));
             Fmt::log_n_plus_one(n_plus_one_queries, config_module.config());
             // Check the endpoints' schema
-            let _ = config_module
+            let endpoints = config_module
                 .extensions()
                 .endpoint_set
                 .clone()
-                .into_checked(&blueprint, runtime)
+                .into_checked(&blueprint, runtime.clone())
                 .await?;
             if schema {
                 display_schema(&blueprint);
             }
+            if openapi {
+                display_openapi(&endpoints, &blueprint)?;
+            }
+            if let Some(format) = emit {
+                let operations = &config_module.extensions().operations;
+                display_client_types(&endpoints, operations, &blueprint, format)?;
+            }
+            if let Some(LintParams { config, format }) = lint {
+                lint_command(config_module.config(), config, format, &runtime).await?;
+            }
 
             Ok(())
         }
@@ -0,0 +1,139 @@
+use anyhow::Result;
+
+use crate::cli::command::FailOn;
+use crate::cli::fmt::Fmt;
+use crate::core::blueprint::{Blueprint, Change, Severity};
+use crate::core::config::reader::ConfigReader;
+use crate::core::Errata;
+
+/// Exit code of `tailcall diff` when it finds changes of the `--fail-on`
+/// severity, so that CI can tell them apart from invalid configurations,
+/// which exit with `exitcode::CONFIG`.
+pub const CHANGES_FOUND: i32 = 1;
+
+/// Error of `tailcall diff` when it finds changes of the `--fail-on` severity.
+#[derive(Debug, thiserror::Error)]
+#[error("Found {0} change(s) that fail the diff")]
+pub struct ChangesFound(usize);
+
+pub(super) async fn diff_command(
+    old: &str,
+    new: &str,
+    fail_on: FailOn,
+    config_reader: &ConfigReader,
+) -> Result<()> {
+    let old = read_blueprint(old, config_reader).await?;
+    let new = read_blueprint(new, config_reader).await?;
+    let changes = old.diff(&new);
+
+    if changes.is_empty() {
+        tracing::info!("No changes detected");
+        return Ok(());
+    }
+
+    display_changes(&changes);
+
+    Ok(check_changes(&changes, fail_on)?)
+}
+
+fn check_changes(changes: &[Change], fail_on: FailOn) -> Result<(), ChangesFound> {
+    let fail_on = match fail_on {
+        FailOn::Breaking => Some(Severity::Breaking),
+        FailOn::Dangerous => Some(Severity::Dangerous),
+        FailOn::Never => None,
+    };
+    let failed = changes
+        .iter()
+        .filter(|change| fail_on.is_some_and(|fail_on| change.severity <= fail_on))
+        .count();
+
+    if failed > 0 {
+        Err(ChangesFound(failed))
+    } else {
+        Ok(())
+    }
+}
+
+/// Exit code of the CLI for the error of a command.
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    if error.is::<ChangesFound>() {
+        CHANGES_FOUND
+    } else {
+        exitcode::CONFIG
+    }
+}
+
+async fn read_blueprint(path: &str, config_reader: &ConfigReader) -> Result<Blueprint> {
+    let config_module = config_reader.read_all(&[path.to_string()]).await?;
+    Ok(Blueprint::try_from(&config_module).map_err(Errata::from)?)
+}
+
+fn display_changes(changes: &[Change]) {
+    for severity in [Severity::Breaking, Severity::Dangerous, Severity::Safe] {
+        let changes = changes
+            .iter()
+            .filter(|change| change.severity == severity)
+            .collect::<Vec<_>>();
+        if changes.is_empty() {
+            continue;
+        }
+
+        Fmt::display(Fmt::heading(&format!(
+            "{severity} changes ({}):",
+            changes.len()
+        )));
+        for change in changes {
+            Fmt::display(format!("  {} {}", Fmt::meta(&change.path), change.message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    fn blueprint(sdl: &str) -> Blueprint {
+        let config = Config::from_sdl(sdl).to_result().unwrap();
+        Blueprint::try_from(&ConfigModule::from(config)).unwrap()
+    }
+
+    fn changes() -> Vec<Change> {
+        let old = blueprint(
+            r#"
+            schema { query: Query }
+            type Query { user: String @expr(body: "user") name: String @expr(body: "name") }
+            "#,
+        );
+        let new = blueprint(
+            r#"
+            schema { query: Query }
+            type Query { user: String @expr(body: "user") }
+            "#,
+        );
+        old.diff(&new)
+    }
+
+    #[test]
+    fn test_check_changes() {
+        let changes = changes();
+
+        assert!(check_changes(&changes, FailOn::Breaking).is_err());
+        assert!(check_changes(&changes, FailOn::Never).is_ok());
+        assert!(check_changes(&[], FailOn::Dangerous).is_ok());
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let error = check_changes(&changes(), FailOn::Breaking).unwrap_err();
+
+        assert_eq!(exit_code(&error.into()), CHANGES_FOUND);
+        assert_eq!(
+            exit_code(&anyhow::anyhow!("Invalid config")),
+            exitcode::CONFIG
+        );
+        assert_ne!(CHANGES_FOUND, exitcode::CONFIG);
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use async_graphql::parser::parse_schema;
+use async_graphql::parser::types::{
+    ConstDirective, EnumType, InputObjectType, InterfaceType, ObjectType, ServiceDocument,
+    TypeKind, TypeSystemDefinition,
+};
+use async_graphql::Positioned;
+
+use crate::cli::fmt::Fmt;
+use crate::core::config::{Config, Source};
+use crate::core::document::print;
+use crate::core::runtime::TargetRuntime;
+
+pub(super) async fn fmt_command(
+    file_paths: &[String],
+    to: Option<Source>,
+    check: bool,
+    runtime: TargetRuntime,
+) -> Result<()> {
+    let mut unformatted = vec![];
+
+    for path in file_paths {
+        let source = Source::detect(path)?;
+        let target = to.clone().unwrap_or(source.clone());
+        let content = runtime.file.read(path).await?;
+        let output = format(&content, source, target.clone())?;
+
+        if check {
+            if output != content {
+                unformatted.push(path.as_str());
+            }
+            continue;
+        }
+
+        let output_path = Path::new(path)
+            .with_extension(target.ext())
+            .to_string_lossy()
+            .to_string();
+        if output_path == *path {
+            if output != content {
+                runtime.file.write(path, output.as_bytes()).await?;
+                Fmt::display(format!("Formatted {path}"));
+            }
+        } else {
+            if Path::new(&output_path).exists() {
+                return Err(anyhow!("{output_path} already exists"));
+            }
+            runtime.file.write(&output_path, output.as_bytes()).await?;
+            Fmt::display(format!("Converted {path} to {output_path}"));
+        }
+    }
+
+    if !unformatted.is_empty() {
+        Fmt::display(Fmt::heading("Not formatted:"));
+        for path in unformatted.iter() {
+            Fmt::display(format!("  {path}"));
+        }
+        return Err(anyhow!("{} file(s) aren't formatted", unformatted.len()));
+    }
+
+    Ok(())
+}
+
+/// Formats the configuration in the target format, failing when it can't be
+/// read back as the same configuration.
+fn format(content: &str, source: Source, target: Source) -> Result<String> {
+    let config = Config::from_source(source.clone(), content)?;
+    let output = match (source, target.clone()) {
+        (Source::GraphQL, Source::GraphQL) => to_sdl_in_order(&config, content)?,
+        (_, target) => config.to_source(target)?,
+    };
+
+    if Config::from_source(target.clone(), &output)? != config {
+        return Err(anyhow!(
+            "The configuration can't be written as {target} without changes"
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Prints the configuration as GraphQL, with the directives in the order of
+/// the formatted file, since the configuration doesn't keep their order.
+fn to_sdl_in_order(config: &Config, content: &str) -> Result<String> {
+    let mut source = parse_schema(content)?;
+    let order = directive_lists(&mut source)
+        .into_iter()
+        .map(|(key, directives)| {
+            let names = directives
+                .iter()
+                .map(|directive| directive.node.name.node.to_string())
+                .collect::<Vec<_>>();
+            (key, names)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut document = ServiceDocument::from(config);
+    for (key, directives) in directive_lists(&mut document) {
+        if let Some(names) = order.get(&key) {
+            directives.sort_by_key(|directive| {
+                names
+                    .iter()
+                    .position(|name| name == directive.node.name.node.as_str())
+                    .unwrap_or(names.len())
+            });
+        }
+    }
+
+    Ok(format!("{}\n", print(document).trim_end()))
+}
+
+/// Directives of the schema, types, fields and enum values of the document,
+/// keyed by the path of their definition, e.g. `Query.users`.
+fn directive_lists(
+    document: &mut ServiceDocument,
+) -> Vec<(String, &mut Vec<Positioned<ConstDirective>>)> {
+    let mut lists = vec![];
+    for definition in document.definitions.iter_mut() {
+        match definition {
+            TypeSystemDefinition::Schema(schema) => {
+                lists.push(("schema".to_string(), &mut schema.node.directives));
+            }
+            TypeSystemDefinition::Type(type_def) => {
+                let type_def = &mut type_def.node;
+                let name = type_def.name.node.to_string();
+                match &mut type_def.kind {
+                    TypeKind::Object(ObjectType { fields, .. })
+                    | TypeKind::Interface(InterfaceType { fields, .. }) => {
+                        for field in fields.iter_mut() {
+                            let key = format!("{name}.{}", field.node.name.node);
+                            lists.push((key, &mut field.node.directives));
+                        }
+                    }
+                    TypeKind::InputObject(InputObjectType { fields, .. }) => {
+                        for field in fields.iter_mut() {
+                            let key = format!("{name}.{}", field.node.name.node);
+                            lists.push((key, &mut field.node.directives));
+                        }
+                    }
+                    TypeKind::Enum(EnumType { values, .. }) => {
+                        for value in values.iter_mut() {
+                            let key = format!("{name}.{}", value.node.value.node);
+                            lists.push((key, &mut value.node.directives));
+                        }
+                    }
+                    TypeKind::Scalar | TypeKind::Union(_) => {}
+                }
+                lists.push((name, &mut type_def.directives));
+            }
+            TypeSystemDefinition::Directive(_) => {}
+        }
+    }
+    lists
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let sdl = "type Query { foo: String @expr(body: \"foo\") }\nschema { query: Query }";
+        let output = format(sdl, Source::GraphQL, Source::GraphQL).unwrap();
+
+        assert!(output.starts_with("schema"));
+        assert_eq!(
+            format(&output, Source::GraphQL, Source::GraphQL).unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_the_directive_order() {
+        let sdl = r#"
+            schema @upstream(batch: {delay: 10}) @server(port: 8000) {
+              query: Query
+            }
+
+            type Query {
+              foo: String @cache(maxAge: 100) @http(url: "http://localhost/foo")
+            }
+        "#;
+        let output = format(sdl, Source::GraphQL, Source::GraphQL).unwrap();
+
+        assert!(output.find("@upstream") < output.find("@server"));
+        assert!(output.find("@cache") < output.find("@http"));
+        assert_eq!(
+            format(&output, Source::GraphQL, Source::GraphQL).unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn test_format_to_yaml() {
+        let json = r#"{"server": {"port": 8000}}"#;
+        let output = format(json, Source::Json, Source::Yml).unwrap();
+
+        assert!(output.contains("port: 8000"));
+        assert_eq!(
+            format(&output, Source::Yml, Source::Json).unwrap(),
+            format(json, Source::Json, Source::Json).unwrap()
+        );
+    }
+}
@@ -1,11 +1,89 @@
+use std::time::{Duration, SystemTime};
+
 use anyhow::Result;
 
 use crate::cli::generator::Generator;
 use crate::core::runtime::TargetRuntime;
 
-pub(super) async fn gen_command(file_path: &str, runtime: TargetRuntime) -> Result<()> {
+/// Interval at which the inputs are checked for changes in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+pub(super) async fn gen_command(
+    file_path: &str,
+    watch: bool,
+    merge: bool,
+    runtime: TargetRuntime,
+) -> Result<()> {
+    // the inputs are read before generating, so that the changes made meanwhile
+    // trigger a regeneration.
+    let watched = if watch {
+        let paths = Generator::new(file_path, runtime.clone())
+            .input_paths()
+            .await?;
+        let modified = modified_times(&paths);
+        Some((paths, modified))
+    } else {
+        None
+    };
+
     Generator::new(file_path, runtime.clone())
+        .merge(merge)
         .generate()
         .await?;
+
+    if let Some((paths, modified)) = watched {
+        watch_inputs(file_path, merge, runtime, paths, modified).await?;
+    }
     Ok(())
 }
+
+/// Regenerates the configuration each time one of its inputs changes. The
+/// errors are reported without stopping, as the inputs are likely being
+/// edited.
+async fn watch_inputs(
+    file_path: &str,
+    merge: bool,
+    runtime: TargetRuntime,
+    mut paths: Vec<String>,
+    mut modified: Vec<Option<SystemTime>>,
+) -> Result<()> {
+    tracing::info!("Watching {} file(s) for changes", paths.len());
+
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        let current = modified_times(&paths);
+        if current == modified {
+            continue;
+        }
+
+        // the inputs of the generator may have changed too
+        if let Ok(new_paths) = Generator::new(file_path, runtime.clone())
+            .input_paths()
+            .await
+        {
+            paths = new_paths;
+        }
+        modified = modified_times(&paths);
+
+        tracing::info!("Change detected, regenerating");
+        let generated = Generator::new(file_path, runtime.clone())
+            .merge(merge)
+            .overwrite(true)
+            .generate()
+            .await;
+        if let Err(err) = generated {
+            tracing::error!("{err}");
+        }
+    }
+}
+
+fn modified_times(paths: &[String]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+        })
+        .collect()
+}
@@ -1,10 +1,12 @@
+use async_graphql::parser::types::ExecutableDocument;
 use lazy_static::lazy_static;
 
+use crate::cli::command::EmitFormat;
 use crate::cli::fmt::Fmt;
 use crate::core::blueprint::Blueprint;
-use crate::core::http::API_URL_PREFIX;
+use crate::core::codegen::{named_operations, ClientTypes};
 use crate::core::print_schema;
-use crate::core::rest::{EndpointSet, Unchecked};
+use crate::core::rest::{Checked, EndpointSet, Unchecked};
 
 pub const TAILCALL_RC: &str = ".tailcallrc.graphql";
 pub const GRAPHQL_RC: &str = ".graphqlrc.yml";
@@ -29,7 +31,7 @@ pub(super) fn log_endpoint_set(endpoint_set: &EndpointSet<Unchecked>) {
         tracing::info!(
             "Endpoint: {} {}{} ... ok",
             endpoint.get_method(),
-            API_URL_PREFIX,
+            endpoint.get_prefix(),
             endpoint.get_path().as_str()
         );
     }
@@ -40,3 +42,44 @@ pub(super) fn display_schema(blueprint: &Blueprint) {
     let sdl = blueprint.to_schema();
     Fmt::display(format!("{}\n", print_schema::print_schema(sdl)));
 }
+
+pub(super) fn display_openapi(
+    endpoint_set: &EndpointSet<Checked>,
+    blueprint: &Blueprint,
+) -> anyhow::Result<()> {
+    Fmt::display(Fmt::heading("OpenAPI Document:\n"));
+    let openapi = endpoint_set.to_openapi(blueprint);
+    Fmt::display(format!("{}\n", serde_json::to_string_pretty(&openapi)?));
+    Ok(())
+}
+
+/// Emits the types of the operations of the endpoints, and of the linked
+/// operations that aren't exposed with `@rest`.
+pub(super) fn display_client_types(
+    endpoint_set: &EndpointSet<Checked>,
+    operations: &[ExecutableDocument],
+    blueprint: &Blueprint,
+    format: EmitFormat,
+) -> anyhow::Result<()> {
+    let linked = named_operations(operations);
+    let mut operations = endpoint_set.operations();
+    for (name, doc) in linked.iter() {
+        if operations.iter().all(|(other, _)| other != name) {
+            operations.push((name.clone(), doc));
+        }
+    }
+
+    let client_types = ClientTypes::new(blueprint, &operations);
+    match format {
+        EmitFormat::Ts => {
+            Fmt::display(Fmt::heading("TypeScript Types:\n"));
+            Fmt::display(client_types.to_typescript());
+        }
+        EmitFormat::JsonSchema => {
+            Fmt::display(Fmt::heading("JSON Schema:\n"));
+            let schema = client_types.to_json_schema();
+            Fmt::display(format!("{}\n", serde_json::to_string_pretty(&schema)?));
+        }
+    }
+    Ok(())
+}
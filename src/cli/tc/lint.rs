@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use tailcall_version::VERSION;
+
+use crate::cli::command::LintFormat;
+use crate::cli::fmt::Fmt;
+use crate::core::config::{Config, Level, Lint, LintConfig, Rule};
+use crate::core::runtime::TargetRuntime;
+
+/// Settings of the linter read when `--lint-config` isn't passed.
+const DEFAULT_LINT_CONFIG: &str = ".tailcalllint.yml";
+
+pub(super) async fn lint_command(
+    config: &Config,
+    lint_config: Option<String>,
+    format: LintFormat,
+    runtime: &TargetRuntime,
+) -> Result<()> {
+    let lint_config = read_lint_config(lint_config, runtime).await?;
+    let lints = config.lint(&lint_config);
+
+    match format {
+        LintFormat::Text => display_lints(&lints),
+        LintFormat::Json => Fmt::display(serde_json::to_string_pretty(&lints)?),
+        LintFormat::Sarif => Fmt::display(serde_json::to_string_pretty(&to_sarif(&lints))?),
+    }
+
+    let errors = lints
+        .iter()
+        .filter(|lint| lint.level == Level::Error)
+        .count();
+    if errors > 0 {
+        return Err(anyhow!("Lint failed with {errors} error(s)"));
+    }
+
+    Ok(())
+}
+
+async fn read_lint_config(path: Option<String>, runtime: &TargetRuntime) -> Result<LintConfig> {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(DEFAULT_LINT_CONFIG).exists() => DEFAULT_LINT_CONFIG.to_string(),
+        None => return Ok(LintConfig::default()),
+    };
+    let content = runtime.file.read(&path).await?;
+    LintConfig::from_yaml(&content).map_err(|e| anyhow!("Invalid lint config {path}: {e}"))
+}
+
+fn display_lints(lints: &[Lint]) {
+    if lints.is_empty() {
+        tracing::info!("Lint ... ok");
+        return;
+    }
+
+    for level in [Level::Error, Level::Warning] {
+        let lints = lints
+            .iter()
+            .filter(|lint| lint.level == level)
+            .collect::<Vec<_>>();
+        if lints.is_empty() {
+            continue;
+        }
+
+        Fmt::display(Fmt::heading(&format!("Lint {level}s ({}):", lints.len())));
+        for lint in lints {
+            Fmt::display(format!(
+                "  {} {} [{}]",
+                Fmt::meta(&lint.path),
+                lint.message,
+                lint.rule
+            ));
+        }
+    }
+}
+
+/// Converts the lints to a SARIF 2.1.0 log, the format read by code scanning
+/// tools.
+fn to_sarif(lints: &[Lint]) -> Value {
+    let rules = Rule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.to_string(),
+                "shortDescription": { "text": rule.description() },
+            })
+        })
+        .collect::<Vec<_>>();
+    let results = lints
+        .iter()
+        .map(|lint| {
+            json!({
+                "ruleId": lint.rule.to_string(),
+                "level": lint.level.to_string(),
+                "message": { "text": lint.message },
+                "locations": [{
+                    "logicalLocations": [{ "fullyQualifiedName": lint.path }],
+                }],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "tailcall",
+                    "version": VERSION.as_str(),
+                    "informationUri": "https://tailcall.run",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_to_sarif() {
+        let lints = vec![Lint {
+            rule: Rule::InsecureSsl,
+            level: Level::Error,
+            path: "@upstream".to_string(),
+            message: "verifySSL is disabled".to_string(),
+        }];
+
+        let sarif = to_sarif(&lints);
+        let run = &sarif["runs"][0];
+
+        assert_eq!(sarif["version"], json!("2.1.0"));
+        assert_eq!(
+            run["tool"]["driver"]["rules"][2]["id"],
+            json!("insecure-ssl")
+        );
+        assert_eq!(run["results"][0]["ruleId"], json!("insecure-ssl"));
+        assert_eq!(run["results"][0]["level"], json!("error"));
+        assert_eq!(
+            run["results"][0]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            json!("@upstream")
+        );
+    }
+}
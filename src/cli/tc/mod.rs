@@ -1,7 +1,11 @@
 mod check;
+pub mod diff;
+mod fmt;
 mod gen;
 mod helpers;
 mod init;
+mod lint;
 pub mod run;
 mod start;
+mod test;
 mod validate_rc;
@@ -4,8 +4,9 @@ use dotenvy::dotenv;
 
 use super::helpers::TRACKER;
 use super::validate_rc::validate_rc_config_files;
-use super::{check, gen, init, start};
+use super::{check, diff, fmt, gen, init, start, test};
 use crate::cli::command::{Cli, Command};
+use crate::cli::runtime::Traffic;
 use crate::cli::{self, update_checker};
 use crate::core::blueprint::Blueprint;
 use crate::core::config::reader::ConfigReader;
@@ -41,16 +42,41 @@ fn get_runtime_and_config_reader(verify_ssl: bool) -> (TargetRuntime, ConfigRead
 
 async fn run_command(cli: Cli) -> Result<()> {
     match cli.command {
-        Command::Start { file_paths, verify_ssl } => {
+        Command::Start { file_paths, record, replay, verify_ssl } => {
             let (runtime, config_reader) = get_runtime_and_config_reader(verify_ssl);
             validate_rc_config_files(runtime, &file_paths).await;
-            start::start_command(file_paths, &config_reader).await?;
+            let traffic = record.map(Traffic::Record).or(replay.map(Traffic::Replay));
+            start::start_command(file_paths, traffic, &config_reader).await?;
         }
-        Command::Check { file_paths, n_plus_one_queries, schema, verify_ssl } => {
+        Command::Mock { file_paths, seed, verify_ssl } => {
+            let (runtime, config_reader) = get_runtime_and_config_reader(verify_ssl);
+            validate_rc_config_files(runtime, &file_paths).await;
+            start::mock_command(file_paths, seed, &config_reader).await?;
+        }
+        Command::Check {
+            file_paths,
+            n_plus_one_queries,
+            schema,
+            openapi,
+            emit,
+            lint,
+            lint_config,
+            format,
+            verify_ssl,
+        } => {
             let (runtime, config_reader) = get_runtime_and_config_reader(verify_ssl);
             validate_rc_config_files(runtime.clone(), &file_paths).await;
+            let lint = lint.then_some(check::LintParams { config: lint_config, format });
             check::check_command(
-                check::CheckParams { file_paths, n_plus_one_queries, schema, runtime },
+                check::CheckParams {
+                    file_paths,
+                    n_plus_one_queries,
+                    schema,
+                    openapi,
+                    emit,
+                    lint,
+                    runtime,
+                },
                 &config_reader,
             )
             .await?;
@@ -59,9 +85,21 @@ async fn run_command(cli: Cli) -> Result<()> {
             let (runtime, _) = get_runtime_and_config_reader(true);
             init::init_command(runtime, &folder_path).await?;
         }
-        Command::Gen { file_path } => {
+        Command::Gen { file_path, watch, merge } => {
             let (runtime, _) = get_runtime_and_config_reader(true);
-            gen::gen_command(&file_path, runtime).await?;
+            gen::gen_command(&file_path, watch, merge, runtime).await?;
+        }
+        Command::Diff { old, new, fail_on, verify_ssl } => {
+            let (_, config_reader) = get_runtime_and_config_reader(verify_ssl);
+            diff::diff_command(&old, &new, fail_on, &config_reader).await?;
+        }
+        Command::Fmt { file_paths, to, check } => {
+            let (runtime, _) = get_runtime_and_config_reader(true);
+            fmt::fmt_command(&file_paths, to, check, runtime).await?;
+        }
+        Command::Test { path, update } => {
+            let (_, config_reader) = get_runtime_and_config_reader(true);
+            test::test_command(&path, update, &config_reader).await?;
         }
     }
     Ok(())
@@ -2,16 +2,38 @@ use anyhow::Result;
 
 use super::helpers::log_endpoint_set;
 use crate::cli::fmt::Fmt;
+use crate::cli::runtime::Traffic;
 use crate::cli::server::Server;
 use crate::core::config::reader::ConfigReader;
+use crate::core::config::ConfigModule;
 
 pub(super) async fn start_command(
     file_paths: Vec<String>,
+    traffic: Option<Traffic>,
     config_reader: &ConfigReader,
 ) -> Result<()> {
     let config_module = config_reader.read_all(&file_paths).await?;
     log_endpoint_set(&config_module.extensions().endpoint_set);
     Fmt::log_n_plus_one(false, config_module.config());
+    let server = Server::new(config_module).traffic(traffic);
+    server.fork_start().await?;
+    Ok(())
+}
+
+pub(super) async fn mock_command(
+    file_paths: Vec<String>,
+    seed: Option<u64>,
+    config_reader: &ConfigReader,
+) -> Result<()> {
+    let config_module = config_reader.read_all(&file_paths).await?;
+    log_endpoint_set(&config_module.extensions().endpoint_set);
+
+    let mut config = config_module.config().clone();
+    config.server.mock = Some(true);
+    config.server.mock_seed = seed.or(config.server.mock_seed);
+    let config_module = ConfigModule::new(config, config_module.extensions().clone());
+
+    tracing::info!("Serving mocked data, the upstream won't be called");
     let server = Server::new(config_module);
     server.fork_start().await?;
     Ok(())
@@ -0,0 +1,423 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use hyper::{Body, Request};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tailcall_valid::Validator;
+
+use crate::cli::fmt::Fmt;
+use crate::cli::runtime::{init_with_http, Exchange, ReplayHttp};
+use crate::core::app_context::AppContext;
+use crate::core::async_graphql_hyper::{GraphQLBatchRequest, GraphQLRequest};
+use crate::core::blueprint::Blueprint;
+use crate::core::config::reader::ConfigReader;
+use crate::core::config::{Config, ConfigModule, Source};
+use crate::core::http::handle_request;
+use crate::core::variance::Invariant;
+use crate::core::{EnvIO, Errata};
+
+/// Directory, next to the specs, where the expected responses are stored.
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Config test written in markdown, in the format of the execution specs of
+/// tailcall: code blocks tagged with `@config`, `@mock`, `@env` and `@test`.
+/// Any other content is documentation.
+#[derive(Debug, Default)]
+struct Spec {
+    path: PathBuf,
+    configs: Vec<(Source, String)>,
+    mocks: Vec<Exchange>,
+    env: HashMap<String, String>,
+    tests: Vec<TestRequest>,
+}
+
+/// Request sent to the server, by default a `POST` to the GraphQL endpoint.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestRequest {
+    #[serde(default = "default_method")]
+    method: String,
+    url: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    body: Option<Value>,
+}
+
+fn default_method() -> String {
+    "POST".to_string()
+}
+
+/// Response of the server, compared with the snapshot of the test.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TestResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: Value,
+}
+
+impl Spec {
+    fn parse(path: &Path, content: &str) -> Result<Self> {
+        let mut spec = Spec { path: path.to_path_buf(), ..Default::default() };
+        let mut lines = content.lines();
+
+        while let Some(line) = lines.next() {
+            let Some(info) = line.trim_start().strip_prefix("```") else {
+                continue;
+            };
+            let block = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let mut info = info.split_whitespace();
+            let (Some(lang), Some(tag)) = (info.next(), info.next()) else {
+                continue;
+            };
+            // Other blocks of the doc, e.g. "```sh title", are left as is.
+            if !tag.starts_with('@') {
+                continue;
+            }
+            let source = Source::from_str(lang)?;
+
+            match tag {
+                "@config" => spec.configs.push((source, block)),
+                "@mock" => spec
+                    .mocks
+                    .extend(parse_block::<Vec<Exchange>>(&source, &block)?),
+                "@env" => spec
+                    .env
+                    .extend(parse_block::<HashMap<_, _>>(&source, &block)?),
+                "@test" => spec
+                    .tests
+                    .extend(parse_block::<Vec<TestRequest>>(&source, &block)?),
+                tag => return Err(anyhow!("Unknown block {tag}")),
+            }
+        }
+
+        if spec.configs.is_empty() {
+            return Err(anyhow!("A spec needs at least one @config block"));
+        }
+
+        Ok(spec)
+    }
+
+    fn snapshot_path(&self, index: usize) -> PathBuf {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.path
+            .with_file_name(SNAPSHOTS_DIR)
+            .join(format!("{file_name}_{index}.json"))
+    }
+}
+
+fn parse_block<T: for<'de> Deserialize<'de>>(source: &Source, block: &str) -> Result<T> {
+    match source {
+        Source::Json => Ok(serde_json::from_str(block)?),
+        Source::Yml => Ok(serde_yaml_ng::from_str(block)?),
+        Source::GraphQL => Err(anyhow!("Only JSON and YAML are supported in this block")),
+    }
+}
+
+struct SpecEnv(HashMap<String, String>);
+
+impl EnvIO for SpecEnv {
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.0.get(key).map(Cow::from)
+    }
+}
+
+pub(super) async fn test_command(
+    path: &str,
+    update: bool,
+    config_reader: &ConfigReader,
+) -> Result<()> {
+    let paths = find_specs(Path::new(path))?;
+    if paths.is_empty() {
+        return Err(anyhow!("No spec found in {path}"));
+    }
+
+    let mut failed = 0;
+    for path in paths.iter() {
+        match run_spec(path, update, config_reader).await {
+            Ok(failures) if failures.is_empty() => {
+                Fmt::display(format!("{} ... ok", path.display()));
+            }
+            Ok(failures) => {
+                failed += 1;
+                Fmt::display(Fmt::heading(&format!("{} ... failed", path.display())));
+                for failure in failures {
+                    Fmt::display(format!("  {failure}"));
+                }
+            }
+            Err(err) => {
+                failed += 1;
+                Fmt::display(Fmt::heading(&format!("{} ... failed", path.display())));
+                Fmt::display(format!("  {err:#}"));
+            }
+        }
+    }
+
+    Fmt::display(format!("{} passed, {failed} failed", paths.len() - failed));
+    if failed > 0 {
+        return Err(anyhow!("{failed} spec(s) failed"));
+    }
+
+    Ok(())
+}
+
+/// Returns the markdown files of the directory, or the path itself when it's
+/// a file.
+fn find_specs(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut paths = std::fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "md"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Runs the tests of a spec and returns the failures.
+async fn run_spec(path: &Path, update: bool, config_reader: &ConfigReader) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)?;
+    let spec = Spec::parse(path, &content)?;
+
+    let mut config_module: Option<ConfigModule> = None;
+    for (source, content) in spec.configs.iter() {
+        let config = Config::from_source(source.to_owned(), content)?;
+        let module = config_reader.resolve(config, path.parent()).await?;
+        config_module = Some(match config_module {
+            Some(config_module) => config_module
+                .unify(module)
+                .to_result()
+                .map_err(Errata::from)?,
+            None => module,
+        });
+    }
+    let config_module = config_module.unwrap_or_default();
+    let blueprint = Blueprint::try_from(&config_module).map_err(Errata::from)?;
+
+    let http = Arc::new(ReplayHttp::new(spec.mocks.clone()));
+    let mut runtime = init_with_http(&blueprint, http.clone());
+    runtime.env = Arc::new(SpecEnv(spec.env.clone()));
+    let endpoints = config_module
+        .extensions()
+        .endpoint_set
+        .clone()
+        .into_checked(&blueprint, runtime.clone())
+        .await?;
+    let app_ctx = Arc::new(AppContext::new(blueprint, runtime, endpoints));
+
+    let mut failures = vec![];
+    for (index, test) in spec.tests.iter().enumerate() {
+        let actual = execute(test, app_ctx.clone()).await?;
+        let snapshot_path = spec.snapshot_path(index);
+
+        if update {
+            if let Some(dir) = snapshot_path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(
+                &snapshot_path,
+                serde_json::to_string_pretty(&actual)? + "\n",
+            )?;
+            continue;
+        }
+
+        let Ok(expected) = std::fs::read_to_string(&snapshot_path) else {
+            failures.push(format!(
+                "test #{index}: {} doesn't exist, run with --update to create it",
+                snapshot_path.display()
+            ));
+            continue;
+        };
+        let expected: Value = serde_json::from_str(&expected)
+            .with_context(|| format!("Invalid snapshot {}", snapshot_path.display()))?;
+        let mut diffs = vec![];
+        diff("", &expected, &serde_json::to_value(&actual)?, &mut diffs);
+        failures.extend(
+            diffs
+                .into_iter()
+                .map(|diff| format!("test #{index}: {diff}")),
+        );
+    }
+    failures.extend(http.unexpected_hits());
+
+    Ok(failures)
+}
+
+async fn execute(test: &TestRequest, app_ctx: Arc<AppContext>) -> Result<TestResponse> {
+    let url = test.url.clone().unwrap_or_else(|| {
+        format!(
+            "http://localhost{}",
+            app_ctx.blueprint.server.routes.graphql()
+        )
+    });
+    let body = test
+        .body
+        .as_ref()
+        .map(serde_json::to_vec)
+        .transpose()?
+        .map(Body::from)
+        .unwrap_or_default();
+    let request = test
+        .headers
+        .iter()
+        .fold(
+            Request::builder().method(test.method.as_str()).uri(url),
+            |request, (name, value)| request.header(name, value),
+        )
+        .body(body)?;
+
+    let response = if app_ctx.blueprint.server.enable_batch_requests {
+        handle_request::<GraphQLBatchRequest>(request, app_ctx).await?
+    } else {
+        handle_request::<GraphQLRequest>(request, app_ctx).await?
+    };
+
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    let body = serde_json::from_slice(&body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).to_string()));
+
+    Ok(TestResponse { status, headers, body })
+}
+
+/// Collects the paths where the actual value differs from the expected one.
+fn diff(path: &str, expected: &Value, actual: &Value, diffs: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for key in expected
+                .keys()
+                .chain(actual.keys().filter(|key| !expected.contains_key(*key)))
+            {
+                let path = format!("{path}.{key}");
+                match (expected.get(key), actual.get(key)) {
+                    (Some(expected), Some(actual)) => diff(&path, expected, actual, diffs),
+                    (Some(expected), None) => {
+                        diffs.push(format!("{path}: expected {expected}, got nothing"))
+                    }
+                    (None, Some(actual)) => {
+                        diffs.push(format!("{path}: expected nothing, got {actual}"))
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(expected_items), Value::Array(actual_items))
+            if expected_items.len() == actual_items.len() =>
+        {
+            for (index, (expected, actual)) in expected_items.iter().zip(actual_items).enumerate() {
+                diff(&format!("{path}[{index}]"), expected, actual, diffs);
+            }
+        }
+        (expected, actual) if expected != actual => {
+            let path = if path.is_empty() { "." } else { path };
+            diffs.push(format!("{path}: expected {expected}, got {actual}"));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const SPEC: &str = r#"# Users
+
+Fetches the users.
+
+```graphql @config
+schema {
+  query: Query
+}
+
+type Query {
+  users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+}
+
+type User {
+  id: Int
+}
+```
+
+```yml @mock
+- request:
+    method: GET
+    url: http://jsonplaceholder.typicode.com/users
+  response:
+    status: 200
+    body:
+      - id: 1
+```
+
+```yml @test
+- body:
+    query: query { users { id } }
+```
+"#;
+
+    #[test]
+    fn test_parse() {
+        let spec = Spec::parse(Path::new("specs/users.md"), SPEC).unwrap();
+
+        assert_eq!(spec.configs.len(), 1);
+        assert_eq!(spec.mocks.len(), 1);
+        assert_eq!(spec.tests.len(), 1);
+        assert_eq!(spec.tests[0].method, "POST");
+        assert_eq!(
+            spec.snapshot_path(0),
+            Path::new("specs/snapshots/users.md_0.json")
+        );
+    }
+
+    #[test]
+    fn test_parse_with_other_blocks() {
+        let content = format!("```sh title\ntailcall test specs\n```\n\n{SPEC}");
+        let spec = Spec::parse(Path::new("specs/users.md"), &content).unwrap();
+
+        assert_eq!(spec.configs.len(), 1);
+        assert_eq!(spec.tests.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_without_config() {
+        assert!(Spec::parse(Path::new("empty.md"), "# Empty").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = json!({"data": {"users": [{"id": 1}, {"id": 2}]}, "errors": null});
+        let actual = json!({"data": {"users": [{"id": 1}, {"id": 3}]}});
+
+        let mut diffs = vec![];
+        diff("", &expected, &actual, &mut diffs);
+
+        assert_eq!(
+            diffs,
+            vec![
+                ".data.users[1].id: expected 2, got 3",
+                ".errors: expected null, got nothing",
+            ]
+        );
+    }
+}
@@ -11,7 +11,7 @@ use crate::core::data_loader::{DataLoader, DedupeResult};
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc;
 use crate::core::grpc::data_loader::GrpcDataLoader;
-use crate::core::http::{DataLoaderRequest, HttpDataLoader};
+use crate::core::http::{DataLoaderRequest, HttpDataLoader, OAuth2Tokens};
 use crate::core::ir::model::{DataLoaderId, IoId, IO, IR};
 use crate::core::ir::Error;
 use crate::core::jit::{OPHash, OperationPlan};
@@ -30,6 +30,7 @@ pub struct AppContext {
     pub dedupe_operation_handler: DedupeResult<OperationId, AnyResponse<Vec<u8>>, Error>,
     pub operation_plans: DashMap<OPHash, OperationPlan<async_graphql_value::Value>>,
     pub const_execution_cache: DashMap<OPHash, AnyResponse<Vec<u8>>>,
+    pub oauth2_tokens: Arc<OAuth2Tokens>,
 }
 
 impl AppContext {
@@ -50,7 +51,13 @@ impl AppContext {
                         expr.modify(&mut |expr| match expr {
                             IR::IO(io) => match io {
                                 IO::Http {
-                                    req_template, group_by, is_list, dedupe, hook, ..
+                                    req_template,
+                                    group_by,
+                                    is_list,
+                                    dedupe,
+                                    hook,
+                                    auth,
+                                    ..
                                 } => {
                                     let is_list = *is_list;
                                     let dedupe = *dedupe;
@@ -68,6 +75,7 @@ impl AppContext {
                                         hook: hook.clone(),
                                         is_list,
                                         dedupe,
+                                        auth: auth.clone(),
                                     }));
 
                                     http_data_loaders.push(data_loader);
@@ -75,7 +83,14 @@ impl AppContext {
                                     result
                                 }
 
-                                IO::GraphQL { req_template, field_name, batch, dedupe, .. } => {
+                                IO::GraphQL {
+                                    req_template,
+                                    field_name,
+                                    batch,
+                                    dedupe,
+                                    auth,
+                                    ..
+                                } => {
                                     let dedupe = *dedupe;
                                     let graphql_data_loader =
                                         GraphqlDataLoader::new(runtime.clone(), *batch)
@@ -89,6 +104,7 @@ impl AppContext {
                                         batch: *batch,
                                         dl_id: Some(DataLoaderId::new(gql_data_loaders.len())),
                                         dedupe,
+                                        auth: auth.clone(),
                                     }));
 
                                     gql_data_loaders.push(graphql_data_loader);
@@ -96,7 +112,7 @@ impl AppContext {
                                     result
                                 }
 
-                                IO::Grpc { req_template, group_by, dedupe, hook, .. } => {
+                                IO::Grpc { req_template, group_by, dedupe, hook, auth, .. } => {
                                     let dedupe = *dedupe;
                                     let data_loader = GrpcDataLoader {
                                         runtime: runtime.clone(),
@@ -113,6 +129,7 @@ impl AppContext {
                                         dl_id: Some(DataLoaderId::new(grpc_data_loaders.len())),
                                         dedupe,
                                         hook: hook.clone(),
+                                        auth: auth.clone(),
                                     }));
 
                                     grpc_data_loaders.push(data_loader);
@@ -146,6 +163,7 @@ impl AppContext {
             dedupe_operation_handler: DedupeResult::new(false),
             operation_plans: DashMap::new(),
             const_execution_cache: DashMap::default(),
+            oauth2_tokens: Arc::new(OAuth2Tokens::default()),
         }
     }
 
@@ -2,7 +2,7 @@ use std::any::Any;
 use std::hash::{Hash, Hasher};
 
 use anyhow::Result;
-use async_graphql::parser::types::{ExecutableDocument, OperationType};
+use async_graphql::parser::types::{DocumentOperations, ExecutableDocument, OperationType};
 use async_graphql::{BatchResponse, Executor, Value};
 use http::header::{HeaderMap, HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
 use http::{Response, StatusCode};
@@ -11,6 +11,8 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use tailcall_hasher::TailcallHasher;
 
+use super::ir::UNAUTHENTICATED;
+use super::jit::graphql_error::Error as ExtensionError;
 use super::jit::{BatchResponse as JITBatchResponse, JITExecutor};
 
 #[derive(PartialEq, Eq, Clone, Hash, Debug)]
@@ -27,6 +29,11 @@ pub trait GraphQLRequestLike: Hash + Send {
 
     fn parse_query(&mut self) -> Option<&ExecutableDocument>;
 
+    /// Returns the type of the operation that is executed.
+    fn operation_type(&mut self) -> Option<OperationType> {
+        None
+    }
+
     fn is_query(&mut self) -> bool {
         self.parse_query()
             .map(|a| {
@@ -132,6 +139,17 @@ impl GraphQLRequestLike for GraphQLRequest {
     fn parse_query(&mut self) -> Option<&ExecutableDocument> {
         self.0.parsed_query().ok()
     }
+
+    fn operation_type(&mut self) -> Option<OperationType> {
+        let name = self.0.operation_name.clone();
+        self.parse_query().and_then(|doc| match &doc.operations {
+            DocumentOperations::Single(operation) => Some(operation.node.ty),
+            DocumentOperations::Multiple(operations) => name
+                .as_deref()
+                .and_then(|name| operations.get(name))
+                .map(|operation| operation.node.ty),
+        })
+    }
 }
 
 // TODO: drop this type since we can use jit::response?
@@ -162,15 +180,26 @@ impl From<GraphQLQuery> for GraphQLRequest {
             request = request.variables(variables);
         }
 
+        if let Some(extensions) = query.extensions {
+            if let Ok(extensions) = serde_json::from_str(&extensions) {
+                request.extensions = extensions;
+            }
+        }
+
         GraphQLRequest(request)
     }
 }
 
-#[derive(Debug)]
+/// GraphQL request that's encoded in the query string of a `GET` request, e.g.
+/// `?query={user(id:1){name}}&variables={}&operationName=User`. The
+/// `variables` and the `extensions` are JSON encoded.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GraphQLQuery {
     query: String,
     operation_name: Option<String>,
     variables: Option<String>,
+    extensions: Option<String>,
 }
 
 impl GraphQLQuery {
@@ -221,31 +250,85 @@ impl GraphQLResponse {
         }
     }
 
+    /// Follows the `unwrap` path of an endpoint through the serialized
+    /// response, e.g. `data.user` or `data.users.0`.
+    fn unwrap_response(
+        res: &async_graphql::Response,
+        unwrap: &[String],
+    ) -> Result<serde_json::Value> {
+        let value = serde_json::to_value(res)?;
+        let value = unwrap
+            .iter()
+            .try_fold(&value, |value, key| match value {
+                serde_json::Value::Array(list) => list.get(key.parse::<usize>().ok()?),
+                value => value.get(key.as_str()),
+            })
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(value)
+    }
+
+    fn rest_body(res: &async_graphql::Response, unwrap: Option<&[String]>) -> Result<String> {
+        Ok(match unwrap {
+            Some(unwrap) => serde_json::to_string(&Self::unwrap_response(res, unwrap)?)?,
+            None => serde_json::to_string(Self::flatten_response(&res.data))?,
+        })
+    }
+
     /// Transforms a plain `GraphQLResponse` into a `Response<Body>`.
     /// Differs as `to_response` by flattening the response's data
-    /// `{"data": {"user": {"name": "John"}}}` becomes `{"name": "John"}`.
-    pub fn into_rest_response(self) -> Result<Response<hyper::Body>> {
+    /// `{"data": {"user": {"name": "John"}}}` becomes `{"name": "John"}`,
+    /// unless an `unwrap` path picks the part of the response to return.
+    /// Successful responses are sent with `status` and failed ones with the
+    /// status derived from their errors.
+    pub fn into_rest_response(
+        self,
+        status: StatusCode,
+        unwrap: Option<&[String]>,
+    ) -> Result<Response<hyper::Body>> {
         if !self.0.is_ok() {
-            return self.build_response(StatusCode::INTERNAL_SERVER_ERROR, self.default_body()?);
+            let failed = match self.0 {
+                BatchResponse::Single(ref res) => Some(res),
+                BatchResponse::Batch(ref list) => list.iter().find(|res| res.is_err()),
+            };
+            let status = failed
+                .map(error_status)
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            return self.build_response(status, self.default_body()?);
         }
 
         match self.0 {
             BatchResponse::Single(ref res) => {
-                let item = Self::flatten_response(&res.data);
-                let data = serde_json::to_string(item)?;
+                let data = Self::rest_body(res, unwrap)?;
 
-                self.build_response(StatusCode::OK, Body::from(data))
+                self.build_response(status, Body::from(data))
             }
             BatchResponse::Batch(ref list) => {
                 let item = list
                     .iter()
-                    .map(|res| Self::flatten_response(&res.data))
-                    .collect::<Vec<&Value>>();
-                let data = serde_json::to_string(&item)?;
+                    .map(|res| Self::rest_body(res, unwrap))
+                    .collect::<Result<Vec<_>>>()?;
+                let data = format!("[{}]", item.join(","));
+
+                self.build_response(status, Body::from(data))
+            }
+        }
+    }
 
-                self.build_response(StatusCode::OK, Body::from(data))
+    /// Adds the extension to each of the responses of the batch.
+    pub fn set_extension(mut self, name: &str, value: Value) -> GraphQLResponse {
+        match self.0 {
+            BatchResponse::Single(ref mut res) => {
+                res.extensions.insert(name.to_string(), value);
+            }
+            BatchResponse::Batch(ref mut list) => {
+                for res in list {
+                    res.extensions.insert(name.to_string(), value.clone());
+                }
             }
         }
+        self
     }
 
     /// Sets the `cache_control` for a given `GraphQLResponse`.
@@ -289,6 +372,64 @@ impl GraphQLResponse {
     }
 }
 
+/// Picks the status code of a failed REST response from its first error.
+/// Errors without a path on a response without data come from requests that
+/// couldn't be executed at all, e.g. because their variables are invalid.
+fn error_status(res: &async_graphql::Response) -> StatusCode {
+    let Some(error) = res.errors.first() else {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    };
+
+    if extension(error, "code").is_some_and(|code| code == Value::from(UNAUTHENTICATED)) {
+        StatusCode::UNAUTHORIZED
+    } else if let Some(code) = grpc_code(error) {
+        grpc_status(code)
+    } else if error.path.is_empty() && res.data == Value::Null {
+        StatusCode::BAD_REQUEST
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// Returns the extension of the error. The resolvers keep the extensions in
+/// the source of the error.
+fn extension(error: &async_graphql::ServerError, name: &str) -> Option<Value> {
+    error
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get(name).cloned())
+        .or_else(|| {
+            error
+                .source::<ExtensionError>()
+                .and_then(|error| error.extensions.as_ref())
+                .and_then(|extensions| extensions.get(name).cloned())
+        })
+}
+
+/// gRPC failures carry their code in the extensions.
+fn grpc_code(error: &async_graphql::ServerError) -> Option<i64> {
+    match extension(error, "grpcCode")? {
+        Value::Number(code) => code.as_i64(),
+        _ => None,
+    }
+}
+
+/// The HTTP equivalents of the gRPC status codes.
+fn grpc_status(code: i64) -> StatusCode {
+    match code {
+        3 | 9 | 11 => StatusCode::BAD_REQUEST,
+        4 => StatusCode::GATEWAY_TIMEOUT,
+        5 => StatusCode::NOT_FOUND,
+        6 | 10 => StatusCode::CONFLICT,
+        7 => StatusCode::FORBIDDEN,
+        8 => StatusCode::TOO_MANY_REQUESTS,
+        12 => StatusCode::NOT_IMPLEMENTED,
+        14 => StatusCode::SERVICE_UNAVAILABLE,
+        16 => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CacheControl {
     pub max_age: i32,
@@ -355,6 +496,10 @@ impl GraphQLArcResponse {
             cache_control: enable_cache_header.then_some(CacheControl { max_age, public }),
         }
     }
+
+    pub fn is_ok(&self) -> bool {
+        self.response.is_ok()
+    }
 }
 
 impl GraphQLArcResponse {
@@ -418,6 +563,8 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::core::jit::graphql_error::ErrorExtensions;
+    use crate::core::{auth, ir};
 
     #[tokio::test]
     async fn test_to_rest_response_single() {
@@ -427,7 +574,7 @@ mod tests {
         let data = IndexMap::from([(Name::new("user"), Value::Object(user))]);
 
         let response = GraphQLResponse(BatchResponse::Single(Response::new(Value::Object(data))));
-        let rest_response = response.into_rest_response().unwrap();
+        let rest_response = response.into_rest_response(StatusCode::OK, None).unwrap();
 
         assert_eq!(rest_response.status(), StatusCode::OK);
         assert_eq!(rest_response.headers()["content-type"], "application/json");
@@ -454,7 +601,7 @@ mod tests {
             .collect();
 
         let response = GraphQLResponse(BatchResponse::Batch(list));
-        let rest_response = response.into_rest_response().unwrap();
+        let rest_response = response.into_rest_response(StatusCode::OK, None).unwrap();
 
         assert_eq!(rest_response.status(), StatusCode::OK);
         assert_eq!(rest_response.headers()["content-type"], "application/json");
@@ -483,9 +630,9 @@ mod tests {
             .map(|error| ServerError::new(error.to_string(), None))
             .collect();
         let response = GraphQLResponse(BatchResponse::Single(response));
-        let rest_response = response.into_rest_response().unwrap();
+        let rest_response = response.into_rest_response(StatusCode::OK, None).unwrap();
 
-        assert_eq!(rest_response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(rest_response.status(), StatusCode::BAD_REQUEST);
         assert_eq!(rest_response.headers()["content-type"], "application/json");
         assert_eq!(
             hyper::body::to_bytes(rest_response.into_body())
@@ -506,6 +653,66 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_to_rest_response_with_field_error() {
+        let mut error = ServerError::new("Some error", None);
+        error.path = vec![async_graphql::PathSegment::Field("user".to_string())];
+        let mut response: Response = Default::default();
+        response.errors = vec![error];
+        let response = GraphQLResponse(BatchResponse::Single(response));
+        let rest_response = response.into_rest_response(StatusCode::OK, None).unwrap();
+
+        assert_eq!(rest_response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_to_rest_response_with_grpc_error() {
+        let mut extensions = async_graphql::ErrorExtensionValues::default();
+        extensions.set("grpcCode", 5);
+        let mut error = ServerError::new("Not found", None);
+        error.extensions = Some(extensions);
+        let mut response: Response = Default::default();
+        response.errors = vec![error];
+        let response = GraphQLResponse(BatchResponse::Single(response));
+        let rest_response = response.into_rest_response(StatusCode::OK, None).unwrap();
+
+        assert_eq!(rest_response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_to_rest_response_with_auth_error() {
+        let error: async_graphql::Error =
+            ir::Error::Auth(auth::error::Error::Missing).extend().into();
+        let mut response: Response = Default::default();
+        response.errors = vec![error.into_server_error(Default::default())];
+        let response = GraphQLResponse(BatchResponse::Single(response));
+        let rest_response = response.into_rest_response(StatusCode::OK, None).unwrap();
+
+        assert_eq!(rest_response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_to_rest_response_unwrap() {
+        let user = IndexMap::from([(Name::new("name"), Value::String("John".to_string()))]);
+        let users = Value::List(vec![Value::Object(user)]);
+        let data = IndexMap::from([(Name::new("users"), users)]);
+
+        let response = GraphQLResponse(BatchResponse::Single(Response::new(Value::Object(data))));
+        let unwrap = ["data", "users", "0", "name"].map(String::from);
+        let rest_response = response
+            .into_rest_response(StatusCode::CREATED, Some(&unwrap[..]))
+            .unwrap();
+
+        assert_eq!(rest_response.status(), StatusCode::CREATED);
+        assert_eq!(
+            hyper::body::to_bytes(rest_response.into_body())
+                .await
+                .unwrap()
+                .to_vec(),
+            json!("John").to_string().as_bytes().to_vec()
+        );
+    }
+
     #[test]
     fn to_value() {
         assert_eq!(CacheControl { public: true, max_age: 0 }.value(), None);
@@ -535,4 +742,18 @@ mod tests {
             Some("no-cache, private".to_string())
         );
     }
+
+    #[test]
+    fn test_operation_type() {
+        let mut request = GraphQLRequest(async_graphql::Request::new("{ users { id } }"));
+        assert_eq!(request.operation_type(), Some(OperationType::Query));
+
+        let query = "query users { users { id } } mutation addUser { addUser { id } }";
+        let mut request =
+            GraphQLRequest(async_graphql::Request::new(query).operation_name("addUser"));
+        assert_eq!(request.operation_type(), Some(OperationType::Mutation));
+
+        let mut request = GraphQLRequest(async_graphql::Request::new(query));
+        assert_eq!(request.operation_type(), None);
+    }
 }
@@ -0,0 +1,140 @@
+use x509_parser::extensions::GeneralName;
+
+use super::error::Error;
+use super::verification::Verification;
+use super::verify::Verify;
+use crate::core::blueprint;
+use crate::core::http::RequestContext;
+
+/// Identity of the client taken from the certificate that was verified during
+/// the TLS handshake.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClientIdentity {
+    /// Common name of the certificate subject.
+    pub subject: Option<String>,
+    /// DNS names, emails and URIs from the subject alternative name extension.
+    pub alt_names: Vec<String>,
+}
+
+impl ClientIdentity {
+    /// Reads the identity from a DER encoded certificate.
+    pub fn from_der(der: &[u8]) -> Option<Self> {
+        let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+
+        let subject = cert
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|name| name.as_str().ok())
+            .map(str::to_string);
+
+        let alt_names = cert
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|ext| {
+                ext.value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(name)
+                        | GeneralName::RFC822Name(name)
+                        | GeneralName::URI(name) => Some(name.to_string()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self { subject, alt_names })
+    }
+
+    fn names(&self) -> impl Iterator<Item = &String> {
+        self.subject.iter().chain(self.alt_names.iter())
+    }
+}
+
+pub struct ClientCertVerifier {
+    options: blueprint::ClientCert,
+}
+
+impl ClientCertVerifier {
+    pub fn new(options: blueprint::ClientCert) -> Self {
+        Self { options }
+    }
+}
+
+#[async_trait::async_trait]
+impl Verify for ClientCertVerifier {
+    /// Verify that the client presented a certificate with an allowed subject.
+    async fn verify(&self, req_ctx: &RequestContext) -> Verification {
+        let Some(identity) = req_ctx.client_identity.as_ref() else {
+            return Verification::fail(Error::Missing);
+        };
+
+        if self.options.subjects.is_empty()
+            || identity
+                .names()
+                .any(|name| self.options.subjects.contains(name))
+        {
+            Verification::succeed()
+        } else {
+            Verification::fail(Error::Invalid)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn setup_provider(subjects: &[&str]) -> ClientCertVerifier {
+        ClientCertVerifier::new(blueprint::ClientCert {
+            subjects: subjects
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<HashSet<_>>(),
+        })
+    }
+
+    fn create_request(identity: Option<ClientIdentity>) -> RequestContext {
+        RequestContext::default().client_identity(identity)
+    }
+
+    fn identity() -> ClientIdentity {
+        ClientIdentity {
+            subject: Some("billing".to_string()),
+            alt_names: vec!["billing.internal".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_missing_certificate() {
+        let provider = setup_provider(&[]);
+        let validation = provider.verify(&create_request(None)).await;
+        assert_eq!(validation, Verification::fail(Error::Missing));
+    }
+
+    #[tokio::test]
+    async fn verify_any_subject() {
+        let provider = setup_provider(&[]);
+        let validation = provider.verify(&create_request(Some(identity()))).await;
+        assert_eq!(validation, Verification::succeed());
+    }
+
+    #[tokio::test]
+    async fn verify_allowed_alt_name() {
+        let provider = setup_provider(&["billing.internal"]);
+        let validation = provider.verify(&create_request(Some(identity()))).await;
+        assert_eq!(validation, Verification::succeed());
+    }
+
+    #[tokio::test]
+    async fn verify_unknown_subject() {
+        let provider = setup_provider(&["orders"]);
+        let validation = provider.verify(&create_request(Some(identity()))).await;
+        assert_eq!(validation, Verification::fail(Error::Invalid));
+    }
+}
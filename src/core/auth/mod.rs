@@ -1,4 +1,5 @@
 pub mod basic;
+pub mod client_cert;
 pub mod error;
 pub mod jwt;
 mod verification;
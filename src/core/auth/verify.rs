@@ -1,6 +1,7 @@
 use futures_util::join;
 
 use super::basic::BasicVerifier;
+use super::client_cert::ClientCertVerifier;
 use super::jwt::jwt_verify::JwtVerifier;
 use super::verification::Verification;
 use crate::core::blueprint;
@@ -14,6 +15,7 @@ pub(crate) trait Verify {
 pub enum Verifier {
     Basic(BasicVerifier),
     Jwt(JwtVerifier),
+    ClientCert(ClientCertVerifier),
 }
 
 pub enum AuthVerifier {
@@ -27,6 +29,9 @@ impl From<blueprint::Provider> for Verifier {
         match provider {
             blueprint::Provider::Basic(options) => Verifier::Basic(BasicVerifier::new(options)),
             blueprint::Provider::Jwt(options) => Verifier::Jwt(JwtVerifier::new(options)),
+            blueprint::Provider::ClientCert(options) => {
+                Verifier::ClientCert(ClientCertVerifier::new(options))
+            }
         }
     }
 }
@@ -51,6 +56,7 @@ impl Verify for Verifier {
         match self {
             Verifier::Basic(basic) => basic.verify(req_ctx).await,
             Verifier::Jwt(jwt) => jwt.verify(req_ctx).await,
+            Verifier::ClientCert(client_cert) => client_cert.verify(req_ctx).await,
         }
     }
 }
@@ -18,10 +18,18 @@ pub struct Jwt {
     pub jwks: JwkSet,
 }
 
+/// Authenticates the clients by the certificate verified during the TLS
+/// handshake.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientCert {
+    pub subjects: HashSet<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Provider {
     Basic(Basic),
     Jwt(Jwt),
+    ClientCert(ClientCert),
 }
 
 impl From<Content<String>> for Content<Provider> {
@@ -62,6 +70,18 @@ impl Provider {
                     .iter()
                     .map(|jwks| jwks.clone().into()),
             )
+            .chain(
+                config_module
+                    .server
+                    .client_auth
+                    .iter()
+                    .map(|client_auth| Content {
+                        id: client_auth.id.clone(),
+                        content: Provider::ClientCert(ClientCert {
+                            subjects: client_auth.subjects.iter().cloned().collect(),
+                        }),
+                    }),
+            )
             .collect()
     }
 }
@@ -10,7 +10,8 @@ use union_resolver::update_union_resolver;
 use crate::core::blueprint::*;
 use crate::core::config::{Config, Enum, Field, GraphQLOperationType, Protected, Union};
 use crate::core::directive::DirectiveCodec;
-use crate::core::ir::model::{Cache, IR};
+use crate::core::ir::model::{Cache, Stale, IR};
+use crate::core::mustache::Mustache;
 use crate::core::try_fold::TryFold;
 use crate::core::{config, scalar, Type};
 
@@ -250,7 +251,7 @@ fn to_enum_type_definition((name, eu): (&String, &Enum)) -> Definition {
             .variants
             .iter()
             .map(|variant| EnumValueDefinition {
-                description: None,
+                description: variant.doc.clone(),
                 name: variant.name.clone(),
                 directives: vec![],
                 alias: variant.alias.clone().unwrap_or_default().options,
@@ -372,8 +373,43 @@ pub fn update_cache_resolvers<'a>() -> TryFold<
 > {
     TryFold::<(&ConfigModule, &Field, &config::Type, &str), FieldDefinition, BlueprintError>::new(
         move |(_config, field, typ, _name), mut b_field| {
-            if let Some(config::Cache { max_age }) = field.cache.as_ref().or(typ.cache.as_ref()) {
-                b_field.map_expr(|expression| Cache::wrap(*max_age, expression))
+            if let Some(config::Cache { max_age, tags, stale_while_revalidate, stale_if_error }) =
+                field.cache.as_ref().or(typ.cache.as_ref())
+            {
+                let tags = tags
+                    .iter()
+                    .map(|tag| Mustache::parse(tag))
+                    .collect::<Vec<_>>();
+                let stale = Stale {
+                    while_revalidate: *stale_while_revalidate,
+                    if_error: *stale_if_error,
+                };
+                b_field.map_expr(|expression| Cache::wrap(*max_age, tags, stale, expression))
+            }
+
+            Valid::succeed(b_field)
+        },
+    )
+}
+
+/// Checks that `@restExpose` is only used on the fields of `Query` and
+/// `Mutation`
+pub fn update_rest_expose<'a>(
+    object_name: &'a str,
+) -> TryFold<
+    'a,
+    (&'a ConfigModule, &'a Field, &'a config::Type, &'a str),
+    FieldDefinition,
+    BlueprintError,
+> {
+    TryFold::<(&ConfigModule, &Field, &config::Type, &str), FieldDefinition, BlueprintError>::new(
+        move |(config, field, _typ, _name), b_field| {
+            let schema = &config.schema;
+            let is_root = schema.query.as_deref() == Some(object_name)
+                || schema.mutation.as_deref() == Some(object_name);
+
+            if field.rest_expose.is_some() && !is_root {
+                return Valid::fail(BlueprintError::RestExposeOnlyOnRootFields);
             }
 
             Valid::succeed(b_field)
@@ -381,6 +417,38 @@ pub fn update_cache_resolvers<'a>() -> TryFold<
     )
 }
 
+/// Wraps the resolver with IR::Invalidate if `Field::invalidates` is present
+/// for that field
+pub fn update_invalidates<'a>(
+    operation_type: &'a GraphQLOperationType,
+) -> TryFold<
+    'a,
+    (&'a ConfigModule, &'a Field, &'a config::Type, &'a str),
+    FieldDefinition,
+    BlueprintError,
+> {
+    TryFold::<(&ConfigModule, &Field, &config::Type, &str), FieldDefinition, BlueprintError>::new(
+        move |(_config, field, _typ, _name), mut b_field| {
+            let Some(invalidates) = field.invalidates.as_ref() else {
+                return Valid::succeed(b_field);
+            };
+
+            if *operation_type != GraphQLOperationType::Mutation {
+                return Valid::fail(BlueprintError::InvalidatesOnlyOnMutation);
+            }
+
+            let tags = invalidates
+                .tags
+                .iter()
+                .map(|tag| Mustache::parse(tag))
+                .collect::<Vec<_>>();
+            b_field.map_expr(|resolver| IR::Invalidate(tags, Box::new(resolver)));
+
+            Valid::succeed(b_field)
+        },
+    )
+}
+
 fn validate_field_type_exist(config: &Config, field: &Field) -> Valid<(), BlueprintError> {
     let field_type = field.type_of.name();
     if !scalar::Scalar::is_predefined(field_type) && !config.contains(field_type) {
@@ -546,8 +614,11 @@ pub fn to_field_definition(
         .and(update_resolver(operation_type, object_name))
         .and(update_modify().trace(config::Modify::trace_name().as_str()))
         .and(fix_dangling_resolvers())
+        .and(update_mock().trace(config::Mock::trace_name().as_str()))
         .and(update_cache_resolvers())
+        .and(update_invalidates(operation_type).trace(config::Invalidates::trace_name().as_str()))
         .and(update_protected(object_name).trace(Protected::trace_name().as_str()))
+        .and(update_rest_expose(object_name).trace(config::RestExpose::trace_name().as_str()))
         .and(update_enum_alias())
         .and(update_union_resolver())
         .and(update_interface_resolver())
@@ -0,0 +1,539 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use super::{Blueprint, Definition, FieldDefinition, InputFieldDefinition};
+use crate::core::ir::model::{IO, IR};
+use crate::core::Type;
+
+/// Impact of a change on the clients of the schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, strum_macros::Display)]
+pub enum Severity {
+    /// Queries that used to work may fail.
+    Breaking,
+    /// Queries keep working, but they may get different results.
+    Dangerous,
+    Safe,
+}
+
+/// Difference between two blueprints.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub severity: Severity,
+    /// Coordinates of the changed element, e.g. `Query.user(id)`.
+    pub path: String,
+    pub message: String,
+}
+
+impl Change {
+    fn new(severity: Severity, path: impl Display, message: impl Into<String>) -> Self {
+        Self { severity, path: path.to_string(), message: message.into() }
+    }
+}
+
+impl Blueprint {
+    /// Lists the changes of the schema and of the resolvers from this
+    /// blueprint to the `new` one, sorted by severity.
+    pub fn diff(&self, new: &Blueprint) -> Vec<Change> {
+        let mut changes = vec![];
+
+        diff_root(
+            &mut changes,
+            "query",
+            Some(&self.schema.query),
+            Some(&new.schema.query),
+        );
+        diff_root(
+            &mut changes,
+            "mutation",
+            self.schema.mutation.as_ref(),
+            new.schema.mutation.as_ref(),
+        );
+
+        let old_definitions = definitions(self);
+        let new_definitions = definitions(new);
+        for (name, old) in old_definitions.iter() {
+            match new_definitions.get(name) {
+                Some(new) => diff_definition(&mut changes, old, new),
+                None => changes.push(Change::new(
+                    Severity::Breaking,
+                    name,
+                    format!("{} was removed", kind(old)),
+                )),
+            }
+        }
+        for (name, new) in new_definitions.iter() {
+            if !old_definitions.contains_key(name) {
+                changes.push(Change::new(
+                    Severity::Safe,
+                    name,
+                    format!("{} was added", kind(new)),
+                ));
+            }
+        }
+
+        changes.sort_by(|a, b| (a.severity, &a.path).cmp(&(b.severity, &b.path)));
+        changes
+    }
+}
+
+fn definitions(blueprint: &Blueprint) -> BTreeMap<&str, &Definition> {
+    blueprint
+        .definitions
+        .iter()
+        .map(|definition| (definition.name(), definition))
+        .collect()
+}
+
+fn kind(definition: &Definition) -> &'static str {
+    match definition {
+        Definition::Interface(_) => "Interface",
+        Definition::Object(_) => "Object",
+        Definition::InputObject(_) => "Input object",
+        Definition::Scalar(_) => "Scalar",
+        Definition::Enum(_) => "Enum",
+        Definition::Union(_) => "Union",
+    }
+}
+
+fn diff_root(changes: &mut Vec<Change>, root: &str, old: Option<&String>, new: Option<&String>) {
+    match (old, new) {
+        (Some(old), Some(new)) if old != new => changes.push(Change::new(
+            Severity::Breaking,
+            "schema",
+            format!("Root {root} type changed from `{old}` to `{new}`"),
+        )),
+        (Some(_), None) => changes.push(Change::new(
+            Severity::Breaking,
+            "schema",
+            format!("Root {root} type was removed"),
+        )),
+        (None, Some(new)) => changes.push(Change::new(
+            Severity::Safe,
+            "schema",
+            format!("Root {root} type `{new}` was added"),
+        )),
+        _ => {}
+    }
+}
+
+fn diff_definition(changes: &mut Vec<Change>, old: &Definition, new: &Definition) {
+    let name = old.name();
+    match (old, new) {
+        (Definition::Object(old), Definition::Object(new)) => {
+            diff_fields(changes, name, &old.fields, &new.fields);
+            diff_members(changes, name, "Interface", &old.implements, &new.implements);
+        }
+        (Definition::Interface(old), Definition::Interface(new)) => {
+            diff_fields(changes, name, &old.fields, &new.fields);
+            diff_members(changes, name, "Interface", &old.implements, &new.implements);
+        }
+        (Definition::InputObject(old), Definition::InputObject(new)) => {
+            diff_inputs(changes, name, "Input field", &old.fields, &new.fields);
+        }
+        (Definition::Enum(old), Definition::Enum(new)) => {
+            let values = |values: &[super::EnumValueDefinition]| {
+                values
+                    .iter()
+                    .map(|value| value.name.clone())
+                    .collect::<BTreeSet<_>>()
+            };
+            diff_members(
+                changes,
+                name,
+                "Value",
+                &values(&old.enum_values),
+                &values(&new.enum_values),
+            );
+        }
+        (Definition::Union(old), Definition::Union(new)) => {
+            diff_members(changes, name, "Member", &old.types, &new.types);
+        }
+        (Definition::Scalar(_), Definition::Scalar(_)) => {}
+        (old, new) => changes.push(Change::new(
+            Severity::Breaking,
+            name,
+            format!("Kind changed from {} to {}", kind(old), kind(new)),
+        )),
+    }
+}
+
+/// Diffs the members of enums and unions, and the interfaces implemented by
+/// objects. Clients may not handle the new members, so adding them is
+/// dangerous.
+fn diff_members(
+    changes: &mut Vec<Change>,
+    name: &str,
+    member: &str,
+    old: &BTreeSet<String>,
+    new: &BTreeSet<String>,
+) {
+    for removed in old.difference(new) {
+        changes.push(Change::new(
+            Severity::Breaking,
+            name,
+            format!("{member} `{removed}` was removed"),
+        ));
+    }
+    for added in new.difference(old) {
+        let severity = if member == "Interface" {
+            Severity::Safe
+        } else {
+            Severity::Dangerous
+        };
+        changes.push(Change::new(
+            severity,
+            name,
+            format!("{member} `{added}` was added"),
+        ));
+    }
+}
+
+fn diff_fields(
+    changes: &mut Vec<Change>,
+    name: &str,
+    old: &[FieldDefinition],
+    new: &[FieldDefinition],
+) {
+    for old in old.iter() {
+        let path = format!("{name}.{}", old.name);
+        let Some(new) = new.iter().find(|new| new.name == old.name) else {
+            changes.push(Change::new(Severity::Breaking, path, "Field was removed"));
+            continue;
+        };
+
+        if old.of_type != new.of_type {
+            let severity = if is_safe_output_change(&old.of_type, &new.of_type) {
+                Severity::Safe
+            } else {
+                Severity::Breaking
+            };
+            changes.push(Change::new(
+                severity,
+                &path,
+                format!(
+                    "Type changed from `{:?}` to `{:?}`",
+                    old.of_type, new.of_type
+                ),
+            ));
+        }
+
+        diff_inputs(changes, &path, "Argument", &old.args, &new.args);
+
+        let old_resolvers = resolvers(old.resolver.as_ref());
+        let new_resolvers = resolvers(new.resolver.as_ref());
+        if old_resolvers != new_resolvers {
+            changes.push(Change::new(
+                Severity::Dangerous,
+                &path,
+                format!(
+                    "Resolver changed from `{}` to `{}`",
+                    describe(&old_resolvers),
+                    describe(&new_resolvers)
+                ),
+            ));
+        }
+    }
+
+    for new in new.iter() {
+        if !old.iter().any(|old| old.name == new.name) {
+            changes.push(Change::new(
+                Severity::Safe,
+                format!("{name}.{}", new.name),
+                "Field was added",
+            ));
+        }
+    }
+}
+
+/// Diffs the arguments of a field or the fields of an input object.
+fn diff_inputs(
+    changes: &mut Vec<Change>,
+    name: &str,
+    input: &str,
+    old: &[InputFieldDefinition],
+    new: &[InputFieldDefinition],
+) {
+    for old in old.iter() {
+        let path = format!("{name}({})", old.name);
+        let Some(new) = new.iter().find(|new| new.name == old.name) else {
+            changes.push(Change::new(
+                Severity::Breaking,
+                path,
+                format!("{input} was removed"),
+            ));
+            continue;
+        };
+
+        if old.of_type != new.of_type {
+            let severity = if is_safe_input_change(&old.of_type, &new.of_type) {
+                Severity::Safe
+            } else {
+                Severity::Breaking
+            };
+            changes.push(Change::new(
+                severity,
+                &path,
+                format!(
+                    "Type changed from `{:?}` to `{:?}`",
+                    old.of_type, new.of_type
+                ),
+            ));
+        }
+
+        if old.default_value != new.default_value {
+            changes.push(Change::new(
+                Severity::Dangerous,
+                &path,
+                format!(
+                    "Default value changed from `{}` to `{}`",
+                    describe_value(old.default_value.as_ref()),
+                    describe_value(new.default_value.as_ref())
+                ),
+            ));
+        }
+    }
+
+    for new in new.iter() {
+        if old.iter().any(|old| old.name == new.name) {
+            continue;
+        }
+
+        let (severity, message) = if is_required(new) {
+            (
+                Severity::Breaking,
+                format!("Required {} was added", input.to_lowercase()),
+            )
+        } else {
+            (Severity::Safe, format!("{input} was added"))
+        };
+        changes.push(Change::new(
+            severity,
+            format!("{name}({})", new.name),
+            message,
+        ));
+    }
+}
+
+fn is_required(input: &InputFieldDefinition) -> bool {
+    !input.of_type.is_nullable() && input.default_value.is_none()
+}
+
+/// Output values of the old type are still valid when the new type is the same
+/// or stricter, e.g. `String` to `String!`.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (
+            Type::Named { name: old, non_null: old_non_null },
+            Type::Named { name: new, non_null: new_non_null },
+        ) => old == new && (*new_non_null || !old_non_null),
+        (
+            Type::List { of_type: old, non_null: old_non_null },
+            Type::List { of_type: new, non_null: new_non_null },
+        ) => (*new_non_null || !old_non_null) && is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+/// Input values of the old type are still valid when the new type is the same
+/// or looser, e.g. `String!` to `String`.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (
+            Type::Named { name: old, non_null: old_non_null },
+            Type::Named { name: new, non_null: new_non_null },
+        ) => old == new && (*old_non_null || !new_non_null),
+        (
+            Type::List { of_type: old, non_null: old_non_null },
+            Type::List { of_type: new, non_null: new_non_null },
+        ) => (*old_non_null || !new_non_null) && is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+/// Describes the calls made by a resolver to the upstream.
+fn resolvers(resolver: Option<&IR>) -> Vec<String> {
+    let mut resolvers = vec![];
+    if let Some(resolver) = resolver {
+        resolver
+            .clone()
+            .modify_io(&mut |io| resolvers.push(describe_io(io)));
+    }
+    resolvers
+}
+
+fn describe_io(io: &IO) -> String {
+    match io {
+        IO::Http { req_template, group_by, .. } => {
+            let mut description = format!("{} {}", req_template.method, req_template.root_url);
+            let query = req_template
+                .query
+                .iter()
+                .map(|query| format!("{}={}", query.key, query.value))
+                .collect::<Vec<_>>();
+            if !query.is_empty() {
+                description.push_str(&format!("?{}", query.join("&")));
+            }
+            if let Some(group_by) = group_by {
+                description.push_str(&format!(" batchKey: {}", group_by.path().join(".")));
+            }
+            description
+        }
+        IO::GraphQL { req_template, batch, .. } => {
+            let mut description = format!(
+                "{} {} {}",
+                req_template.operation_type, req_template.url, req_template.operation_name
+            );
+            if *batch {
+                description.push_str(" batched");
+            }
+            description
+        }
+        IO::Grpc { req_template, group_by, .. } => {
+            let mut description = format!(
+                "{} {}",
+                req_template.url,
+                req_template.operation.method.full_name()
+            );
+            if let Some(group_by) = group_by {
+                description.push_str(&format!(" batchKey: {}", group_by.path().join(".")));
+            }
+            description
+        }
+        IO::Js { name } => format!("js {name}"),
+    }
+}
+
+fn describe(resolvers: &[String]) -> String {
+    if resolvers.is_empty() {
+        "none".to_string()
+    } else {
+        resolvers.join(", ")
+    }
+}
+
+fn describe_value(value: Option<&serde_json::Value>) -> String {
+    value.map_or("none".to_string(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    fn blueprint(sdl: &str) -> Blueprint {
+        let config = Config::from_sdl(sdl).to_result().unwrap();
+        Blueprint::try_from(&ConfigModule::from(config)).unwrap()
+    }
+
+    const OLD: &str = r#"
+        schema @server @upstream { query: Query }
+
+        type Query {
+          user(id: Int!): User @http(url: "http://jsonplaceholder.typicode.com/users/{{.args.id}}")
+          users(limit: Int): [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+        }
+
+        enum Role { ADMIN USER }
+
+        type User {
+          id: Int!
+          name: String
+          email: String!
+          role: Role
+        }
+    "#;
+
+    fn changes(old: &str, new: &str) -> Vec<(Severity, String, String)> {
+        blueprint(old)
+            .diff(&blueprint(new))
+            .into_iter()
+            .map(|change| (change.severity, change.path, change.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        assert_eq!(changes(OLD, OLD), vec![]);
+    }
+
+    #[test]
+    fn test_output_changes() {
+        let new = OLD
+            .replace("name: String\n", "name: String!\n")
+            .replace("email: String!\n", "email: String\n  phone: String\n");
+
+        assert_eq!(
+            changes(OLD, &new),
+            vec![
+                (
+                    Severity::Breaking,
+                    "User.email".to_string(),
+                    "Type changed from `String!` to `String`".to_string()
+                ),
+                (
+                    Severity::Safe,
+                    "User.name".to_string(),
+                    "Type changed from `String` to `String!`".to_string()
+                ),
+                (
+                    Severity::Safe,
+                    "User.phone".to_string(),
+                    "Field was added".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_argument_and_enum_changes() {
+        let new = OLD
+            .replace("user(id: Int!)", "user(id: Int!, verbose: Boolean!)")
+            .replace("users(limit: Int)", "users(limit: Int!)")
+            .replace("enum Role { ADMIN USER }", "enum Role { USER GUEST }");
+
+        assert_eq!(
+            changes(OLD, &new),
+            vec![
+                (
+                    Severity::Breaking,
+                    "Query.user(verbose)".to_string(),
+                    "Required argument was added".to_string()
+                ),
+                (
+                    Severity::Breaking,
+                    "Query.users(limit)".to_string(),
+                    "Type changed from `Int` to `Int!`".to_string()
+                ),
+                (
+                    Severity::Breaking,
+                    "Role".to_string(),
+                    "Value `ADMIN` was removed".to_string()
+                ),
+                (
+                    Severity::Dangerous,
+                    "Role".to_string(),
+                    "Value `GUEST` was added".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolver_changes() {
+        let new = OLD.replace(
+            "http://jsonplaceholder.typicode.com/users\")",
+            "http://jsonplaceholder.typicode.com/v2/users\")",
+        );
+
+        assert_eq!(
+            changes(OLD, &new),
+            vec![(
+                Severity::Dangerous,
+                "Query.users".to_string(),
+                "Resolver changed from `GET http://jsonplaceholder.typicode.com/users` to `GET http://jsonplaceholder.typicode.com/v2/users`".to_string()
+            )]
+        );
+    }
+}
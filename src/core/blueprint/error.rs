@@ -61,6 +61,24 @@ pub enum BlueprintError {
     #[error("script is required")]
     ScriptIsRequired,
 
+    #[error("@server(cachePurge) requires at least one auth provider")]
+    CachePurgeNoAuthProviders,
+
+    #[error("@server(queryPlan) requires at least one auth provider")]
+    QueryPlanNoAuthProviders,
+
+    #[error("@invalidates can only be used on mutations")]
+    InvalidatesOnlyOnMutation,
+
+    #[error("@restExpose can only be used on the fields of Query and Mutation")]
+    RestExposeOnlyOnRootFields,
+
+    #[error("Script module '{0}' not found")]
+    ScriptModuleNotFound(String),
+
+    #[error("Script module id '{0}' must be non-empty and can't contain '.'")]
+    InvalidScriptModuleId(String),
+
     #[error("Field is already implemented from interface")]
     FieldExistsInInterface,
 
@@ -154,6 +172,9 @@ pub enum BlueprintError {
     #[error("Key is required for HTTP2")]
     KeyIsRequiredForHTTP2,
 
+    #[error("Client authentication is only available for HTTP2")]
+    ClientAuthRequiresHTTP2,
+
     #[error("Experimental headers must start with 'x-' or 'X-'. Got: '{0}'")]
     ExperimentalHeaderInvalidFormat(String),
 
@@ -163,6 +184,15 @@ pub enum BlueprintError {
     #[error("Invalid CORS configuration: Cannot combine `Access-Control-Allow-Credentials: true` with `{0}: *`")]
     InvalidCORSConfiguration(String),
 
+    #[error("ClientCert and ClientKey links must be used together")]
+    ClientCertRequiresClientKey,
+
+    #[error("Link with id '{0}' not found")]
+    LinkNotFound(String),
+
+    #[error("OAuth2 `{0}` can't be empty")]
+    OAuth2FieldCanNotBeEmpty(String),
+
     #[error("{0}")]
     Cause(String),
 
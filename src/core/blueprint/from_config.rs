@@ -52,6 +52,7 @@ pub fn config_blueprint<'a>() -> TryFold<'a, ConfigModule, Blueprint, BlueprintE
         // set the federation config only after setting other properties to be able
         // to use blueprint inside the handler and to avoid recursion overflow
         .and(update_federation().trace("federation"))
+        .and(update_mock_data())
         .update(apply_batching)
         .update(compress)
 }
@@ -9,6 +9,7 @@ use crate::core::blueprint::{Blueprint, Definition};
 use crate::core::http::RequestContext;
 use crate::core::ir::{EvalContext, ResolverContext, TypedValue};
 use crate::core::jit::graphql_error::ErrorExtensions;
+use crate::core::metrics;
 use crate::core::scalar::Scalar;
 
 /// We set the default value for an `InputValue` by reading it from the
@@ -59,6 +60,9 @@ fn to_type(def: &Definition) -> dynamic::Type {
                 let field = field.clone();
                 let type_ref = TypeRef::from(&field.of_type);
                 let field_name = &field.name.clone();
+                // Labels of the resolver metrics, shared by every call of the resolver.
+                let labels: Arc<(String, String)> =
+                    Arc::new((def.name.clone(), field.name.clone()));
 
                 let mut dyn_schema_field = dynamic::Field::new(
                     field_name,
@@ -89,13 +93,21 @@ fn to_type(def: &Definition) -> dynamic::Type {
                                 );
 
                                 let expr = expr.to_owned();
+                                let labels = labels.clone();
                                 FieldFuture::new(
                                     async move {
                                         let ctx: ResolverContext = ctx.into();
                                         let ctx = &mut EvalContext::new(req_ctx, &ctx);
 
-                                        let value =
-                                            expr.eval(ctx).await.map_err(|err| err.extend())?;
+                                        let timer = metrics::Timer::start();
+                                        let value = expr.eval(ctx).await;
+                                        metrics::record_resolver(
+                                            timer,
+                                            &labels.0,
+                                            &labels.1,
+                                            value.is_err(),
+                                        );
+                                        let value = value.map_err(|err| err.extend())?;
 
                                         if let ConstValue::Null = value {
                                             Ok(FieldValue::NONE)
@@ -27,12 +27,26 @@ impl TryFrom<Vec<Link>> for Links {
                     }
                     Valid::succeed(link)
                 })
+                .and_then(|link| {
+                    // The id of a script module is the prefix of the `@js`
+                    // names that reference its exports, so it can't contain
+                    // dots.
+                    match &link.id {
+                        Some(id)
+                            if link.type_of == LinkType::Script
+                                && (id.is_empty() || id.contains('.')) =>
+                        {
+                            Valid::fail(BlueprintError::InvalidScriptModuleId(id.clone()))
+                        }
+                        _ => Valid::succeed(link),
+                    }
+                })
                 .trace(&pos.to_string())
         })
         .and_then(|links| {
             let script_links = links
                 .iter()
-                .filter(|l| l.type_of == LinkType::Script)
+                .filter(|l| l.type_of == LinkType::Script && l.id.is_none())
                 .collect::<Vec<&Link>>();
 
             if script_links.len() > 1 {
@@ -59,3 +73,44 @@ impl TryFrom<Vec<Link>> for Links {
         .to_result()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(type_of: LinkType, id: Option<&str>, src: &str) -> Link {
+        Link {
+            id: id.map(ToString::to_string),
+            src: src.to_string(),
+            type_of,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_script_modules() {
+        let links = vec![
+            link(LinkType::Script, None, "main.js"),
+            link(LinkType::Script, Some("orders"), "orders.js"),
+            link(LinkType::Script, Some("users"), "users.js"),
+        ];
+        assert!(Links::try_from(links).is_ok());
+
+        let links = vec![
+            link(LinkType::Script, None, "main.js"),
+            link(LinkType::Script, None, "other.js"),
+        ];
+        let error = Links::try_from(links).err().unwrap();
+        assert!(matches!(
+            &error.as_vec()[0].message,
+            BlueprintError::OnlyOneScriptLinkAllowed
+        ));
+
+        let links = vec![link(LinkType::Script, Some("shop.orders"), "orders.js")];
+        let error = Links::try_from(links).err().unwrap();
+        assert!(matches!(
+            &error.as_vec()[0].message,
+            BlueprintError::InvalidScriptModuleId(id) if id == "shop.orders"
+        ));
+    }
+}
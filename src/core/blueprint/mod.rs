@@ -3,6 +3,7 @@ mod blueprint;
 mod compress;
 mod cors;
 mod definitions;
+mod diff;
 mod directive;
 mod dynamic_value;
 mod error;
@@ -15,6 +16,7 @@ mod links;
 mod mustache;
 mod operators;
 mod schema;
+mod selection;
 mod server;
 pub mod telemetry;
 mod template_validation;
@@ -26,6 +28,7 @@ pub use auth::*;
 pub use blueprint::*;
 pub use cors::*;
 pub use definitions::*;
+pub use diff::{Change, Severity};
 pub use dynamic_value::*;
 pub use error::*;
 pub use from_config::*;
@@ -33,6 +36,7 @@ pub use index::*;
 pub use links::*;
 pub use operators::*;
 pub use schema::*;
+pub use selection::{SelectedField, SelectionWalker};
 pub use server::*;
 pub use timeout::GlobalTimeout;
 pub use upstream::*;
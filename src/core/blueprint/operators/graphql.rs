@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use tailcall_valid::{Valid, Validator};
 
-use crate::core::blueprint::BlueprintError;
+use crate::core::blueprint::{BlueprintError, UpstreamAuth};
 use crate::core::config::{Config, ConfigModule, GraphQL, GraphQLOperationType};
 use crate::core::graphql::RequestTemplate;
 use crate::core::helpers;
@@ -81,10 +81,11 @@ pub fn compile_graphql(
                 Err(err) => Valid::fail(BlueprintError::Error(err)),
             }
         })
-        .map(|req_template| {
+        .zip(UpstreamAuth::compile(config, graphql.auth.as_ref()))
+        .map(|(req_template, auth)| {
             let field_name = graphql.name.clone();
             let batch = graphql.batch;
             let dedupe = graphql.dedupe;
-            IR::IO(IO::GraphQL { req_template, field_name, batch, dl_id: None, dedupe })
+            IR::IO(IO::GraphQL { req_template, field_name, batch, dl_id: None, dedupe, auth })
         })
 }
@@ -5,7 +5,7 @@ use prost_reflect::FieldDescriptor;
 use tailcall_valid::{Valid, ValidationError, Validator};
 
 use super::apply_select;
-use crate::core::blueprint::BlueprintError;
+use crate::core::blueprint::{BlueprintError, UpstreamAuth};
 use crate::core::config::group_by::GroupBy;
 use crate::core::config::{Config, ConfigModule, Field, GraphQLOperationType, Grpc};
 use crate::core::grpc::protobuf::{ProtobufOperation, ProtobufSet};
@@ -218,7 +218,8 @@ pub fn compile_grpc(inputs: CompileGrpc) -> Valid<IR, BlueprintError> {
             };
             validation.map(|_| (url, headers, operation, body))
         })
-        .map(|(url, headers, operation, body)| {
+        .zip(UpstreamAuth::compile(config_module, grpc.auth.as_ref()))
+        .map(|((url, headers, operation, body), auth)| {
             let req_template = RequestTemplate {
                 url,
                 headers,
@@ -236,9 +237,17 @@ pub fn compile_grpc(inputs: CompileGrpc) -> Valid<IR, BlueprintError> {
                     dl_id: None,
                     dedupe,
                     hook,
+                    auth,
                 })
             } else {
-                IR::IO(IO::Grpc { req_template, group_by: None, dl_id: None, dedupe, hook })
+                IR::IO(IO::Grpc {
+                    req_template,
+                    group_by: None,
+                    dl_id: None,
+                    dedupe,
+                    hook,
+                    auth,
+                })
             };
 
             (io, &grpc.select)
@@ -42,7 +42,8 @@ pub fn compile_http(
         )
         .and(Valid::succeed(http.url.as_str()))
         .zip(mustache_headers)
-        .and_then(|(base_url, headers)| {
+        .zip(UpstreamAuth::compile(config_module, http.auth.as_ref()))
+        .and_then(|((base_url, headers), auth)| {
             let query = http
                 .query
                 .clone()
@@ -65,27 +66,27 @@ pub fn compile_http(
             )
             .map(|req_tmpl| req_tmpl.headers(headers))
             {
-                Ok(data) => Valid::succeed(data),
+                Ok(data) => Valid::succeed((data, auth)),
                 Err(e) => Valid::fail(BlueprintError::Error(e)),
             }
         })
-        .and_then(|request_template| {
+        .and_then(|(request_template, auth)| {
             if !http.batch_key.is_empty() && (http.body.is_some() || http.method != Method::GET) {
                 if let Some(body) = http.body.as_ref() {
                     let dynamic_paths = count_dynamic_paths(body);
                     if dynamic_paths != 1 {
                         Valid::fail(BlueprintError::BatchRequiresDynamicParameter).trace("body")
                     } else {
-                        Valid::succeed(request_template)
+                        Valid::succeed((request_template, auth))
                     }
                 } else {
                     Valid::fail(BlueprintError::BatchRequiresDynamicParameter).trace("body")
                 }
             } else {
-                Valid::succeed(request_template)
+                Valid::succeed((request_template, auth))
             }
         })
-        .map(|req_template| {
+        .map(|(req_template, auth)| {
             // marge http and upstream on_request
             let on_request = http
                 .on_request
@@ -113,6 +114,7 @@ pub fn compile_http(
                     is_list,
                     dedupe,
                     hook,
+                    auth,
                 })
             } else {
                 IR::IO(IO::Http {
@@ -122,6 +124,7 @@ pub fn compile_http(
                     is_list,
                     dedupe,
                     hook,
+                    auth,
                 })
             };
             (io, &http.select)
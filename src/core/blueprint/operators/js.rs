@@ -1,16 +1,69 @@
 use tailcall_valid::{Valid, Validator};
 
 use crate::core::blueprint::BlueprintError;
-use crate::core::config::JS;
+use crate::core::config::{ScriptModule, JS};
 use crate::core::ir::model::{IO, IR};
 
 pub struct CompileJs<'a> {
     pub js: &'a JS,
     pub script: &'a Option<String>,
+    pub modules: &'a [ScriptModule],
 }
 
 pub fn compile_js(inputs: CompileJs) -> Valid<IR, BlueprintError> {
     let name = &inputs.js.name;
-    Valid::from_option(inputs.script.as_ref(), BlueprintError::ScriptIsRequired)
-        .map(|_| IR::IO(IO::Js { name: name.to_string() }))
+
+    // Names in the form of `<id>.<function>` reference an export of the module
+    // linked with that id, the rest are looked up in the global script.
+    match name.split_once('.') {
+        Some((id, _)) => Valid::from_option(
+            inputs.modules.iter().find(|module| module.id == id),
+            BlueprintError::ScriptModuleNotFound(id.to_string()),
+        )
+        .map(|_| IR::IO(IO::Js { name: name.to_string() })),
+        None => Valid::from_option(inputs.script.as_ref(), BlueprintError::ScriptIsRequired)
+            .map(|_| IR::IO(IO::Js { name: name.to_string() })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modules() -> Vec<ScriptModule> {
+        vec![ScriptModule {
+            id: "orders".to_string(),
+            path: "orders.js".to_string(),
+            source: "export function total() {}".to_string(),
+        }]
+    }
+
+    #[test]
+    fn test_module_function() {
+        let js = JS { name: "orders.total".to_string() };
+        let actual = compile_js(CompileJs { js: &js, script: &None, modules: &modules() });
+        assert!(actual.is_succeed());
+    }
+
+    #[test]
+    fn test_unknown_module() {
+        let js = JS { name: "users.total".to_string() };
+        let actual = compile_js(CompileJs { js: &js, script: &None, modules: &modules() });
+        let error = actual.to_result().unwrap_err();
+        assert!(matches!(
+            &error.as_vec()[0].message,
+            BlueprintError::ScriptModuleNotFound(id) if id == "users"
+        ));
+    }
+
+    #[test]
+    fn test_global_function_requires_script() {
+        let js = JS { name: "total".to_string() };
+        let actual = compile_js(CompileJs { js: &js, script: &None, modules: &modules() });
+        let error = actual.to_result().unwrap_err();
+        assert!(matches!(
+            &error.as_vec()[0].message,
+            BlueprintError::ScriptIsRequired
+        ));
+    }
 }
@@ -0,0 +1,466 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use async_graphql_value::{ConstValue, Name};
+use chrono::{DateTime, SecondsFormat};
+use indexmap::IndexMap;
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+use serde_json::Number;
+use tailcall_hasher::TailcallHasher;
+use tailcall_valid::Valid;
+
+use crate::core::blueprint::*;
+use crate::core::config::{self, Config, ConfigModule, Expr, Field};
+use crate::core::ir::model::{Map, Mock, IR};
+use crate::core::scalar::Scalar;
+use crate::core::try_fold::TryFold;
+use crate::core::Type;
+
+/// Number of items of the generated lists.
+const LIST_SIZE: std::ops::RangeInclusive<usize> = 1..=3;
+
+const WORDS: &[&str] = &[
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliet",
+    "kilo", "lima", "mike", "november", "oscar", "papa",
+];
+
+/// Resolves the fields overridden with `@mock` to their value when the server
+/// runs in mock mode. The other fields are mocked by [update_mock_data].
+pub fn update_mock<'a>() -> TryFold<
+    'a,
+    (&'a ConfigModule, &'a Field, &'a config::Type, &'a str),
+    FieldDefinition,
+    BlueprintError,
+> {
+    TryFold::<(&ConfigModule, &Field, &config::Type, &'a str), FieldDefinition, BlueprintError>::new(
+        move |(config_module, field, _, _), mut b_field| {
+            let Some(mock) = field.mock.as_ref() else {
+                return Valid::succeed(b_field);
+            };
+            if !config_module.server.enable_mock() {
+                return Valid::succeed(b_field);
+            }
+
+            let expr = Expr { body: mock.value.clone() };
+            compile_expr(CompileExpr { config_module, field, expr: &expr, validate: true }).map(
+                |resolver| {
+                    b_field.resolver = Some(resolver);
+                    b_field
+                },
+            )
+        },
+    )
+}
+
+/// Replaces the resolvers that call the upstream with synthetic data when the
+/// server runs in mock mode. The objects are generated without the fields of
+/// object types, which are generated in turn as they are resolved, so that
+/// only the selected fields are generated, however recursive the types are.
+pub fn update_mock_data<'a>() -> TryFoldConfig<'a, Blueprint> {
+    TryFoldConfig::<Blueprint>::new(|config_module, mut blueprint| {
+        if !config_module.server.enable_mock() {
+            return Valid::succeed(blueprint);
+        }
+
+        let generator = Arc::new(MockGenerator::new(config_module));
+        for definition in blueprint.definitions.iter_mut() {
+            match definition {
+                Definition::Object(object) => {
+                    mock_fields(&generator, &object.name, &mut object.fields)
+                }
+                Definition::Interface(interface) => {
+                    mock_fields(&generator, &interface.name, &mut interface.fields)
+                }
+                _ => {}
+            }
+        }
+
+        Valid::succeed(blueprint)
+    })
+}
+
+fn mock_fields(generator: &Arc<MockGenerator>, type_name: &str, fields: &mut [FieldDefinition]) {
+    for field in fields.iter_mut() {
+        let key = match field.resolver.as_mut() {
+            Some(resolver) => {
+                let mut has_io = false;
+                resolver.modify_io(&mut |_| has_io = true);
+                if !has_io {
+                    continue;
+                }
+                None
+            }
+            None if generator.is_object(field.of_type.name()) => Some(field.name.clone()),
+            None => continue,
+        };
+
+        let mock = IR::Mock(Mock {
+            generator: generator.clone(),
+            path: format!("{type_name}.{}", field.name),
+            type_of: field.of_type.clone(),
+            key,
+        });
+        field.resolver = Some(match field.resolver.take() {
+            Some(resolver) => replace_upstream(resolver, mock),
+            None => mock,
+        });
+    }
+}
+
+/// Replaces the call to the upstream with the mock, keeping the resolvers that
+/// wrap it, e.g. to check the auth of the field or to resolve its union type.
+fn replace_upstream(resolver: IR, mock: IR) -> IR {
+    match resolver {
+        IR::Protect(auth, ir) => IR::Protect(auth, Box::new(replace_upstream(*ir, mock))),
+        IR::Invalidate(tags, ir) => IR::Invalidate(tags, Box::new(replace_upstream(*ir, mock))),
+        IR::Discriminate(discriminator, ir) => {
+            IR::Discriminate(discriminator, Box::new(replace_upstream(*ir, mock)))
+        }
+        IR::Map(Map { input, map }) => {
+            IR::Map(Map { input: Box::new(replace_upstream(*input, mock)), map })
+        }
+        _ => mock,
+    }
+}
+
+/// Generates values that match the types of the configuration.
+#[derive(Debug)]
+pub struct MockGenerator {
+    config: Config,
+    interfaces: BTreeMap<String, BTreeSet<String>>,
+    seed: u64,
+}
+
+impl MockGenerator {
+    fn new(config_module: &ConfigModule) -> Self {
+        Self {
+            config: config_module.config().clone(),
+            interfaces: config_module.interfaces_types_map(),
+            seed: config_module.server.get_mock_seed(),
+        }
+    }
+
+    /// Generates the value of the field at the path, e.g. `Query.users`. The
+    /// values are derived from the seed, the arguments of the field and the
+    /// value of its parent, so that the same query always returns the same
+    /// data.
+    pub fn generate(
+        &self,
+        path: &str,
+        type_of: &Type,
+        args: Option<&ConstValue>,
+        parent: Option<&ConstValue>,
+    ) -> ConstValue {
+        let mut hasher = TailcallHasher::default();
+        self.seed.hash(&mut hasher);
+        path.hash(&mut hasher);
+        hash_value(args.unwrap_or(&ConstValue::Null), &mut hasher);
+        hash_value(parent.unwrap_or(&ConstValue::Null), &mut hasher);
+
+        Values { generator: self, rng: StdRng::seed_from_u64(hasher.finish()) }.generate(type_of)
+    }
+
+    fn is_object(&self, name: &str) -> bool {
+        self.config.find_type(name).is_some() || self.config.find_union(name).is_some()
+    }
+}
+
+/// Hashes the value regardless of the order of the keys of its objects.
+fn hash_value(value: &ConstValue, hasher: &mut impl Hasher) {
+    match value {
+        ConstValue::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+            for (key, value) in entries {
+                key.as_str().hash(hasher);
+                hash_value(value, hasher);
+            }
+        }
+        ConstValue::List(list) => list.iter().for_each(|value| hash_value(value, hasher)),
+        value => value.to_string().hash(hasher),
+    }
+}
+
+/// Values generated for a single field.
+struct Values<'a> {
+    generator: &'a MockGenerator,
+    rng: StdRng,
+}
+
+impl Values<'_> {
+    fn generate(&mut self, type_of: &Type) -> ConstValue {
+        match type_of {
+            Type::List { of_type, .. } => {
+                let size = self.rng.gen_range(LIST_SIZE);
+                ConstValue::List((0..size).map(|_| self.generate(of_type)).collect())
+            }
+            Type::Named { name, .. } => {
+                if let Some(value) = self.scalar(name) {
+                    return value;
+                }
+                if let Some(enum_) = self.generator.config.find_enum(name) {
+                    return enum_
+                        .variants
+                        .iter()
+                        .choose(&mut self.rng)
+                        .map(|variant| ConstValue::Enum(Name::new(&variant.name)))
+                        .unwrap_or_default();
+                }
+                self.object(name)
+            }
+        }
+    }
+
+    fn object(&mut self, name: &str) -> ConstValue {
+        let generator = self.generator;
+        // unions and interfaces are generated as one of their types
+        let possible_types = generator
+            .config
+            .find_union(name)
+            .map(|union_| &union_.types)
+            .or_else(|| generator.interfaces.get(name));
+        let type_name = match possible_types {
+            Some(types) => match types.iter().choose(&mut self.rng) {
+                Some(type_name) => type_name.as_str(),
+                None => return ConstValue::Null,
+            },
+            None => name,
+        };
+        let Some(type_) = generator.config.find_type(type_name) else {
+            return ConstValue::Null;
+        };
+
+        let mut object = IndexMap::new();
+        if possible_types.is_some() {
+            object.insert(
+                Name::new("__typename"),
+                ConstValue::String(type_name.to_string()),
+            );
+        }
+        // the fields with a resolver are generated by their own resolver, and
+        // so are the fields of object types, once they are resolved
+        for (field_name, field) in type_.fields.iter() {
+            if !field.has_resolver() && !generator.is_object(field.type_of.name()) {
+                let value = self.generate(&field.type_of);
+                object.insert(Name::new(field_name), value);
+            }
+        }
+
+        ConstValue::Object(object)
+    }
+
+    fn scalar(&mut self, name: &str) -> Option<ConstValue> {
+        let value = match name {
+            "String" => ConstValue::String(self.words(2)),
+            "ID" => ConstValue::String(self.rng.gen_range(1..10_000).to_string()),
+            "Int" => number(self.rng.gen_range(0..1_000)),
+            "Float" => {
+                let float = (self.rng.gen_range(0.0..1_000.0) * 100.0_f64).round() / 100.0;
+                ConstValue::Number(Number::from_f64(float)?)
+            }
+            "Boolean" => ConstValue::Boolean(self.rng.gen()),
+            name => match Scalar::find(name)? {
+                Scalar::Empty => ConstValue::Null,
+                Scalar::Email => ConstValue::String(format!("{}@example.com", self.words(1))),
+                Scalar::PhoneNumber => {
+                    ConstValue::String(format!("+1415555{:04}", self.rng.gen_range(0..10_000)))
+                }
+                // the Date scalar is validated as an RFC 3339 timestamp
+                Scalar::Date | Scalar::DateTime => ConstValue::String(self.date_time()),
+                Scalar::Url => ConstValue::String(format!("https://example.com/{}", self.words(1))),
+                Scalar::JSON => {
+                    let mut object = IndexMap::new();
+                    object.insert(Name::new(self.words(1)), ConstValue::String(self.words(1)));
+                    ConstValue::Object(object)
+                }
+                Scalar::Int8 => number(self.rng.gen::<i8>()),
+                Scalar::Int16 => number(self.rng.gen::<i16>()),
+                Scalar::Int32 => number(self.rng.gen::<i32>()),
+                Scalar::UInt8 => number(self.rng.gen::<u8>()),
+                Scalar::UInt16 => number(self.rng.gen::<u16>()),
+                Scalar::UInt32 => number(self.rng.gen::<u32>()),
+                // the 64 and 128 bit integers are represented as strings
+                Scalar::Int64 => ConstValue::String(self.rng.gen::<i64>().to_string()),
+                Scalar::UInt64 => ConstValue::String(self.rng.gen::<u64>().to_string()),
+                Scalar::Int128 => ConstValue::String(self.rng.gen::<i128>().to_string()),
+                Scalar::UInt128 => ConstValue::String(self.rng.gen::<u128>().to_string()),
+                Scalar::Bytes => ConstValue::String(self.words(1)),
+            },
+        };
+
+        Some(value)
+    }
+
+    fn words(&mut self, count: usize) -> String {
+        (0..count)
+            .map(|_| WORDS[self.rng.gen_range(0..WORDS.len())])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn date_time(&mut self) -> String {
+        // between 2020-01-01 and 2025-01-01
+        let seconds = self.rng.gen_range(1_577_836_800..1_735_689_600);
+        DateTime::from_timestamp(seconds, 0)
+            .map(|date_time| date_time.to_rfc3339_opts(SecondsFormat::Secs, true))
+            .unwrap_or_default()
+    }
+}
+
+fn number(value: impl Into<Number>) -> ConstValue {
+    ConstValue::Number(value.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+        schema @server(mock: true, mockSeed: 42) {
+          query: Query
+        }
+
+        type Query {
+          users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+          user(id: Int!): User @http(url: "http://jsonplaceholder.typicode.com/users/{{.args.id}}")
+          count: Int @http(url: "http://jsonplaceholder.typicode.com/count") @mock(value: 42)
+        }
+
+        enum Role {
+          ADMIN
+          GUEST
+        }
+
+        type User {
+          id: Int!
+          email: Email!
+          createdAt: DateTime
+          role: Role!
+          manager: User!
+          friends: [User]
+        }
+    "#;
+
+    fn config_module(sdl: &str) -> ConfigModule {
+        ConfigModule::from(Config::from_sdl(sdl).to_result().unwrap())
+    }
+
+    fn user_type() -> Type {
+        Type::Named { name: "User".to_string(), non_null: true }
+    }
+
+    fn args(id: i32) -> ConstValue {
+        ConstValue::from_json(serde_json::json!({ "id": id })).unwrap()
+    }
+
+    fn find_object<'a>(blueprint: &'a Blueprint, name: &str) -> &'a ObjectTypeDefinition {
+        blueprint
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::Object(object) if object.name == name => Some(object),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn find_field<'a>(object: &'a ObjectTypeDefinition, name: &str) -> &'a FieldDefinition {
+        object
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let generator = MockGenerator::new(&config_module(CONFIG));
+        let user = generator
+            .generate("Query.user", &user_type(), Some(&args(1)), None)
+            .into_json()
+            .unwrap();
+
+        assert!(user["id"].is_number());
+        assert!(user["email"].as_str().unwrap().ends_with("@example.com"));
+        assert!(DateTime::parse_from_rfc3339(user["createdAt"].as_str().unwrap()).is_ok());
+        assert!(["ADMIN", "GUEST"].contains(&user["role"].as_str().unwrap()));
+        // the fields of object types are generated once they are resolved
+        assert!(user.get("manager").is_none());
+        assert!(user.get("friends").is_none());
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let generate = |sdl: &str, id: i32| {
+            MockGenerator::new(&config_module(sdl)).generate(
+                "Query.user",
+                &user_type(),
+                Some(&args(id)),
+                None,
+            )
+        };
+
+        assert_eq!(generate(CONFIG, 1), generate(CONFIG, 1));
+        assert_ne!(generate(CONFIG, 1), generate(CONFIG, 2));
+        assert_ne!(
+            generate(CONFIG, 1),
+            generate(&CONFIG.replace("mockSeed: 42", "mockSeed: 7"), 1)
+        );
+    }
+
+    #[test]
+    fn test_update_mock() {
+        let blueprint = Blueprint::try_from(&config_module(CONFIG)).unwrap();
+        let query = find_object(&blueprint, "Query");
+        let user = find_object(&blueprint, "User");
+
+        for name in ["users", "user"] {
+            let resolver = find_field(query, name).resolver.as_ref();
+            assert!(matches!(resolver, Some(IR::Mock(Mock { key: None, .. }))));
+        }
+        assert!(matches!(
+            find_field(query, "count").resolver.as_ref(),
+            Some(IR::Dynamic(DynamicValue::Value(ConstValue::Number(n)))) if n.as_i64() == Some(42)
+        ));
+        for name in ["manager", "friends"] {
+            let resolver = find_field(user, name).resolver.as_ref();
+            assert!(matches!(resolver, Some(IR::Mock(Mock { key: Some(key), .. })) if key == name));
+        }
+        assert!(find_field(user, "id").resolver.is_none());
+    }
+
+    #[test]
+    fn test_non_null_fields() {
+        let blueprint = Blueprint::try_from(&config_module(CONFIG)).unwrap();
+        let resolver =
+            |object: &str, field: &str| match find_field(find_object(&blueprint, object), field)
+                .resolver
+                .clone()
+            {
+                Some(IR::Mock(mock)) => mock,
+                resolver => panic!("unexpected resolver: {resolver:?}"),
+            };
+
+        // the non-null recursive fields are never cut off with null
+        let mut user = resolver("Query", "user").resolve(Some(&args(1)), None);
+        for _ in 0..10 {
+            let manager = resolver("User", "manager").resolve(None, Some(&user));
+            assert!(matches!(manager, ConstValue::Object(_)));
+            assert_ne!(manager, user);
+            user = manager;
+        }
+
+        // the values of the parents that aren't generated are kept
+        let manager = ConstValue::from_json(serde_json::json!({ "id": 1 })).unwrap();
+        let parent = ConstValue::from_json(serde_json::json!({ "manager": { "id": 1 } })).unwrap();
+        assert_eq!(
+            resolver("User", "manager").resolve(None, Some(&parent)),
+            manager
+        );
+    }
+}
@@ -6,6 +6,7 @@ mod graphql;
 mod grpc;
 mod http;
 mod js;
+mod mock;
 mod modify;
 mod protected;
 mod resolver;
@@ -19,6 +20,7 @@ pub use graphql::*;
 pub use grpc::*;
 pub use http::*;
 pub use js::*;
+pub use mock::*;
 pub use modify::*;
 pub use protected::*;
 pub use resolver::*;
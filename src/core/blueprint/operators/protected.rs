@@ -26,12 +26,14 @@ pub fn update_protected<'a>(
                     return Valid::fail(BlueprintError::InputTypesCannotBeProtected);
                 }
 
-                if !config.extensions().has_auth() {
+                let providers = Provider::from_config(config);
+
+                if providers.is_empty() {
                     return Valid::fail(BlueprintError::ProtectedOperatorNoAuthProviders);
                 }
 
                 // Used to collect the providers that are used in the field
-                let providers: std::collections::HashMap<_, _> = Provider::from_config(config)
+                let providers: std::collections::HashMap<_, _> = providers
                     .into_iter()
                     .filter_map(|provider| provider.id.clone().map(|id| (id, provider.content)))
                     .collect();
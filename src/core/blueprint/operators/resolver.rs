@@ -39,8 +39,13 @@ pub fn compile_resolver(
         Resolver::Call(call) => compile_call(config_module, call, operation_type, object_name)
             .trace(config::Call::trace_name().as_str()),
         Resolver::Js(js) => {
-            compile_js(super::CompileJs { js, script: &config_module.extensions().script })
-                .trace(config::JS::trace_name().as_str())
+            let extensions = config_module.extensions();
+            compile_js(super::CompileJs {
+                js,
+                script: &extensions.script,
+                modules: &extensions.script_modules,
+            })
+            .trace(config::JS::trace_name().as_str())
         }
         Resolver::Expr(expr) => {
             compile_expr(super::CompileExpr { config_module, field, expr, validate: true })
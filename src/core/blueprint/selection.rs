@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use async_graphql::parser::types::{ExecutableDocument, OperationType, Selection, SelectionSet};
+
+use super::{Blueprint, Definition, FieldDefinition};
+
+/// Field of the selection set of an operation, resolved over the definitions
+/// of the blueprint.
+pub struct SelectedField<'a, 'b> {
+    /// Alias of the field, or its name.
+    pub key: String,
+    /// Definition of the field, `None` for `__typename`.
+    pub definition: Option<&'a FieldDefinition>,
+    pub selection: &'b SelectionSet,
+    /// Fields selected through fragments on a different type (e.g. a union
+    /// member) only show up for some values.
+    pub always: bool,
+}
+
+/// Walks the selection sets of the operations over the types of the
+/// blueprint, to describe the shape of their results.
+pub struct SelectionWalker<'a> {
+    definitions: HashMap<&'a str, &'a Definition>,
+    query: &'a str,
+    mutation: Option<&'a str>,
+}
+
+impl<'a> SelectionWalker<'a> {
+    pub fn new(blueprint: &'a Blueprint) -> Self {
+        Self {
+            definitions: blueprint
+                .definitions
+                .iter()
+                .map(|definition| (definition.name(), definition))
+                .collect(),
+            query: blueprint.schema.query.as_str(),
+            mutation: blueprint.schema.mutation.as_deref(),
+        }
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&'a Definition> {
+        self.definitions.get(name).copied()
+    }
+
+    /// Name of the type the operation selects its fields on.
+    pub fn root(&self, ty: OperationType) -> Option<&'a str> {
+        match ty {
+            OperationType::Query => Some(self.query),
+            OperationType::Mutation => self.mutation,
+            OperationType::Subscription => None,
+        }
+    }
+
+    pub fn field(&self, type_name: &str, field_name: &str) -> Option<&'a FieldDefinition> {
+        let fields = match self.definition(type_name)? {
+            Definition::Object(def) => &def.fields,
+            Definition::Interface(def) => &def.fields,
+            _ => return None,
+        };
+        fields.iter().find(|field| field.name == field_name)
+    }
+
+    /// Returns the fields selected on the type, with the fragments flattened.
+    /// The fields that aren't defined on the type are left out, and the ones
+    /// selected more than once are returned once.
+    pub fn fields<'b>(
+        &self,
+        type_name: &str,
+        selection: &'b SelectionSet,
+        doc: &'b ExecutableDocument,
+    ) -> Vec<SelectedField<'a, 'b>> {
+        let mut fields = vec![];
+        self.collect_fields(type_name, selection, doc, true, &mut fields);
+        fields
+    }
+
+    fn collect_fields<'b>(
+        &self,
+        type_name: &str,
+        selection: &'b SelectionSet,
+        doc: &'b ExecutableDocument,
+        always: bool,
+        fields: &mut Vec<SelectedField<'a, 'b>>,
+    ) {
+        for item in selection.items.iter() {
+            let (on, selection) = match &item.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let key = field.response_key().node.to_string();
+                    if fields.iter().any(|selected| selected.key == key) {
+                        continue;
+                    }
+
+                    let definition = if field.name.node == "__typename" {
+                        None
+                    } else if let Some(definition) = self.field(type_name, &field.name.node) {
+                        Some(definition)
+                    } else {
+                        continue;
+                    };
+                    let selection = &field.selection_set.node;
+                    fields.push(SelectedField { key, definition, selection, always });
+                    continue;
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = doc.fragments.get(&spread.node.fragment_name.node) else {
+                        continue;
+                    };
+                    (
+                        fragment.node.type_condition.node.on.node.as_str(),
+                        &fragment.node.selection_set.node,
+                    )
+                }
+                Selection::InlineFragment(fragment) => (
+                    fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|cond| cond.node.on.node.as_str())
+                        .unwrap_or(type_name),
+                    &fragment.node.selection_set.node,
+                ),
+            };
+            self.collect_fields(on, selection, doc, always && on == type_name, fields);
+        }
+    }
+}
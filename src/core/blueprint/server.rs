@@ -9,20 +9,25 @@ use rustls_pki_types::CertificateDer;
 use tailcall_valid::{Valid, ValidationError, Validator};
 
 use super::BlueprintError;
-use crate::core::blueprint::Cors;
-use crate::core::config::{self, ConfigModule, HttpVersion, PrivateKey, Routes};
+use crate::core::blueprint::{Auth, Cors, Provider};
+use crate::core::config::{
+    self, ConfigModule, Content, HttpVersion, PrivateKey, Routes, ScriptModule,
+};
 
 #[derive(Clone, Debug, Setters)]
 pub struct Server {
     pub enable_jit: bool,
     pub enable_apollo_tracing: bool,
     pub enable_cache_control_header: bool,
+    pub enable_cache_status: bool,
+    pub enable_etag: bool,
     pub enable_set_cookie_header: bool,
     pub enable_introspection: bool,
     pub enable_query_validation: bool,
     pub enable_response_validation: bool,
     pub enable_batch_requests: bool,
     pub enable_showcase: bool,
+    pub enable_openapi: bool,
     pub global_response_timeout: i64,
     pub worker: usize,
     pub port: u16,
@@ -35,13 +40,48 @@ pub struct Server {
     pub cors: Option<Cors>,
     pub experimental_headers: HashSet<HeaderName>,
     pub routes: Routes,
+    pub client_auth: Option<ClientAuth>,
+    pub cache_purge: Option<CachePurge>,
+    pub response_cache: Option<ResponseCache>,
+    pub query_plan: Option<QueryPlan>,
+}
+
+/// Endpoint that purges the cached values by their tags.
+#[derive(Clone, Debug)]
+pub struct CachePurge {
+    pub path: String,
+    pub auth: Auth,
+}
+
+/// Extension that describes how the requests were executed.
+#[derive(Clone, Debug)]
+pub struct QueryPlan {
+    /// Request header that asks for the extension.
+    pub header: HeaderName,
+    pub auth: Auth,
+}
+
+/// Cache of the whole responses of the queries.
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    /// Request headers whose values are part of the key.
+    pub vary: Vec<HeaderName>,
+}
+
+/// Verification of the client certificates presented to the server.
+#[derive(Clone, Debug)]
+pub struct ClientAuth {
+    pub ca: Vec<CertificateDer<'static>>,
+    pub required: bool,
 }
 
 /// Mimic of mini_v8::Script that's wasm compatible
 #[derive(Clone, Debug)]
 pub struct Script {
     pub source: String,
-    pub timeout: Option<Duration>,
+    /// Modules that are imported along with the global source.
+    pub modules: Vec<ScriptModule>,
+    pub timeout: Duration,
 }
 
 #[derive(Clone, Debug)]
@@ -122,17 +162,43 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     .as_ref()
                     .and_then(|headers| headers.get_cors()),
             ))
+            .zip(to_client_auth(&config_module))
+            .zip(to_cache_purge(&config_module))
+            .zip(to_response_cache(&config_module))
+            .zip(to_query_plan(&config_module))
             .map(
-                |(hostname, http, response_headers, script, experimental_headers, cors)| Server {
+                |(
+                    (
+                        (
+                            (
+                                (
+                                    hostname,
+                                    http,
+                                    response_headers,
+                                    script,
+                                    experimental_headers,
+                                    cors,
+                                ),
+                                client_auth,
+                            ),
+                            cache_purge,
+                        ),
+                        response_cache,
+                    ),
+                    query_plan,
+                )| Server {
                     enable_jit: (config_server).enable_jit(),
                     enable_apollo_tracing: (config_server).enable_apollo_tracing(),
                     enable_cache_control_header: (config_server).enable_cache_control(),
+                    enable_cache_status: (config_server).enable_cache_status(),
+                    enable_etag: (config_server).enable_etag(),
                     enable_set_cookie_header: (config_server).enable_set_cookies(),
                     enable_introspection: (config_server).enable_introspection(),
                     enable_query_validation: (config_server).enable_query_validation(),
                     enable_response_validation: (config_server).enable_http_validation(),
                     enable_batch_requests: (config_server).enable_batch_requests(),
                     enable_showcase: (config_server).enable_showcase(),
+                    enable_openapi: (config_server).enable_openapi(),
                     experimental_headers,
                     global_response_timeout: (config_server).get_global_response_timeout(),
                     http,
@@ -145,6 +211,10 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     script,
                     cors,
                     routes: config_server.get_routes(),
+                    client_auth,
+                    cache_purge,
+                    response_cache,
+                    query_plan,
                 },
             )
             .to_result()
@@ -154,20 +224,131 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
 fn to_script(
     config_module: &crate::core::config::ConfigModule,
 ) -> Valid<Option<Script>, BlueprintError> {
-    config_module.extensions().script.as_ref().map_or_else(
-        || Valid::succeed(None),
-        |script| {
-            Valid::succeed(Some(Script {
-                source: script.clone(),
-                timeout: config_module
-                    .server
-                    .script
-                    .clone()
-                    .map_or_else(|| None, |script| script.timeout)
-                    .map(Duration::from_millis),
-            }))
+    let extensions = config_module.extensions();
+    if extensions.script.is_none() && extensions.script_modules.is_empty() {
+        return Valid::succeed(None);
+    }
+
+    Valid::succeed(Some(Script {
+        source: extensions.script.clone().unwrap_or_default(),
+        modules: extensions.script_modules.clone(),
+        timeout: Duration::from_millis(config_module.server.get_script_timeout()),
+    }))
+}
+
+fn to_cache_purge(config_module: &ConfigModule) -> Valid<Option<CachePurge>, BlueprintError> {
+    let Some(cache_purge) = config_module.server.cache_purge.as_ref() else {
+        return Valid::succeed(None);
+    };
+
+    let providers = Provider::from_config(config_module);
+    if providers.is_empty() {
+        return Valid::fail(BlueprintError::CachePurgeNoAuthProviders);
+    }
+
+    to_providers_auth(config_module, &providers, cache_purge.id.as_ref())
+        .map(|auth| auth.map(|auth| CachePurge { path: cache_purge.get_path(), auth }))
+}
+
+fn to_query_plan(config_module: &ConfigModule) -> Valid<Option<QueryPlan>, BlueprintError> {
+    let Some(query_plan) = config_module.server.query_plan.as_ref() else {
+        return Valid::succeed(None);
+    };
+
+    let providers = Provider::from_config(config_module);
+    if providers.is_empty() {
+        return Valid::fail(BlueprintError::QueryPlanNoAuthProviders);
+    }
+
+    let header = match HeaderName::from_str(&query_plan.get_header()) {
+        Ok(header) => Valid::succeed(header),
+        Err(e) => Valid::fail(BlueprintError::InvalidHeaderName(e)).trace("header"),
+    };
+
+    header
+        .zip(to_providers_auth(
+            config_module,
+            &providers,
+            query_plan.id.as_ref(),
+        ))
+        .map(|(header, auth)| auth.map(|auth| QueryPlan { header, auth }))
+        .trace("queryPlan")
+}
+
+/// Combines the auth of the providers with the given IDs, or of all the
+/// providers when no ID is given.
+fn to_providers_auth(
+    config_module: &ConfigModule,
+    providers: &[Content<Provider>],
+    ids: Option<&Vec<String>>,
+) -> Valid<Option<Auth>, BlueprintError> {
+    match ids {
+        Some(ids) => Valid::from_iter(ids.iter(), |id| {
+            Valid::from_option(
+                providers
+                    .iter()
+                    .find(|provider| provider.id.as_ref() == Some(id))
+                    .map(|provider| Auth::Provider(provider.content.clone())),
+                BlueprintError::AuthProviderNotFound(id.clone()),
+            )
+        })
+        .map(|auth| auth.into_iter().reduce(|left, right| left.and(right))),
+        None => Valid::succeed(Auth::from_config(config_module)),
+    }
+}
+
+fn to_response_cache(config_module: &ConfigModule) -> Valid<Option<ResponseCache>, BlueprintError> {
+    let Some(response_cache) = config_module.server.response_cache.as_ref() else {
+        return Valid::succeed(None);
+    };
+
+    Valid::from_iter(
+        response_cache.vary.iter(),
+        |name| match HeaderName::from_str(name) {
+            Ok(name) => Valid::succeed(name),
+            Err(e) => Valid::fail(BlueprintError::InvalidHeaderName(e)),
         },
     )
+    .map(|vary| Some(ResponseCache { vary }))
+    .trace("vary")
+    .trace("responseCache")
+    .trace("@server")
+    .trace("schema")
+}
+
+fn to_client_auth(config_module: &ConfigModule) -> Valid<Option<ClientAuth>, BlueprintError> {
+    let Some(client_auth) = config_module.server.client_auth.as_ref() else {
+        return Valid::succeed(None);
+    };
+
+    Valid::succeed(config_module.server.clone().get_version())
+        .and_then(|version| match version {
+            HttpVersion::HTTP2 => Valid::succeed(()),
+            HttpVersion::HTTP1 => Valid::fail(BlueprintError::ClientAuthRequiresHTTP2),
+        })
+        .and_then(|_| {
+            match config_module
+                .extensions()
+                .ca_certs
+                .iter()
+                .find(|ca| ca.id.as_ref() == Some(&client_auth.ca))
+            {
+                Some(ca) => Valid::succeed(ca),
+                None => {
+                    Valid::fail(BlueprintError::LinkNotFound(client_auth.ca.clone())).trace("ca")
+                }
+            }
+        })
+        .and_then(|ca| match rustls_pemfile::certs(&mut ca.as_bytes()) {
+            Ok(certs) => Valid::succeed(Some(ClientAuth {
+                ca: certs.into_iter().map(CertificateDer::from).collect(),
+                required: client_auth.is_required(),
+            })),
+            Err(e) => Valid::fail(BlueprintError::Error(e.into())).trace("ca"),
+        })
+        .trace("clientAuth")
+        .trace("@server")
+        .trace("schema")
 }
 
 fn validate_cors(cors: Option<config::cors::Cors>) -> Valid<Option<Cors>, BlueprintError> {
@@ -1,16 +1,91 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use derive_setters::Setters;
 use tailcall_valid::{Valid, ValidationError, Validator};
 
 use super::BlueprintError;
-use crate::core::config::{self, Batch, ConfigModule};
+use crate::core::config::{self, Batch, ConfigModule, Content};
+use crate::core::mustache::Mustache;
 
 #[derive(PartialEq, Eq, Clone, Debug, schemars::JsonSchema)]
 pub struct Proxy {
     pub url: String,
 }
 
+/// Credentials for the OAuth2 client credentials grant. All the values are
+/// rendered against the request context before the token is requested.
+#[derive(Clone, Debug)]
+pub struct OAuth2 {
+    pub token_url: Mustache,
+    pub client_id: Mustache,
+    pub client_secret: Mustache,
+    pub scopes: Vec<String>,
+}
+
+/// Describes how the outgoing requests of a resolver are authenticated.
+#[derive(Clone, Debug)]
+pub enum UpstreamAuth {
+    OAuth2(OAuth2),
+}
+
+impl UpstreamAuth {
+    /// Compiles the `auth` settings of a resolver. When the resolver doesn't
+    /// define its own settings the ones from `@upstream` are used.
+    pub fn compile(
+        config_module: &ConfigModule,
+        auth: Option<&config::UpstreamAuth>,
+    ) -> Valid<Option<UpstreamAuth>, BlueprintError> {
+        let auth = auth.or(config_module.upstream.auth.as_ref());
+
+        match auth.and_then(|auth| auth.oauth2.as_ref()) {
+            Some(oauth2) => compile_oauth2(oauth2)
+                .map(|oauth2| Some(UpstreamAuth::OAuth2(oauth2)))
+                .trace("oauth2")
+                .trace("auth"),
+            None => Valid::succeed(None),
+        }
+    }
+}
+
+fn compile_oauth2(oauth2: &config::OAuth2) -> Valid<OAuth2, BlueprintError> {
+    let token_url = Mustache::parse(&oauth2.token_url);
+
+    Valid::<(), BlueprintError>::fail(BlueprintError::OAuth2FieldCanNotBeEmpty(
+        "clientId".to_string(),
+    ))
+    .when(|| oauth2.client_id.is_empty())
+    .and(
+        Valid::<(), BlueprintError>::fail(BlueprintError::OAuth2FieldCanNotBeEmpty(
+            "clientSecret".to_string(),
+        ))
+        .when(|| oauth2.client_secret.is_empty()),
+    )
+    .and(if token_url.is_const() {
+        match url::Url::parse(&oauth2.token_url) {
+            Ok(_) => Valid::succeed(()),
+            Err(e) => Valid::fail(BlueprintError::UrlParse(e)).trace("tokenUrl"),
+        }
+    } else {
+        Valid::succeed(())
+    })
+    .map(|_| OAuth2 {
+        token_url,
+        client_id: Mustache::parse(&oauth2.client_id),
+        client_secret: Mustache::parse(&oauth2.client_secret),
+        scopes: oauth2.scopes.clone(),
+    })
+}
+
+/// TLS settings for the connections to the upstream.
+#[derive(PartialEq, Eq, Clone, Debug, Default, schemars::JsonSchema)]
+pub struct Tls {
+    /// PEM encoded certificate chain followed by the private key, presented to
+    /// the upstream for mutual TLS.
+    pub identity: Option<String>,
+    /// PEM encoded CA certificates trusted in addition to the system roots.
+    pub ca_certs: Vec<String>,
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Setters, schemars::JsonSchema)]
 pub struct Upstream {
     pub pool_idle_timeout: u64,
@@ -29,6 +104,8 @@ pub struct Upstream {
     pub http2_only: bool,
     pub on_request: Option<String>,
     pub verify_ssl: bool,
+    pub tls: Tls,
+    pub host_tls: BTreeMap<String, Tls>,
 }
 
 impl Upstream {
@@ -65,7 +142,8 @@ impl TryFrom<&ConfigModule> for Upstream {
 
         get_batch(&config_upstream)
             .fuse(get_proxy(&config_upstream))
-            .map(|(batch, proxy)| Upstream {
+            .zip(get_tls(config_module))
+            .map(|((batch, proxy), (tls, host_tls))| Upstream {
                 pool_idle_timeout: (config_upstream).get_pool_idle_timeout(),
                 pool_max_idle_per_host: (config_upstream).get_pool_max_idle_per_host(),
                 keep_alive_interval: (config_upstream).get_keep_alive_interval(),
@@ -82,6 +160,8 @@ impl TryFrom<&ConfigModule> for Upstream {
                 http2_only: (config_upstream).get_http_2_only(),
                 on_request: (config_upstream).get_on_request(),
                 verify_ssl: (config_upstream).get_verify_ssl(),
+                tls,
+                host_tls,
             })
             .to_result()
     }
@@ -107,3 +187,152 @@ fn get_proxy(upstream: &config::Upstream) -> Valid<Option<Proxy>, BlueprintError
         Valid::succeed(None)
     }
 }
+
+fn get_tls(config_module: &ConfigModule) -> Valid<(Tls, BTreeMap<String, Tls>), BlueprintError> {
+    let extensions = config_module.extensions();
+    let cert = extensions
+        .client_certs
+        .iter()
+        .find(|cert| cert.id.is_none());
+    let key = extensions.client_keys.iter().find(|key| key.id.is_none());
+    let ca_certs: Vec<String> = extensions
+        .ca_certs
+        .iter()
+        .filter(|ca| ca.id.is_none())
+        .map(|ca| ca.content.clone())
+        .collect();
+
+    to_identity(cert.map(|c| c.as_str()), key.map(|k| k.as_str()))
+        .map(|identity| Tls { identity, ca_certs })
+        .and_then(|tls| {
+            Valid::from_iter(config_module.upstream.tls.iter(), |host_tls| {
+                find_link(&extensions.client_certs, host_tls.client_cert.as_ref())
+                    .trace("clientCert")
+                    .fuse(
+                        find_link(&extensions.client_keys, host_tls.client_key.as_ref())
+                            .trace("clientKey"),
+                    )
+                    .fuse(find_link(&extensions.ca_certs, host_tls.ca.as_ref()).trace("ca"))
+                    .and_then(|(cert, key, ca)| {
+                        to_identity(cert, key).map(|identity| {
+                            // settings that are not specified for the host fall back to the
+                            // ones that apply to all the hosts.
+                            let identity = identity.or(tls.identity.clone());
+                            let ca_certs = tls
+                                .ca_certs
+                                .iter()
+                                .cloned()
+                                .chain(ca.map(|ca| ca.to_string()))
+                                .collect();
+
+                            (host_tls.host.clone(), Tls { identity, ca_certs })
+                        })
+                    })
+                    .trace(&host_tls.host)
+            })
+            .trace("tls")
+            .map(|host_tls| (tls.clone(), host_tls.into_iter().collect()))
+        })
+}
+
+/// Combines the certificate chain and the key into a single PEM bundle.
+fn to_identity(cert: Option<&str>, key: Option<&str>) -> Valid<Option<String>, BlueprintError> {
+    match (cert, key) {
+        (Some(cert), Some(key)) => Valid::succeed(Some(format!("{}\n{}", cert, key))),
+        (None, None) => Valid::succeed(None),
+        _ => Valid::fail(BlueprintError::ClientCertRequiresClientKey),
+    }
+}
+
+fn find_link<'a>(
+    links: &'a [Content<String>],
+    id: Option<&String>,
+) -> Valid<Option<&'a str>, BlueprintError> {
+    match id {
+        Some(id) => match links.iter().find(|link| link.id.as_ref() == Some(id)) {
+            Some(link) => Valid::succeed(Some(link.as_str())),
+            None => Valid::fail(BlueprintError::LinkNotFound(id.clone())),
+        },
+        None => Valid::succeed(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{Config, Extensions, HostTls};
+
+    fn content(id: Option<&str>, content: &str) -> Content<String> {
+        Content { id: id.map(str::to_string), content: content.to_string() }
+    }
+
+    fn config_module(tls: Vec<HostTls>) -> ConfigModule {
+        let mut config = Config::default();
+        config.upstream.tls = tls;
+
+        ConfigModule::from(config).set_extensions(Extensions {
+            client_certs: vec![content(None, "cert"), content(Some("api"), "api-cert")],
+            client_keys: vec![content(None, "key"), content(Some("api"), "api-key")],
+            ca_certs: vec![content(None, "ca"), content(Some("api"), "api-ca")],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_host_tls_falls_back_to_default() {
+        let tls = vec![
+            HostTls {
+                host: "api.local".to_string(),
+                client_cert: Some("api".to_string()),
+                client_key: Some("api".to_string()),
+                ca: Some("api".to_string()),
+            },
+            HostTls { host: "other.local".to_string(), ..Default::default() },
+        ];
+        let (default, hosts) = get_tls(&config_module(tls)).to_result().unwrap();
+
+        assert_eq!(
+            default,
+            Tls {
+                identity: Some("cert\nkey".to_string()),
+                ca_certs: vec!["ca".to_string()]
+            }
+        );
+        assert_eq!(
+            hosts.get("api.local").unwrap(),
+            &Tls {
+                identity: Some("api-cert\napi-key".to_string()),
+                ca_certs: vec!["ca".to_string(), "api-ca".to_string()],
+            }
+        );
+        assert_eq!(hosts.get("other.local").unwrap(), &default);
+    }
+
+    #[test]
+    fn test_host_tls_unknown_link() {
+        let tls = vec![HostTls {
+            host: "api.local".to_string(),
+            client_cert: Some("missing".to_string()),
+            client_key: Some("api".to_string()),
+            ca: None,
+        }];
+
+        assert!(get_tls(&config_module(tls)).to_result().is_err());
+    }
+
+    #[test]
+    fn test_host_tls_cert_without_key() {
+        let tls = vec![HostTls {
+            host: "api.local".to_string(),
+            client_cert: Some("api".to_string()),
+            client_key: None,
+            ca: None,
+        }];
+        let config_module = config_module(tls).set_extensions(Extensions {
+            client_certs: vec![content(Some("api"), "api-cert")],
+            ..Default::default()
+        });
+
+        assert!(get_tls(&config_module).to_result().is_err());
+    }
+}
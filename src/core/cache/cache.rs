@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::num::NonZeroU64;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -10,6 +11,7 @@ use super::error::Result;
 
 pub struct InMemoryCache<K: Hash + Eq, V> {
     data: Arc<RwLock<TtlCache<K, V>>>,
+    tags: Arc<RwLock<TagIndex<K>>>,
     hits: AtomicUsize,
     miss: AtomicUsize,
 }
@@ -24,14 +26,53 @@ impl<K: Hash + Eq, V: Clone> InMemoryCache<K, V> {
     pub fn new(capacity: usize) -> Self {
         InMemoryCache {
             data: Arc::new(RwLock::new(TtlCache::new(capacity))),
+            tags: Arc::new(RwLock::new(TagIndex::new(capacity))),
             hits: AtomicUsize::new(0),
             miss: AtomicUsize::new(0),
         }
     }
 }
 
+/// Keys of the cache by tag.
+///
+/// Entries evicted or expired from the cache don't notify the index, so the
+/// keys that aren't in the cache anymore are swept out whenever the index
+/// doubles in size since the last sweep.
+struct TagIndex<K> {
+    keys: HashMap<String, HashSet<K>>,
+    len: usize,
+    sweep_at: usize,
+}
+
+impl<K: Hash + Eq> TagIndex<K> {
+    fn new(capacity: usize) -> Self {
+        Self { keys: HashMap::new(), len: 0, sweep_at: capacity.max(1) }
+    }
+
+    fn insert<V>(&mut self, tag: String, key: K, data: &TtlCache<K, V>) {
+        if self.keys.entry(tag).or_default().insert(key) {
+            self.len += 1;
+        }
+
+        if self.len > self.sweep_at {
+            self.keys.retain(|_, keys| {
+                keys.retain(|key| data.contains_key(key));
+                !keys.is_empty()
+            });
+            self.len = self.keys.values().map(HashSet::len).sum();
+            self.sweep_at = self.sweep_at.max(self.len * 2);
+        }
+    }
+
+    fn remove(&mut self, tag: &str) -> Option<HashSet<K>> {
+        let keys = self.keys.remove(tag)?;
+        self.len -= keys.len();
+        Some(keys)
+    }
+}
+
 #[async_trait::async_trait]
-impl<K: Hash + Eq + Send + Sync, V: Clone + Send + Sync> crate::core::Cache
+impl<K: Hash + Eq + Clone + Send + Sync, V: Clone + Send + Sync> crate::core::Cache
     for InMemoryCache<K, V>
 {
     type Key = K;
@@ -53,6 +94,29 @@ impl<K: Hash + Eq + Send + Sync, V: Clone + Send + Sync> crate::core::Cache
         Ok(val)
     }
 
+    async fn delete<'a>(&'a self, key: &'a K) -> Result<()> {
+        self.data.write().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn tag<'a>(&'a self, key: &'a K, tags: &'a [String], _: NonZeroU64) -> Result<()> {
+        let data = self.data.read().unwrap();
+        let mut index = self.tags.write().unwrap();
+        for tag in tags {
+            index.insert(tag.clone(), key.clone(), &data);
+        }
+        Ok(())
+    }
+
+    async fn purge_by_tag<'a>(&'a self, tag: &'a str) -> Result<usize> {
+        let Some(keys) = self.tags.write().unwrap().remove(tag) else {
+            return Ok(0);
+        };
+
+        let mut data = self.data.write().unwrap();
+        Ok(keys.iter().filter(|key| data.remove(key).is_some()).count())
+    }
+
     fn hit_rate(&self) -> Option<f64> {
         let cache = self.data.read().unwrap();
         let hits = self.hits.load(Ordering::Relaxed);
@@ -89,4 +153,53 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(ttl.get())).await;
         assert_eq!(cache.get(&10).await.ok(), Some(None));
     }
+
+    #[tokio::test]
+    async fn test_purge_by_tag() {
+        let cache: crate::core::cache::InMemoryCache<u64, String> =
+            crate::core::cache::InMemoryCache::default();
+        let ttl = NonZeroU64::new(60_000).unwrap();
+
+        cache.set(1, "one".into(), ttl).await.unwrap();
+        cache.set(2, "two".into(), ttl).await.unwrap();
+        cache.set(3, "three".into(), ttl).await.unwrap();
+        cache.tag(&1, &["user:1".into()], ttl).await.unwrap();
+        cache
+            .tag(&2, &["user:1".into(), "user:2".into()], ttl)
+            .await
+            .unwrap();
+
+        assert_eq!(cache.purge_by_tag("user:1").await.ok(), Some(2));
+        assert_eq!(cache.purge_by_tag("user:1").await.ok(), Some(0));
+        assert_eq!(cache.get(&1).await.ok(), Some(None));
+        assert_eq!(cache.get(&2).await.ok(), Some(None));
+        assert_eq!(cache.get(&3).await.ok(), Some(Some("three".into())));
+    }
+
+    #[tokio::test]
+    async fn test_tag_index_drops_evicted_keys() {
+        let cache: crate::core::cache::InMemoryCache<u64, String> =
+            crate::core::cache::InMemoryCache::new(2);
+        let ttl = NonZeroU64::new(60_000).unwrap();
+
+        for key in 0..100 {
+            cache.set(key, key.to_string(), ttl).await.unwrap();
+            cache.tag(&key, &["all".into()], ttl).await.unwrap();
+        }
+
+        assert!(cache.tags.read().unwrap().len <= 4);
+        assert_eq!(cache.purge_by_tag("all").await.ok(), Some(2));
+        assert_eq!(cache.tags.read().unwrap().len, 0);
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let cache: crate::core::cache::InMemoryCache<u64, String> =
+            crate::core::cache::InMemoryCache::default();
+        let ttl = NonZeroU64::new(60_000).unwrap();
+
+        cache.set(1, "one".into(), ttl).await.unwrap();
+        cache.delete(&1).await.unwrap();
+        assert_eq!(cache.get(&1).await.ok(), Some(None));
+    }
 }
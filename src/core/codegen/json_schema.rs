@@ -0,0 +1,121 @@
+use serde_json::{json, Map, Value};
+
+use super::{scalar_type, ClientTypes, Kind, Property, Shape};
+use crate::core::scalar::Scalar;
+
+const JSON_SCHEMA_VERSION: &str = "http://json-schema.org/draft-07/schema#";
+
+impl ClientTypes {
+    /// Writes the types as a JSON Schema document. The types of the schema are
+    /// under `definitions`, next to the `<Operation>Variables` and
+    /// `<Operation>Result` types of the operations.
+    pub fn to_json_schema(&self) -> Value {
+        let mut definitions = Map::new();
+
+        for named in self.types.iter() {
+            let mut schema = match &named.kind {
+                Kind::Scalar => json!({}),
+                Kind::Enum(values) => json!({ "type": "string", "enum": values }),
+                Kind::Object(properties) => self.object(properties),
+                Kind::Union(types) => json!({
+                    "oneOf": types.iter().map(|name| reference(name)).collect::<Vec<_>>(),
+                }),
+            };
+            describe(&mut schema, &named.description);
+            definitions.insert(named.name.clone(), schema);
+        }
+
+        for operation in self.operations.iter() {
+            definitions.insert(
+                format!("{}Variables", operation.name),
+                self.object(&operation.variables),
+            );
+            definitions.insert(
+                format!("{}Result", operation.name),
+                self.object(&operation.result),
+            );
+        }
+
+        json!({
+            "$schema": JSON_SCHEMA_VERSION,
+            "definitions": definitions,
+        })
+    }
+
+    fn object(&self, properties: &[Property]) -> Value {
+        let mut schema_properties = Map::new();
+        let mut required = vec![];
+        for property in properties {
+            let mut schema = self.shape(&property.shape);
+            describe(&mut schema, &property.description);
+            if !property.optional {
+                required.push(property.name.clone());
+            }
+            schema_properties.insert(property.name.clone(), schema);
+        }
+
+        let mut schema = json!({ "type": "object", "properties": schema_properties });
+        if !required.is_empty() {
+            schema["required"] = json!(required);
+        }
+        schema
+    }
+
+    fn shape(&self, shape: &Shape) -> Value {
+        match shape {
+            Shape::Named(name) if self.types.iter().any(|named| &named.name == name) => {
+                reference(name)
+            }
+            Shape::Named(name) => match Scalar::find(name) {
+                Some(scalar) => serde_json::to_value(scalar.schema()).unwrap_or_default(),
+                None => scalar_type(name)
+                    .map(|type_of| json!({ "type": type_of }))
+                    .unwrap_or_else(|| json!({})),
+            },
+            Shape::List(of_type) => json!({ "type": "array", "items": self.shape(of_type) }),
+            Shape::Nullable(of_type) => {
+                json!({ "anyOf": [self.shape(of_type), { "type": "null" }] })
+            }
+            Shape::Object(properties) => self.object(properties),
+        }
+    }
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{name}") })
+}
+
+fn describe(schema: &mut Value, description: &Option<String>) {
+    if let (Value::Object(schema), Some(description)) = (schema, description) {
+        schema.insert("description".to_string(), json!(description));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::super::tests::client_types;
+    use super::*;
+
+    #[test]
+    fn test_to_json_schema() {
+        let schema = client_types().to_json_schema();
+        let definitions = &schema["definitions"];
+
+        assert_eq!(
+            definitions["Role"],
+            json!({ "type": "string", "enum": ["ADMIN", "GUEST"], "description": "Role of a user" })
+        );
+        assert_eq!(definitions["UserFilter"]["required"], json!(["name"]));
+        assert_eq!(
+            definitions["User"]["properties"]["role"],
+            json!({ "$ref": "#/definitions/Role" })
+        );
+        assert_eq!(
+            definitions["UsersResult"]["properties"]["users"]["items"]["required"],
+            json!(["id", "contact"])
+        );
+        assert_eq!(definitions["UsersVariables"]["required"], Value::Null);
+    }
+}
@@ -0,0 +1,382 @@
+//! Client artifacts generated from the blueprint: the JSON Schema and the
+//! TypeScript types of the types of the schema and of the results of the
+//! operations linked to the configuration.
+
+mod json_schema;
+mod typescript;
+
+use async_graphql::parser::types::{
+    BaseType, DocumentOperations, ExecutableDocument, OperationDefinition, SelectionSet,
+    Type as VariableType,
+};
+use schemars::schema::InstanceType;
+
+use crate::core::blueprint::{
+    Blueprint, Definition, FieldDefinition, InputFieldDefinition, SelectionWalker,
+};
+use crate::core::scalar::Scalar;
+use crate::core::Type;
+
+/// Type of a value, described with the types of the blueprint.
+#[derive(Clone, Debug, PartialEq)]
+enum Shape {
+    /// Scalar, enum, object, union or interface of the blueprint.
+    Named(String),
+    List(Box<Shape>),
+    Nullable(Box<Shape>),
+    /// Object described by the selection set of an operation.
+    Object(Vec<Property>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Property {
+    name: String,
+    shape: Shape,
+    /// Properties that can be left out, like the nullable fields of the input
+    /// types, or the fields selected on a single type of a union.
+    optional: bool,
+    description: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    /// Scalar declared in the configuration, that can hold any value.
+    Scalar,
+    Enum(Vec<String>),
+    Object(Vec<Property>),
+    Union(Vec<String>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct NamedType {
+    name: String,
+    description: Option<String>,
+    kind: Kind,
+}
+
+/// Variables and result of an operation.
+#[derive(Clone, Debug, PartialEq)]
+struct Operation {
+    name: String,
+    variables: Vec<Property>,
+    result: Vec<Property>,
+}
+
+/// Types of the schema and of the operations, from which the artifacts are
+/// written.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientTypes {
+    types: Vec<NamedType>,
+    operations: Vec<Operation>,
+}
+
+impl ClientTypes {
+    /// Collects the types of the blueprint, and the types of the given
+    /// operations, identified by their name.
+    pub fn new(blueprint: &Blueprint, operations: &[(String, &ExecutableDocument)]) -> Self {
+        let collector = Collector::new(blueprint);
+        let mut types = blueprint
+            .definitions
+            .iter()
+            .map(|definition| collector.named_type(definition))
+            .collect::<Vec<_>>();
+        types.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let operations = operations
+            .iter()
+            .filter_map(|(name, doc)| collector.operation(name, doc))
+            .collect();
+
+        Self { types, operations }
+    }
+}
+
+/// Splits the documents into one document per named operation, along with
+/// the fragments of its document. The anonymous operations are left out, as
+/// there's nothing to name their types after.
+pub fn named_operations(docs: &[ExecutableDocument]) -> Vec<(String, ExecutableDocument)> {
+    let mut operations = docs
+        .iter()
+        .flat_map(|doc| {
+            doc.operations.iter().filter_map(|(name, operation)| {
+                let doc = ExecutableDocument {
+                    operations: DocumentOperations::Single(operation.clone()),
+                    fragments: doc.fragments.clone(),
+                };
+                Some((name?.to_string(), doc))
+            })
+        })
+        .collect::<Vec<_>>();
+    // the operations of a document aren't ordered
+    operations.sort_by(|(a, _), (b, _)| a.cmp(b));
+    operations
+}
+
+/// Type of the built-in and custom scalars, which aren't definitions of the
+/// blueprint.
+fn scalar_type(name: &str) -> Option<InstanceType> {
+    match name {
+        "Int" => Some(InstanceType::Integer),
+        "Float" => Some(InstanceType::Number),
+        "String" | "ID" => Some(InstanceType::String),
+        "Boolean" => Some(InstanceType::Boolean),
+        name => Scalar::find(name).map(Scalar::ty),
+    }
+}
+
+struct Collector<'a> {
+    walker: SelectionWalker<'a>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(blueprint: &'a Blueprint) -> Self {
+        Self { walker: SelectionWalker::new(blueprint) }
+    }
+
+    fn named_type(&self, definition: &Definition) -> NamedType {
+        let (description, kind) = match definition {
+            Definition::Object(def) => (&def.description, Kind::Object(output_fields(&def.fields))),
+            Definition::Interface(def) => {
+                (&def.description, Kind::Object(output_fields(&def.fields)))
+            }
+            Definition::InputObject(def) => {
+                (&def.description, Kind::Object(input_fields(&def.fields)))
+            }
+            Definition::Scalar(def) => (&def.description, Kind::Scalar),
+            Definition::Enum(def) => (
+                &def.description,
+                Kind::Enum(def.enum_values.iter().map(|v| v.name.clone()).collect()),
+            ),
+            Definition::Union(def) => (
+                &def.description,
+                Kind::Union(def.types.iter().cloned().collect()),
+            ),
+        };
+
+        NamedType {
+            name: definition.name().to_string(),
+            description: description.clone(),
+            kind,
+        }
+    }
+
+    fn operation(&self, name: &str, doc: &ExecutableDocument) -> Option<Operation> {
+        let (_, operation) = doc.operations.iter().next()?;
+        let operation: &OperationDefinition = &operation.node;
+        let root = self.walker.root(operation.ty)?;
+
+        let variables = operation
+            .variable_definitions
+            .iter()
+            .map(|var| {
+                let var = &var.node;
+                Property {
+                    name: var.name.node.to_string(),
+                    shape: variable_shape(&var.var_type.node),
+                    optional: var.var_type.node.nullable || var.default_value.is_some(),
+                    description: None,
+                }
+            })
+            .collect();
+        let result = self.properties(root, &operation.selection_set.node, doc);
+
+        Some(Operation { name: name.to_string(), variables, result })
+    }
+
+    /// Fields selected through fragments on a different type (e.g. a union
+    /// member) only show up for some values, so they are optional.
+    fn properties(
+        &self,
+        type_name: &str,
+        selection: &SelectionSet,
+        doc: &ExecutableDocument,
+    ) -> Vec<Property> {
+        self.walker
+            .fields(type_name, selection, doc)
+            .into_iter()
+            .map(|field| {
+                let (shape, description) = match field.definition {
+                    Some(definition) => (
+                        self.output_shape(&definition.of_type, field.selection, doc),
+                        definition.description.clone(),
+                    ),
+                    None => (Shape::Named("String".to_string()), None),
+                };
+                Property { name: field.key, shape, optional: !field.always, description }
+            })
+            .collect()
+    }
+
+    fn output_shape(
+        &self,
+        of_type: &Type,
+        selection: &SelectionSet,
+        doc: &ExecutableDocument,
+    ) -> Shape {
+        let shape = match of_type {
+            Type::Named { name, .. } if selection.items.is_empty() => Shape::Named(name.clone()),
+            Type::Named { name, .. } => Shape::Object(self.properties(name, selection, doc)),
+            Type::List { of_type, .. } => {
+                Shape::List(Box::new(self.output_shape(of_type, selection, doc)))
+            }
+        };
+        nullable(shape, of_type.is_nullable())
+    }
+}
+
+fn output_fields(fields: &[FieldDefinition]) -> Vec<Property> {
+    fields
+        .iter()
+        .map(|field| Property {
+            name: field.name.clone(),
+            shape: shape(&field.of_type),
+            optional: false,
+            description: field.description.clone(),
+        })
+        .collect()
+}
+
+fn input_fields(fields: &[InputFieldDefinition]) -> Vec<Property> {
+    fields
+        .iter()
+        .map(|field| Property {
+            name: field.name.clone(),
+            shape: shape(&field.of_type),
+            optional: field.of_type.is_nullable() || field.default_value.is_some(),
+            description: field.description.clone(),
+        })
+        .collect()
+}
+
+fn shape(of_type: &Type) -> Shape {
+    let shape = match of_type {
+        Type::Named { name, .. } => Shape::Named(name.clone()),
+        Type::List { of_type, .. } => Shape::List(Box::new(self::shape(of_type))),
+    };
+    nullable(shape, of_type.is_nullable())
+}
+
+fn variable_shape(of_type: &VariableType) -> Shape {
+    let shape = match &of_type.base {
+        BaseType::Named(name) => Shape::Named(name.to_string()),
+        BaseType::List(of_type) => Shape::List(Box::new(variable_shape(of_type))),
+    };
+    nullable(shape, of_type.nullable)
+}
+
+fn nullable(shape: Shape, is_nullable: bool) -> Shape {
+    if is_nullable {
+        Shape::Nullable(Box::new(shape))
+    } else {
+        shape
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    pub(super) const CONFIG: &str = r#"
+        schema {
+          query: Query
+        }
+
+        "Role of a user"
+        enum Role {
+          ADMIN
+          GUEST
+        }
+
+        input UserFilter {
+          role: Role
+          name: String!
+        }
+
+        type Query {
+          users(filter: UserFilter): [User!]! @expr(body: [])
+        }
+
+        type User {
+          id: Int!
+          email: Email
+          role: Role!
+        }
+    "#;
+
+    pub(super) const OPERATION: &str = r#"
+        query Users($filter: UserFilter) {
+          users(filter: $filter) { id contact: email }
+        }
+    "#;
+
+    pub(super) fn client_types() -> ClientTypes {
+        let config = Config::from_sdl(CONFIG).to_result().unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+        let doc = async_graphql::parser::parse_query(OPERATION).unwrap();
+
+        ClientTypes::new(&blueprint, &[("Users".to_string(), &doc)])
+    }
+
+    #[test]
+    fn test_operation() {
+        let client_types = client_types();
+        let user = Shape::Object(vec![
+            Property {
+                name: "id".to_string(),
+                shape: Shape::Named("Int".to_string()),
+                optional: false,
+                description: None,
+            },
+            Property {
+                name: "contact".to_string(),
+                shape: Shape::Nullable(Box::new(Shape::Named("Email".to_string()))),
+                optional: false,
+                description: None,
+            },
+        ]);
+
+        assert_eq!(
+            client_types.operations,
+            vec![Operation {
+                name: "Users".to_string(),
+                variables: vec![Property {
+                    name: "filter".to_string(),
+                    shape: Shape::Nullable(Box::new(Shape::Named("UserFilter".to_string()))),
+                    optional: true,
+                    description: None,
+                }],
+                result: vec![Property {
+                    name: "users".to_string(),
+                    shape: Shape::List(Box::new(user)),
+                    optional: false,
+                    description: None,
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_named_operations() {
+        let doc = async_graphql::parser::parse_query(
+            r#"
+            query Users { users { id } }
+            query Roles { users { ...UserRole } }
+            fragment UserRole on User { role }
+        "#,
+        )
+        .unwrap();
+        let operations = named_operations(&[doc]);
+        let names = operations
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["Roles", "Users"]);
+        assert!(operations
+            .iter()
+            .all(|(_, doc)| doc.fragments.contains_key("UserRole")));
+    }
+}
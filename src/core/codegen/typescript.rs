@@ -0,0 +1,173 @@
+use std::fmt::Write;
+
+use schemars::schema::InstanceType;
+
+use super::{scalar_type, ClientTypes, Kind, Property, Shape};
+
+const HEADER: &str = "// Generated by tailcall, do not edit.\n";
+
+impl ClientTypes {
+    /// Writes the types as TypeScript declarations. Enums are written as
+    /// unions of string literals, so that the file holds no runtime code.
+    pub fn to_typescript(&self) -> String {
+        let mut output = HEADER.to_string();
+
+        for named in self.types.iter() {
+            output.push('\n');
+            output.push_str(&doc(&named.description, 0));
+            let name = &named.name;
+            let _ = match &named.kind {
+                Kind::Scalar => writeln!(output, "export type {name} = unknown;"),
+                Kind::Enum(values) => {
+                    let values = values
+                        .iter()
+                        .map(|value| format!("{value:?}"))
+                        .collect::<Vec<_>>();
+                    writeln!(output, "export type {name} = {};", union(values))
+                }
+                Kind::Object(properties) => {
+                    writeln!(
+                        output,
+                        "export interface {name} {}",
+                        self.object(properties, 0)
+                    )
+                }
+                Kind::Union(types) => {
+                    writeln!(output, "export type {name} = {};", union(types.clone()))
+                }
+            };
+        }
+
+        for operation in self.operations.iter() {
+            let name = &operation.name;
+            let variables = self.object(&operation.variables, 0);
+            let result = self.object(&operation.result, 0);
+            let _ = write!(
+                output,
+                "\nexport interface {name}Variables {variables}\n\nexport interface {name}Result {result}\n"
+            );
+        }
+
+        output
+    }
+
+    fn object(&self, properties: &[Property], depth: usize) -> String {
+        if properties.is_empty() {
+            return "{}".to_string();
+        }
+
+        let indent = "  ".repeat(depth + 1);
+        let mut output = "{\n".to_string();
+        for property in properties {
+            output.push_str(&doc(&property.description, depth + 1));
+            let optional = if property.optional { "?" } else { "" };
+            let _ = writeln!(
+                output,
+                "{indent}{}{optional}: {};",
+                property_name(&property.name),
+                self.shape(&property.shape, depth + 1)
+            );
+        }
+        output.push_str(&"  ".repeat(depth));
+        output.push('}');
+        output
+    }
+
+    fn shape(&self, shape: &Shape, depth: usize) -> String {
+        match shape {
+            Shape::Named(name) if self.types.iter().any(|named| &named.name == name) => {
+                name.clone()
+            }
+            Shape::Named(name) => scalar_type(name)
+                .map(primitive)
+                .unwrap_or("unknown")
+                .to_string(),
+            Shape::List(of_type) => match of_type.as_ref() {
+                Shape::Nullable(_) => format!("Array<{}>", self.shape(of_type, depth)),
+                of_type => format!("{}[]", self.shape(of_type, depth)),
+            },
+            Shape::Nullable(of_type) => format!("{} | null", self.shape(of_type, depth)),
+            Shape::Object(properties) => self.object(properties, depth),
+        }
+    }
+}
+
+fn primitive(type_of: InstanceType) -> &'static str {
+    match type_of {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "boolean",
+        InstanceType::Integer | InstanceType::Number => "number",
+        InstanceType::String => "string",
+        InstanceType::Array => "unknown[]",
+        InstanceType::Object => "unknown",
+    }
+}
+
+fn union(values: Vec<String>) -> String {
+    if values.is_empty() {
+        "never".to_string()
+    } else {
+        values.join(" | ")
+    }
+}
+
+/// Quotes the names that aren't valid identifiers.
+fn property_name(name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if is_identifier {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+fn doc(description: &Option<String>, depth: usize) -> String {
+    let Some(description) = description else {
+        return String::new();
+    };
+    let indent = "  ".repeat(depth);
+    // `*/` would close the comment early
+    let lines = description
+        .replace("*/", "*\\/")
+        .lines()
+        .map(|line| format!("{indent} * {line}").trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{indent}/**\n{lines}\n{indent} */\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::super::tests::client_types;
+
+    #[test]
+    fn test_to_typescript() {
+        let typescript = client_types().to_typescript();
+
+        assert!(typescript
+            .contains("/**\n * Role of a user\n */\nexport type Role = \"ADMIN\" | \"GUEST\";\n"));
+        assert!(typescript.contains(
+            "export interface UserFilter {\n  name: string;\n  role?: Role | null;\n}\n"
+        ));
+        assert!(typescript
+            .contains("export interface UsersVariables {\n  filter?: UserFilter | null;\n}\n"));
+        assert_eq!(
+            typescript.split("export interface UsersResult ").nth(1),
+            Some("{\n  users: {\n    id: number;\n    contact: string | null;\n  }[];\n}\n")
+        );
+    }
+
+    #[test]
+    fn test_doc_escapes_comment_end() {
+        let description = Some("Ends with */ here".to_string());
+        assert_eq!(
+            super::doc(&description, 1),
+            "  /**\n   * Ends with *\\/ here\n   */\n"
+        );
+    }
+}
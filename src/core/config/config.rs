@@ -16,8 +16,8 @@ use tailcall_valid::{Valid, Validator};
 use super::directive::Directive;
 use super::from_document::from_document;
 use super::{
-    AddField, Alias, Cache, Call, Discriminate, Expr, GraphQL, Grpc, Http, Link, Modify, Omit,
-    Protected, ResolverSet, Server, Telemetry, Upstream, JS,
+    AddField, Alias, Cache, Call, Discriminate, Expr, GraphQL, Grpc, Http, Invalidates, Link, Mock,
+    Modify, Omit, Protected, ResolverSet, RestExpose, Server, Telemetry, Upstream, JS,
 };
 use crate::core::config::npo::QueryPath;
 use crate::core::config::source::Source;
@@ -211,6 +211,18 @@ pub struct Field {
     /// Marks field as protected by auth provider
     pub protected: Option<Protected>,
 
+    ///
+    /// Purges the cached values with the given tags once the field resolves
+    pub invalidates: Option<Invalidates>,
+
+    ///
+    /// Exposes the field as a REST endpoint
+    pub rest_expose: Option<RestExpose>,
+
+    ///
+    /// Sets the value of the field in mock mode
+    pub mock: Option<Mock>,
+
     ///
     /// Used to overwrite the default discrimination strategy
     pub discriminate: Option<Discriminate>,
@@ -296,12 +308,34 @@ pub struct Enum {
     pub doc: Option<String>,
 }
 
-/// Definition of GraphQL value
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, MergeRight)]
+/// Definition of GraphQL value. Variants are compared by their name only, so
+/// that an enum holds a single variant per name, whatever its docs.
+#[derive(Clone, Debug, MergeRight)]
 pub struct Variant {
     pub name: String,
     // directive: alias
     pub alias: Option<Alias>,
+    pub doc: Option<String>,
+}
+
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Variant {}
+
+impl PartialOrd for Variant {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Variant {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.name.cmp(&other.name)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -430,6 +464,29 @@ impl Config {
         }
     }
 
+    /// Writes the configuration in the given format. JSON and YAML can only
+    /// hold the runtime settings, so the conversion fails when the
+    /// configuration has a schema that would be lost.
+    pub fn to_source(&self, source: Source) -> Result<String> {
+        let content = match source {
+            Source::GraphQL => self.to_sdl(),
+            source => {
+                let runtime_config = RuntimeConfig::from(self);
+                if Config::from(runtime_config.clone()) != *self {
+                    return Err(anyhow!(
+                        "The schema can't be written as {source}, only as GraphQL"
+                    ));
+                }
+                match source {
+                    Source::Json => runtime_config.to_json(true)?,
+                    _ => runtime_config.to_yaml()?,
+                }
+            }
+        };
+
+        Ok(format!("{}\n", content.trim_end()))
+    }
+
     pub fn n_plus_one(&self) -> QueryPath {
         super::npo::PathTracker::new(self).find()
     }
@@ -646,6 +703,9 @@ impl Config {
             .add_directive(Omit::directive_definition(generated_types))
             .add_directive(Protected::directive_definition(generated_types))
             .add_directive(Discriminate::directive_definition(generated_types))
+            .add_directive(Invalidates::directive_definition(generated_types))
+            .add_directive(RestExpose::directive_definition(generated_types))
+            .add_directive(Mock::directive_definition(generated_types))
             .add_input(GraphQL::input_definition())
             .add_input(Grpc::input_definition())
             .add_input(Http::input_definition())
@@ -662,6 +722,17 @@ impl Config {
     }
 }
 
+impl From<&Config> for RuntimeConfig {
+    fn from(config: &Config) -> Self {
+        Self {
+            server: config.server.clone(),
+            upstream: config.upstream.clone(),
+            links: config.links.clone(),
+            telemetry: config.telemetry.clone(),
+        }
+    }
+}
+
 impl From<RuntimeConfig> for Config {
     fn from(config: RuntimeConfig) -> Self {
         Self {
@@ -857,4 +928,53 @@ mod tests {
 
         assert_eq!(interfaces_types_map, expected_union_types);
     }
+
+    #[test]
+    fn test_to_source_keeps_the_docs() {
+        let sdl = r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            "Result of a search"
+            union Result = Post | User
+
+            "Role of a user"
+            enum Role {
+              "Can do anything"
+              ADMIN
+              GUEST
+            }
+
+            type Query {
+              role: Role @expr(body: "ADMIN")
+            }
+
+            type Post {
+              id: Int
+            }
+
+            type User {
+              id: Int
+            }
+        "#;
+        let config = Config::from_sdl(sdl).to_result().unwrap();
+        let actual = Config::from_sdl(&config.to_source(Source::GraphQL).unwrap())
+            .to_result()
+            .unwrap();
+
+        assert_eq!(actual, config);
+    }
+
+    #[test]
+    fn test_to_source_json() {
+        let config = Config::from_sdl("schema @server(port: 8000) { query: Query }")
+            .to_result()
+            .unwrap();
+        assert!(config.to_source(Source::Json).is_err());
+
+        let config = Config::from(RuntimeConfig::from(&config));
+        let json = config.to_source(Source::Json).unwrap();
+        assert_eq!(Config::from_source(Source::Json, &json).unwrap(), config);
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
 
+use async_graphql::parser::types::ExecutableDocument;
 use jsonwebtoken::jwk::JwkSet;
 use prost_reflect::prost_types::{FileDescriptorProto, FileDescriptorSet};
 use rustls_pki_types::{CertificateDer, PrivateKeyDer};
@@ -103,6 +104,15 @@ impl<A> Deref for Content<A> {
     }
 }
 
+/// A JS file linked with an id. It's loaded as an ES module, so that its
+/// exports can be referenced as `<id>.<name>` and imported by other modules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptModule {
+    pub id: String,
+    pub path: String,
+    pub source: String,
+}
+
 #[derive(Debug)]
 pub struct PrivateKey(PrivateKeyDer<'static>);
 
@@ -135,6 +145,9 @@ pub struct Extensions {
     /// Contains the contents of the JS file
     pub script: Option<String>,
 
+    /// Contains the JS files linked with an id
+    pub script_modules: Vec<ScriptModule>,
+
     /// Contains the certificate used on HTTP2 with TLS
     pub cert: Vec<CertificateDer<'static>>,
 
@@ -144,9 +157,23 @@ pub struct Extensions {
     /// Contains the endpoints
     pub endpoint_set: EndpointSet<Unchecked>,
 
+    /// Contains the linked operations, including the ones that aren't exposed
+    /// with `@rest`
+    pub operations: Vec<ExecutableDocument>,
+
     pub htpasswd: Vec<Content<String>>,
 
     pub jwks: Vec<Content<JwkSet>>,
+
+    /// Contains the PEM encoded client certificates used for mutual TLS with
+    /// the upstream
+    pub client_certs: Vec<Content<String>>,
+
+    /// Contains the PEM encoded keys of the client certificates
+    pub client_keys: Vec<Content<String>>,
+
+    /// Contains the PEM encoded CA certificates trusted for the upstream
+    pub ca_certs: Vec<Content<String>>,
 }
 
 impl Extensions {
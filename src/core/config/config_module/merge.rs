@@ -100,6 +100,9 @@ impl Contravariant for Field {
                 cache: self.cache.merge_right(other.cache),
                 default_value: self.default_value.or(other.default_value),
                 protected: self.protected.merge_right(other.protected),
+                invalidates: self.invalidates.merge_right(other.invalidates),
+                rest_expose: self.rest_expose.merge_right(other.rest_expose),
+                mock: self.mock.merge_right(other.mock),
                 discriminate: self.discriminate.merge_right(other.discriminate),
                 resolvers: self.resolvers.merge_right(other.resolvers),
                 directives: self.directives.merge_right(other.directives),
@@ -122,6 +125,9 @@ impl Covariant for Field {
                 cache: self.cache.merge_right(other.cache),
                 default_value: self.default_value.or(other.default_value),
                 protected: self.protected.merge_right(other.protected),
+                invalidates: self.invalidates.merge_right(other.invalidates),
+                rest_expose: self.rest_expose.merge_right(other.rest_expose),
+                mock: self.mock.merge_right(other.mock),
                 discriminate: self.discriminate.merge_right(other.discriminate),
                 resolvers: self.resolvers.merge_right(other.resolvers),
                 directives: self.directives.merge_right(other.directives),
@@ -162,19 +168,29 @@ impl Covariant for Type {
 }
 
 impl Contravariant for Enum {
-    fn shrink(mut self, other: Self) -> Valid<Self, String> {
-        self.variants.retain(|key| other.variants.contains(key));
+    fn shrink(self, mut other: Self) -> Valid<Self, String> {
+        let variants = self
+            .variants
+            .into_iter()
+            .filter_map(|variant| {
+                let other_variant = other.variants.take(&variant)?;
+                Some(variant.merge_right(other_variant))
+            })
+            .collect();
 
-        Valid::succeed(Self {
-            variants: self.variants,
-            doc: self.doc.merge_right(other.doc),
-        })
+        Valid::succeed(Self { variants, doc: self.doc.merge_right(other.doc) })
     }
 }
 
 impl Covariant for Enum {
     fn expand(mut self, other: Self) -> Valid<Self, String> {
-        self.variants.extend(other.variants);
+        for variant in other.variants {
+            let variant = match self.variants.take(&variant) {
+                Some(this) => this.merge_right(variant),
+                None => variant,
+            };
+            self.variants.insert(variant);
+        }
 
         Valid::succeed(Self {
             variants: self.variants,
@@ -423,7 +439,7 @@ mod tests {
     use tailcall_valid::Validator;
 
     use super::*;
-    use crate::core::config::ConfigModule;
+    use crate::core::config::{ConfigModule, Variant};
     use crate::include_config;
 
     #[test]
@@ -486,6 +502,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_enums_with_docs() {
+        let variant = |name: &str, doc: Option<&str>| Variant {
+            name: name.to_string(),
+            alias: None,
+            doc: doc.map(str::to_string),
+        };
+        let enum_of =
+            |variants: Vec<Variant>| Enum { variants: variants.into_iter().collect(), doc: None };
+        let enum1 = enum_of(vec![variant("A", Some("First")), variant("B", None)]);
+        let enum2 = enum_of(vec![variant("A", None), variant("C", Some("Third"))]);
+
+        let shrunk = enum1.clone().shrink(enum2.clone()).to_result().unwrap();
+        assert_eq!(
+            shrunk
+                .variants
+                .into_iter()
+                .map(|v| (v.name, v.doc))
+                .collect::<Vec<_>>(),
+            vec![("A".to_string(), Some("First".to_string()))]
+        );
+
+        let expanded = enum1.expand(enum2).to_result().unwrap();
+        assert_eq!(
+            expanded
+                .variants
+                .into_iter()
+                .map(|v| (v.name, v.doc))
+                .collect::<Vec<_>>(),
+            vec![
+                ("A".to_string(), Some("First".to_string())),
+                ("B".to_string(), None),
+                ("C".to_string(), Some("Third".to_string())),
+            ]
+        );
+    }
+
     #[test]
     fn test_federation_router() -> Result<()> {
         let router = ConfigModule::from(include_config!("./fixtures/router.graphql")?);
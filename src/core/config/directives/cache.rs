@@ -3,6 +3,8 @@ use std::num::NonZeroU64;
 use serde::{Deserialize, Serialize};
 use tailcall_macros::{DirectiveDefinition, InputDefinition, MergeRight};
 
+use crate::core::is_default;
+
 #[derive(
     Clone,
     Debug,
@@ -24,4 +26,21 @@ pub struct Cache {
     /// Specifies the duration, in milliseconds, of how long the value has to be
     /// stored in the cache.
     pub max_age: NonZeroU64,
+
+    /// Tags associated with the cached values, so that they can be purged with
+    /// `@invalidates` or the cache purge endpoint before they expire. They are
+    /// templates rendered with the arguments of the field, e.g.
+    /// `"user:{{.args.id}}"`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tags: Vec<String>,
+
+    /// Specifies the duration, in milliseconds, for which an expired value is
+    /// still served while it's refreshed in the background.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub stale_while_revalidate: Option<NonZeroU64>,
+
+    /// Specifies the duration, in milliseconds, for which an expired value is
+    /// served when refreshing it fails.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub stale_if_error: Option<NonZeroU64>,
 }
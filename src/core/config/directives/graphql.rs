@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use tailcall_macros::{DirectiveDefinition, InputDefinition};
 
-use crate::core::config::KeyValue;
+use crate::core::config::{KeyValue, UpstreamAuth};
 use crate::core::is_default;
 
 #[derive(
@@ -49,6 +49,9 @@ pub struct GraphQL {
     /// corresponding upstream field.
     pub name: String,
     #[serde(default, skip_serializing_if = "is_default")]
+    /// Overrides the `auth` settings of `@upstream` for this resolver.
+    pub auth: Option<UpstreamAuth>,
+    #[serde(default, skip_serializing_if = "is_default")]
     /// Enables deduplication of IO operations to enhance performance.
     ///
     /// This flag prevents duplicate IO requests from being executed
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tailcall_macros::{DirectiveDefinition, InputDefinition};
 
-use crate::core::config::{Encoding, KeyValue, URLQuery};
+use crate::core::config::{Encoding, KeyValue, URLQuery, UpstreamAuth};
 use crate::core::http::Method;
 use crate::core::is_default;
 use crate::core::json::JsonSchema;
@@ -83,6 +83,11 @@ pub struct Http {
     /// first parameter referencing a field in the current value using mustache
     /// syntax is automatically selected as the batching parameter.
     pub query: Vec<URLQuery>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Overrides the `auth` settings of `@upstream` for this resolver.
+    pub auth: Option<UpstreamAuth>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// Enables deduplication of IO operations to enhance performance.
     ///
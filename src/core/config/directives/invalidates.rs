@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use tailcall_macros::{DirectiveDefinition, MergeRight};
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    schemars::JsonSchema,
+    DirectiveDefinition,
+    MergeRight,
+)]
+#[directive_definition(locations = "FieldDefinition")]
+#[serde(deny_unknown_fields)]
+///
+/// The `@invalidates` directive purges the cached values tagged with any of the
+/// given tags once the mutation it is applied to succeeds. For example
+/// `@invalidates(tags: ["user:{{.args.id}}"])` removes the values cached with
+/// `@cache(tags: ["user:{{.args.id}}"])` for the same user.
+pub struct Invalidates {
+    /// Tags of the cached values to purge. They are templates rendered with the
+    /// arguments (`{{.args}}`) and the result (`{{.value}}`) of the mutation.
+    pub tags: Vec<String>,
+}
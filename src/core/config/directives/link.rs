@@ -27,7 +27,9 @@ pub enum LinkType {
     Protobuf,
 
     /// Points to a JS file. The imported JS file will be used by the `@js`
-    /// directive.
+    /// directive. When the link has an id, the file is loaded as an ES module
+    /// whose exports are used as `<id>.<name>`, and that the other modules can
+    /// import by its id.
     Script,
 
     /// Points to a Cert file. The imported Cert file will be used by the server
@@ -53,6 +55,20 @@ pub enum LinkType {
     /// Points to a reflection endpoint. The imported reflection endpoint will
     /// be used by the `@grpc` directive to resolve data from gRPC services.
     Grpc,
+
+    /// Points to a Cert file. The imported certificate chain will be presented
+    /// to the upstream services that require mutual TLS.
+    ClientCert,
+
+    /// Points to a Key file. The imported key belongs to the certificate linked
+    /// with `ClientCert` and is used for mutual TLS with the upstream services.
+    ClientKey,
+
+    /// Points to a Cert file containing one or more CA certificates. The
+    /// imported certificates are trusted in addition to the system roots when
+    /// connecting to the upstream services. It is also used to verify the
+    /// client certificates when referenced by `@server(clientAuth)`.
+    CaCert,
 }
 
 /// The @link directive allows you to import external resources, such as
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tailcall_macros::DirectiveDefinition;
+
+use crate::core::merge_right::MergeRight;
+
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, Eq, schemars::JsonSchema, DirectiveDefinition,
+)]
+#[directive_definition(locations = "FieldDefinition")]
+#[serde(deny_unknown_fields)]
+///
+/// The `@mock` directive sets the value returned by a field when the server
+/// runs in mock mode, instead of the generated one. It's ignored otherwise.
+pub struct Mock {
+    /// Value returned by the field.
+    pub value: Value,
+}
+
+// The value is replaced as a whole rather than merged
+impl MergeRight for Mock {
+    fn merge_right(self, other: Self) -> Self {
+        other
+    }
+}
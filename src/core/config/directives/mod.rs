@@ -8,11 +8,14 @@ mod federation;
 mod graphql;
 mod grpc;
 mod http;
+mod invalidates;
 mod js;
 mod link;
+mod mock;
 mod modify;
 mod omit;
 mod protected;
+mod rest_expose;
 mod server;
 mod telemetry;
 mod upstream;
@@ -27,11 +30,14 @@ pub use federation::*;
 pub use graphql::*;
 pub use grpc::*;
 pub use http::*;
+pub use invalidates::*;
 pub use js::*;
 pub use link::*;
+pub use mock::*;
 pub use modify::*;
 pub use omit::*;
 pub use protected::*;
+pub use rest_expose::*;
 pub use server::*;
 pub use telemetry::*;
 pub use upstream::*;
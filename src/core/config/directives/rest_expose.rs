@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use tailcall_macros::{DirectiveDefinition, MergeRight};
+
+use crate::core::is_default;
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    schemars::JsonSchema,
+    DirectiveDefinition,
+    MergeRight,
+)]
+#[directive_definition(locations = "FieldDefinition")]
+#[serde(deny_unknown_fields)]
+///
+/// The `@restExpose` directive exposes a field of `Query` or `Mutation` as a
+/// REST endpoint, without writing an `@rest` operation for it. Queries are
+/// served with `GET` and mutations with `POST`. Required scalar arguments
+/// become path params, optional ones query params and the remaining input
+/// argument, if any, the request body.
+pub struct RestExpose {
+    /// Path of the endpoint, e.g. `/users/$id`, where `$id` refers to the
+    /// `id` argument of the field. Defaults to the name of the field followed
+    /// by its required scalar arguments.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub path: Option<String>,
+
+    /// Depth of the selection set that is generated for the output type of
+    /// the field. Defaults to `2`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub depth: Option<usize>,
+}
@@ -46,6 +46,12 @@ pub struct Server {
     /// debugging. Use judiciously. @default `false`.
     pub batch_requests: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `clientAuth` makes the server ask the clients for a TLS certificate
+    /// and verify it against the given CA. The verified certificate can be
+    /// used to protect fields with `@protected`. Only available with `HTTP2`.
+    pub client_auth: Option<ClientAuth>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// `headers` contains key-value pairs that are included as default headers
     /// in server responses, allowing for consistent header management across
@@ -125,6 +131,60 @@ pub struct Server {
     /// - graphQL: "/graphql" If not specified, these default values will be
     ///   used.
     pub routes: Option<Routes>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `cachePurge` exposes an endpoint that purges the values cached with
+    /// `@cache(tags)` by their tags. The endpoint requires the same
+    /// authentication as `@protected`.
+    pub cache_purge: Option<CachePurge>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `cacheStatus` adds a `cacheStatus` extension to the responses that
+    /// counts how the values of `@cache` were resolved, which helps debugging
+    /// the stale modes of the cache. @default `false`.
+    pub cache_status: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `responseCache` caches the whole response of the queries whose fields
+    /// are all cached with `@cache`, for the least `maxAge` of the fields.
+    /// The queries with a `@protected` field are never cached. It requires
+    /// `enableJIT` and it's bypassed while `cacheStatus` is enabled.
+    pub response_cache: Option<ResponseCache>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `autoRest` exposes every field of `Query` and `Mutation` as a REST
+    /// endpoint, as if they were annotated with `@restExpose`. The fields
+    /// whose arguments can't be mapped to the request are skipped.
+    /// @default `false`.
+    pub auto_rest: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `openapi` serves an OpenAPI document that describes the REST endpoints
+    /// at `/api/openapi.json`. @default `false`.
+    pub openapi: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `queryPlan` adds a `queryPlan` extension to the responses of the
+    /// requests that send its header. The extension describes how the request
+    /// was executed: the plan of the operation, the calls made to the upstream
+    /// with their rendered URLs and timings, the values served by `@cache`,
+    /// the number of calls sent through each data loader and the number of
+    /// deduplicated calls. The traced requests are always executed: they
+    /// bypass the cache of the constant responses, the deduplication of the
+    /// identical operations in flight and the `responseCache`.
+    pub query_plan: Option<QueryPlan>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `mock` serves synthetic data instead of calling the upstream. The
+    /// fields with a resolver return values that match their type, e.g. valid
+    /// emails for `Email` and one of the values for enums, unless they are
+    /// overridden with `@mock`. @default `false`.
+    pub mock: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `mockSeed` is the seed of the generator of the mocked data. The same
+    /// seed, schema and query always produce the same data. @default `0`.
+    pub mock_seed: Option<u64>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, MergeRight, JsonSchema, Getters)]
@@ -164,9 +224,109 @@ fn merge_right_vars(mut left: Vec<KeyValue>, right: Vec<KeyValue>) -> Vec<KeyVal
     left
 }
 
+#[derive(
+    Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+/// Settings for the verification of client certificates (mutual TLS).
+pub struct ClientAuth {
+    /// The `id` of the auth provider exposed for the client certificates. Use
+    /// it in `@protected` to require a verified client certificate.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub id: Option<String>,
+
+    /// The `id` of the `CaCert` link containing the CA certificates that the
+    /// client certificates are verified with.
+    pub ca: String,
+
+    /// When `true` the TLS handshake fails for clients that don't present a
+    /// valid certificate. Otherwise such clients are accepted, but they can't
+    /// access the protected fields. @default `false`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub required: Option<bool>,
+
+    /// The certificate subjects (common name or subject alternative name)
+    /// that are allowed to access the protected fields. Any certificate issued
+    /// by the CA is allowed when empty.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub subjects: Vec<String>,
+}
+
+impl ClientAuth {
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(false)
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+/// Settings of the endpoint that purges the cached values by their tags. It
+/// accepts `POST` requests with a JSON body like `{"tags": ["user:1"]}`.
+pub struct CachePurge {
+    /// Path of the endpoint. @default `/cache/purge`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub path: Option<String>,
+
+    /// List of authentication provider IDs that can purge the cache. Leave
+    /// empty to require authorization from all providers.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub id: Option<Vec<String>>,
+}
+
+impl CachePurge {
+    pub fn get_path(&self) -> String {
+        self.path.clone().unwrap_or("/cache/purge".to_string())
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+/// Settings of the `queryPlan` extension. The requests that ask for it must be
+/// authorized by the same providers as `@protected`.
+pub struct QueryPlan {
+    /// Name of the request header that asks for the extension.
+    /// @default `x-tailcall-query-plan`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub header: Option<String>,
+
+    /// List of authentication provider IDs that can see the query plans. Leave
+    /// empty to require authorization from all providers.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub id: Option<Vec<String>>,
+}
+
+impl QueryPlan {
+    pub fn get_header(&self) -> String {
+        self.header
+            .clone()
+            .unwrap_or("x-tailcall-query-plan".to_string())
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+/// Settings of the cache of the whole responses. The responses are keyed by
+/// the normalized query, its variables and the `vary` headers.
+pub struct ResponseCache {
+    /// Names of the request headers whose values are part of the key, e.g. the
+    /// headers that select the language of the response.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub vary: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, schemars::JsonSchema, MergeRight)]
 #[serde(rename_all = "camelCase")]
 pub struct ScriptOptions {
+    /// `timeout` sets the maximum time in milliseconds a single call to the
+    /// script can take, including the `fetch` calls made by it. @default
+    /// `5000`.
     pub timeout: Option<u64>,
 }
 
@@ -184,10 +344,21 @@ impl Server {
         self.apollo_tracing.unwrap_or(false)
     }
 
+    pub fn enable_cache_status(&self) -> bool {
+        self.cache_status.unwrap_or(false)
+    }
+
     pub fn get_global_response_timeout(&self) -> i64 {
         self.global_response_timeout.unwrap_or(0)
     }
 
+    pub fn get_script_timeout(&self) -> u64 {
+        self.script
+            .as_ref()
+            .and_then(|script| script.timeout)
+            .unwrap_or(5000)
+    }
+
     pub fn get_workers(&self) -> usize {
         self.workers.unwrap_or(num_cpus::get())
     }
@@ -204,12 +375,30 @@ impl Server {
             .map(|h| h.enable_cache_control())
             .unwrap_or(false)
     }
+    pub fn enable_etag(&self) -> bool {
+        self.headers
+            .as_ref()
+            .map(|h| h.enable_etag())
+            .unwrap_or(false)
+    }
     pub fn enable_set_cookies(&self) -> bool {
         self.headers
             .as_ref()
             .map(|h| h.set_cookies())
             .unwrap_or(false)
     }
+    pub fn enable_auto_rest(&self) -> bool {
+        self.auto_rest.unwrap_or(false)
+    }
+    pub fn enable_openapi(&self) -> bool {
+        self.openapi.unwrap_or(false)
+    }
+    pub fn enable_mock(&self) -> bool {
+        self.mock.unwrap_or(false)
+    }
+    pub fn get_mock_seed(&self) -> u64 {
+        self.mock_seed.unwrap_or(0)
+    }
     pub fn enable_introspection(&self) -> bool {
         self.introspection.unwrap_or(true)
     }
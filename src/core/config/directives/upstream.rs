@@ -34,6 +34,67 @@ pub struct Proxy {
     pub url: String,
 }
 
+#[derive(
+    Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+/// TLS settings for the connections to a single upstream host. The values
+/// refer to the `id` of `@link` definitions of the matching type.
+pub struct HostTls {
+    /// The host name these settings apply to, e.g. `orders.mesh.local`.
+    pub host: String,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// The id of the `ClientCert` link presented to this host.
+    pub client_cert: Option<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// The id of the `ClientKey` link that belongs to `clientCert`.
+    pub client_key: Option<String>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// The id of the `CaCert` link trusted for this host.
+    pub ca: Option<String>,
+}
+
+#[derive(
+    Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+/// Credentials used to obtain an access token from an OAuth2 authorization
+/// server using the client credentials grant. Every value can be a Mustache
+/// template, e.g. `{{.env.CLIENT_SECRET}}`.
+pub struct OAuth2 {
+    /// The URL of the token endpoint of the authorization server.
+    pub token_url: String,
+
+    /// The client identifier issued to Tailcall by the authorization server.
+    pub client_id: String,
+
+    /// The client secret issued to Tailcall by the authorization server.
+    pub client_secret: String,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// The scopes that are requested for the access token.
+    pub scopes: Vec<String>,
+}
+
+#[derive(
+    Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, schemars::JsonSchema, MergeRight,
+)]
+#[serde(deny_unknown_fields)]
+/// The `auth` setting describes how Tailcall authenticates itself when making
+/// requests to the upstream. The obtained token is sent to the upstream in the
+/// `Authorization` header.
+pub struct UpstreamAuth {
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Fetches a bearer token using the OAuth2 client credentials grant. The
+    /// token is cached and refreshed shortly before it expires.
+    pub oauth2: Option<OAuth2>,
+}
+
 #[derive(
     Serialize,
     Deserialize,
@@ -66,6 +127,12 @@ pub struct Upstream {
     /// security but possibly limiting data flow.
     pub allowed_headers: Option<BTreeSet<String>>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `auth` defines how Tailcall authenticates with the upstream services.
+    /// It applies to every `@http`, `@grpc` and `@graphQL` resolver unless
+    /// the resolver specifies its own `auth`.
+    pub auth: Option<UpstreamAuth>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// An object that specifies the batch settings, including `maxSize` (the
     /// maximum size of the batch), `delay` (the delay in milliseconds between
@@ -126,6 +193,12 @@ pub struct Upstream {
     /// the connection.
     pub tcp_keep_alive: Option<u64>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// The `tls` setting overrides the client certificate and the trusted CA
+    /// certificates for specific upstream hosts. Links of type `ClientCert`,
+    /// `ClientKey` and `CaCert` without an `id` apply to all the other hosts.
+    pub tls: Vec<HostTls>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// The maximum time in seconds that the connection will wait for a
     /// response.
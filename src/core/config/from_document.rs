@@ -12,7 +12,10 @@ use indexmap::IndexMap;
 use tailcall_valid::{Valid, ValidationError, Validator};
 
 use super::directive::{to_directive, Directive};
-use super::{Alias, Discriminate, Resolver, RuntimeConfig, Telemetry, FEDERATION_DIRECTIVES};
+use super::{
+    Alias, Discriminate, Invalidates, Mock, Resolver, RestExpose, RuntimeConfig, Telemetry,
+    FEDERATION_DIRECTIVES,
+};
 use crate::core::config::{
     self, Cache, Config, Enum, Link, Modify, Omit, Protected, RootSchema, Server, Union, Upstream,
     Variant,
@@ -326,7 +329,12 @@ where
     let doc = description.to_owned().map(|pos| pos.node);
 
     config::Resolver::from_directives(directives)
-        .fuse(Cache::from_directives(directives.iter()))
+        .fuse(
+            Cache::from_directives(directives.iter())
+                .zip(Invalidates::from_directives(directives.iter()))
+                .zip(RestExpose::from_directives(directives.iter()))
+                .zip(Mock::from_directives(directives.iter())),
+        )
         .fuse(Omit::from_directives(directives.iter()))
         .fuse(Modify::from_directives(directives.iter()))
         .fuse(Protected::from_directives(directives.iter()))
@@ -336,7 +344,7 @@ where
         .map(
             |(
                 resolvers,
-                cache,
+                (((cache, invalidates), rest_expose), mock),
                 omit,
                 modify,
                 protected,
@@ -351,6 +359,9 @@ where
                 omit,
                 cache,
                 protected,
+                invalidates,
+                rest_expose,
+                mock,
                 discriminate,
                 default_value,
                 resolvers,
@@ -403,15 +414,20 @@ fn to_union(union_type: UnionType, doc: &Option<String>) -> Valid<Union, String>
 fn to_enum(enum_type: EnumType, doc: Option<String>) -> Valid<Enum, String> {
     let variants = Valid::from_iter(enum_type.values.iter(), |member| {
         let name = member.node.value.node.as_str().to_owned();
+        let doc = member.node.description.to_owned().map(|pos| pos.node);
         let alias = member
             .node
             .directives
             .iter()
             .find(|d| d.node.name.node.as_str() == Alias::directive_name());
         if let Some(alias) = alias {
-            Alias::from_directive(&alias.node).map(|alias| Variant { name, alias: Some(alias) })
+            Alias::from_directive(&alias.node).map(|alias| Variant {
+                name,
+                alias: Some(alias),
+                doc,
+            })
         } else {
-            Valid::succeed(Variant { name, alias: None })
+            Valid::succeed(Variant { name, alias: None, doc })
         }
     });
     variants.map(|v| Enum { variants: v.into_iter().collect::<BTreeSet<Variant>>(), doc })
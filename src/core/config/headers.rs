@@ -18,6 +18,13 @@ pub struct Headers {
     /// upstream services. @default `false`.
     pub cache_control: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `etag` sends `ETag` headers, computed from the content of the response,
+    /// in the responses of the `GET` requests and replies with `304 Not
+    /// Modified` when the `If-None-Match` header of the request matches.
+    /// @default `false`.
+    pub etag: Option<bool>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// `headers` are key-value pairs included in every server
     /// response. Useful for setting headers like `Access-Control-Allow-Origin`
@@ -43,6 +50,9 @@ impl Headers {
     pub fn enable_cache_control(&self) -> bool {
         self.cache_control.unwrap_or(false)
     }
+    pub fn enable_etag(&self) -> bool {
+        self.etag.unwrap_or(false)
+    }
     pub fn set_cookies(&self) -> bool {
         self.set_cookies.unwrap_or_default()
     }
@@ -162,7 +162,7 @@ fn config_document(config: &Config) -> ServiceDocument {
     for (name, union) in config.unions.iter() {
         definitions.push(TypeSystemDefinition::Type(pos(TypeDefinition {
             extend: false,
-            description: None,
+            description: union.doc.clone().map(pos),
             name: pos(Name::new(name)),
             directives: Vec::new(),
             kind: TypeKind::Union(UnionType {
@@ -187,7 +187,7 @@ fn config_document(config: &Config) -> ServiceDocument {
                     .iter()
                     .map(|variant| {
                         pos(EnumValueDefinition {
-                            description: None,
+                            description: variant.doc.clone().map(pos),
                             value: pos(Name::new(&variant.name)),
                             directives: variant
                                 .alias
@@ -221,6 +221,9 @@ fn field_directives(field: &crate::core::config::Field) -> Vec<Positioned<ConstD
         .chain(field.omit.as_ref().map(|d| pos(d.to_directive())))
         .chain(field.cache.as_ref().map(|d| pos(d.to_directive())))
         .chain(field.protected.as_ref().map(|d| pos(d.to_directive())))
+        .chain(field.invalidates.as_ref().map(|d| pos(d.to_directive())))
+        .chain(field.rest_expose.as_ref().map(|d| pos(d.to_directive())))
+        .chain(field.mock.as_ref().map(|d| pos(d.to_directive())))
         .chain(into_directives(&field.directives))
         .collect()
 }
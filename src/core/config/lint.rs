@@ -0,0 +1,411 @@
+use std::collections::BTreeMap;
+
+use convert_case::{Case, Casing};
+use serde::{Deserialize, Serialize};
+
+use super::{Config, Field, Resolver};
+use crate::core::http::Method;
+
+/// Rules checked by the linter. They flag configurations that are valid, but
+/// likely to be slow, unsafe or hard to maintain.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Rule {
+    UnbatchedListResolver,
+    MissingCache,
+    InsecureSsl,
+    UnprotectedMutation,
+    InconsistentNaming,
+    UnusedType,
+    ForwardedAuthorization,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 7] = [
+        Rule::UnbatchedListResolver,
+        Rule::MissingCache,
+        Rule::InsecureSsl,
+        Rule::UnprotectedMutation,
+        Rule::InconsistentNaming,
+        Rule::UnusedType,
+        Rule::ForwardedAuthorization,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Rule::UnbatchedListResolver => {
+                "Resolvers of the items of a list should be batched to avoid N + 1 requests"
+            }
+            Rule::MissingCache => "GET requests to the upstream should be cached with @cache",
+            Rule::InsecureSsl => "The certificates of the upstream should be verified",
+            Rule::UnprotectedMutation => "Mutations should be protected with @protected",
+            Rule::InconsistentNaming => {
+                "Types should be PascalCase, fields and arguments camelCase and enum values UPPER_CASE"
+            }
+            Rule::UnusedType => "Types should be reachable from the root types",
+            Rule::ForwardedAuthorization => {
+                "The authorization header of the clients shouldn't be forwarded to the upstream"
+            }
+        }
+    }
+
+    fn default_level(&self) -> Level {
+        match self {
+            Rule::InsecureSsl | Rule::ForwardedAuthorization => Level::Error,
+            _ => Level::Warning,
+        }
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Level {
+    Off,
+    Warning,
+    Error,
+}
+
+/// Settings of the linter, usually read from a `.tailcalllint.yml` file, e.g.
+/// `rules: { missing-cache: off, unused-type: error }`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// Levels of the rules, overriding their default level.
+    #[serde(default)]
+    pub rules: BTreeMap<Rule, Level>,
+}
+
+impl LintConfig {
+    /// Parses the settings from YAML, or JSON.
+    pub fn from_yaml(content: &str) -> anyhow::Result<Self> {
+        Ok(serde_yaml_ng::from_str(content)?)
+    }
+
+    pub fn level(&self, rule: Rule) -> Level {
+        self.rules
+            .get(&rule)
+            .copied()
+            .unwrap_or(rule.default_level())
+    }
+}
+
+/// Violation of a rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Lint {
+    pub rule: Rule,
+    pub level: Level,
+    /// Element of the configuration that violates the rule, e.g. `User.posts`.
+    pub path: String,
+    pub message: String,
+}
+
+struct Linter<'a> {
+    config: &'a Config,
+    lint_config: &'a LintConfig,
+    lints: Vec<Lint>,
+}
+
+impl Linter<'_> {
+    fn add(&mut self, rule: Rule, path: impl Into<String>, message: impl Into<String>) {
+        let level = self.lint_config.level(rule);
+        if level != Level::Off {
+            self.lints
+                .push(Lint { rule, level, path: path.into(), message: message.into() });
+        }
+    }
+
+    fn unbatched_list_resolvers(&mut self) {
+        for path in self.config.n_plus_one().paths() {
+            self.add(
+                Rule::UnbatchedListResolver,
+                path.join("."),
+                "A request is sent for each item of the list, add a batchKey to batch them",
+            );
+        }
+    }
+
+    fn missing_cache(&mut self) {
+        for (type_name, type_of) in self.config.types.iter() {
+            if type_of.cache.is_some() {
+                continue;
+            }
+            for (field_name, field) in type_of.fields.iter() {
+                let is_get = field.resolvers.0.iter().any(
+                    |resolver| matches!(resolver, Resolver::Http(http) if http.method == Method::GET),
+                );
+                if is_get && field.cache.is_none() {
+                    self.add(
+                        Rule::MissingCache,
+                        format!("{type_name}.{field_name}"),
+                        "The GET request isn't cached, add @cache to cache it",
+                    );
+                }
+            }
+        }
+    }
+
+    fn insecure_ssl(&mut self) {
+        if !self.config.upstream.get_verify_ssl() {
+            self.add(
+                Rule::InsecureSsl,
+                "@upstream",
+                "verifySSL is disabled, the certificates of the upstream aren't verified",
+            );
+        }
+    }
+
+    fn unprotected_mutations(&mut self) {
+        let Some((name, mutation)) = self
+            .config
+            .schema
+            .mutation
+            .as_ref()
+            .and_then(|name| Some((name, self.config.find_type(name)?)))
+        else {
+            return;
+        };
+        if mutation.protected.is_some() {
+            return;
+        }
+
+        for (field_name, field) in mutation.fields.iter() {
+            if field.protected.is_none() {
+                self.add(
+                    Rule::UnprotectedMutation,
+                    format!("{name}.{field_name}"),
+                    "The mutation can be called by anyone, add @protected to require authentication",
+                );
+            }
+        }
+    }
+
+    fn inconsistent_naming(&mut self) {
+        for (type_name, type_of) in self.config.types.iter() {
+            self.check_case(type_name, type_name, Case::Pascal, "PascalCase");
+            for (field_name, field) in type_of.fields.iter() {
+                let path = format!("{type_name}.{field_name}");
+                self.check_case(&path, field_name, Case::Camel, "camelCase");
+                self.check_args(&path, field);
+            }
+        }
+        for (union_name, _) in self.config.unions.iter() {
+            self.check_case(union_name, union_name, Case::Pascal, "PascalCase");
+        }
+        for (enum_name, enum_of) in self.config.enums.iter() {
+            self.check_case(enum_name, enum_name, Case::Pascal, "PascalCase");
+            for variant in enum_of.variants.iter() {
+                let path = format!("{enum_name}.{}", variant.name);
+                self.check_case(&path, &variant.name, Case::UpperSnake, "UPPER_CASE");
+            }
+        }
+    }
+
+    fn check_args(&mut self, path: &str, field: &Field) {
+        for arg_name in field.args.keys() {
+            let path = format!("{path}({arg_name})");
+            self.check_case(&path, arg_name, Case::Camel, "camelCase");
+        }
+    }
+
+    fn check_case(&mut self, path: &str, name: &str, case: Case, case_name: &str) {
+        // names reserved for introspection and federation are left alone
+        if name.starts_with('_') || name.to_case(case) == name {
+            return;
+        }
+
+        self.add(
+            Rule::InconsistentNaming,
+            path,
+            format!(
+                "`{name}` should be {case_name}, e.g. `{}`",
+                name.to_case(case)
+            ),
+        );
+    }
+
+    fn unused_types(&mut self) {
+        let mut unused_types = self.config.unused_types().into_iter().collect::<Vec<_>>();
+        unused_types.sort();
+        let input_types = self.config.input_types();
+        for type_name in unused_types {
+            // input types are only reachable from the arguments
+            if input_types.contains(&type_name) {
+                continue;
+            }
+            self.add(
+                Rule::UnusedType,
+                type_name,
+                "The type isn't reachable from Query or Mutation",
+            );
+        }
+    }
+
+    fn forwarded_authorization(&mut self) {
+        let forwarded = self
+            .config
+            .upstream
+            .get_allowed_headers()
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case("authorization"));
+        if forwarded {
+            self.add(
+                Rule::ForwardedAuthorization,
+                "@upstream",
+                "allowedHeaders forwards the authorization header of the clients to the upstream",
+            );
+        }
+    }
+}
+
+impl Config {
+    /// Checks the configuration against the rules enabled in the settings.
+    pub fn lint(&self, lint_config: &LintConfig) -> Vec<Lint> {
+        let mut linter = Linter { config: self, lint_config, lints: vec![] };
+
+        linter.unbatched_list_resolvers();
+        linter.missing_cache();
+        linter.insecure_ssl();
+        linter.unprotected_mutations();
+        linter.inconsistent_naming();
+        linter.unused_types();
+        linter.forwarded_authorization();
+
+        linter.lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tailcall_valid::Validator;
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+        schema
+          @server
+          @upstream(batch: {delay: 10}, verifySSL: false, allowedHeaders: ["Authorization"]) {
+          query: Query
+          mutation: Mutation
+        }
+
+        type Query {
+          users: [User] @http(url: "http://jsonplaceholder.typicode.com/users") @cache(maxAge: 60)
+        }
+
+        type Mutation {
+          create_user(name: String): User
+            @http(method: POST, url: "http://jsonplaceholder.typicode.com/users")
+        }
+
+        type User {
+          id: Int!
+          name: String
+          posts: [Post] @http(url: "http://jsonplaceholder.typicode.com/users/{{.value.id}}/posts")
+        }
+
+        type Post {
+          id: Int!
+        }
+
+        type Orphan {
+          id: Int!
+        }
+    "#;
+
+    fn lints(lint_config: &LintConfig) -> Vec<(Rule, Level, String)> {
+        Config::from_sdl(CONFIG)
+            .to_result()
+            .unwrap()
+            .lint(lint_config)
+            .into_iter()
+            .map(|lint| (lint.rule, lint.level, lint.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(
+            lints(&LintConfig::default()),
+            vec![
+                (
+                    Rule::UnbatchedListResolver,
+                    Level::Warning,
+                    "users.posts".to_string()
+                ),
+                (Rule::MissingCache, Level::Warning, "User.posts".to_string()),
+                (Rule::InsecureSsl, Level::Error, "@upstream".to_string()),
+                (
+                    Rule::UnprotectedMutation,
+                    Level::Warning,
+                    "Mutation.create_user".to_string()
+                ),
+                (
+                    Rule::InconsistentNaming,
+                    Level::Warning,
+                    "Mutation.create_user".to_string()
+                ),
+                (Rule::UnusedType, Level::Warning, "Orphan".to_string()),
+                (
+                    Rule::ForwardedAuthorization,
+                    Level::Error,
+                    "@upstream".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_config() {
+        let lint_config = LintConfig::from_yaml(
+            r#"
+            rules:
+              unbatched-list-resolver: off
+              missing-cache: off
+              insecure-ssl: warning
+              unprotected-mutation: off
+              inconsistent-naming: off
+              unused-type: error
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            lints(&lint_config),
+            vec![
+                (Rule::InsecureSsl, Level::Warning, "@upstream".to_string()),
+                (Rule::UnusedType, Level::Error, "Orphan".to_string()),
+                (
+                    Rule::ForwardedAuthorization,
+                    Level::Error,
+                    "@upstream".to_string()
+                ),
+            ]
+        );
+    }
+}
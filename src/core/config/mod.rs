@@ -4,6 +4,7 @@ pub use config_module::*;
 pub use directive::Directive;
 pub use directives::*;
 pub use key_values::*;
+pub use lint::{Level, Lint, LintConfig, Rule};
 pub use npo::QueryPath;
 pub use reader_context::*;
 pub use resolver::*;
@@ -20,6 +21,7 @@ pub mod group_by;
 mod headers;
 mod into_document;
 mod key_values;
+mod lint;
 mod npo;
 pub mod reader;
 pub mod reader_context;
@@ -14,6 +14,10 @@ impl QueryPath {
     pub fn size(&self) -> usize {
         self.0.len()
     }
+
+    pub fn paths(&self) -> &[Vec<String>] {
+        &self.0
+    }
 }
 
 impl<'a> From<Chunk<Chunk<Name<'a>>>> for QueryPath {
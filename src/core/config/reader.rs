@@ -8,7 +8,7 @@ use rustls_pki_types::{
 use tailcall_valid::{Valid, ValidationError, Validator};
 use url::Url;
 
-use super::{ConfigModule, Content, Link, LinkType, PrivateKey};
+use super::{ConfigModule, Content, Extensions, Link, LinkType, PrivateKey, ScriptModule};
 use crate::core::config::{Config, ConfigReaderContext, Source};
 use crate::core::proto_reader::ProtoReader;
 use crate::core::resource_reader::{Cached, Resource, ResourceReader};
@@ -91,7 +91,14 @@ impl ConfigReader {
                 LinkType::Script => {
                     let source = self.resource_reader.read_file(path).await?;
                     let content = source.content;
-                    extensions.script = Some(content);
+                    match link.id.as_ref() {
+                        Some(id) => extensions.script_modules.push(ScriptModule {
+                            id: id.clone(),
+                            path: source.path,
+                            source: content,
+                        }),
+                        None => extensions.script = Some(content),
+                    }
                 }
                 LinkType::Cert => {
                     let source = self.resource_reader.read_file(path).await?;
@@ -108,6 +115,9 @@ impl ConfigReader {
                     let content = source.content;
 
                     extensions.endpoint_set = EndpointSet::try_new(&content)?;
+                    extensions
+                        .operations
+                        .push(async_graphql::parser::parse_query(&content)?);
                 }
                 LinkType::Htpasswd => {
                     let source = self.resource_reader.read_file(path).await?;
@@ -128,6 +138,39 @@ impl ConfigReader {
                         content: serde_path_to_error::deserialize(de)?,
                     })
                 }
+                LinkType::ClientCert => {
+                    let source = self.resource_reader.read_file(path).await?;
+                    let content = source.content;
+                    if self.load_cert(content.clone()).await?.is_empty() {
+                        anyhow::bail!("No certificate found in {}", link.src);
+                    }
+
+                    extensions
+                        .client_certs
+                        .push(Content { id: link.id.clone(), content });
+                }
+                LinkType::ClientKey => {
+                    let source = self.resource_reader.read_file(path).await?;
+                    let content = source.content;
+                    if self.load_private_key(content.clone()).await?.is_empty() {
+                        anyhow::bail!("No private key found in {}", link.src);
+                    }
+
+                    extensions
+                        .client_keys
+                        .push(Content { id: link.id.clone(), content });
+                }
+                LinkType::CaCert => {
+                    let source = self.resource_reader.read_file(path).await?;
+                    let content = source.content;
+                    if self.load_cert(content.clone()).await?.is_empty() {
+                        anyhow::bail!("No certificate found in {}", link.src);
+                    }
+
+                    extensions
+                        .ca_certs
+                        .push(Content { id: link.id.clone(), content });
+                }
                 LinkType::Grpc => {
                     let meta = self
                         .proto_reader
@@ -225,7 +268,10 @@ impl ConfigReader {
             })
         });
 
-        Ok(config_module.to_result()?)
+        let config_module = config_module.to_result()?;
+        let endpoint_set = EndpointSet::from_config(config_module.config())?;
+
+        Ok(config_module.merge_extensions(Extensions { endpoint_set, ..Default::default() }))
     }
 
     /// Resolves all the links in a Config to create a ConfigModule
@@ -3,6 +3,7 @@ mod flatten_single_field;
 mod improve_type_names;
 mod merge_types;
 mod nested_unions;
+mod preserve_edits;
 mod preset;
 mod rename_types;
 mod required;
@@ -15,6 +16,7 @@ pub use flatten_single_field::FlattenSingleField;
 pub use improve_type_names::ImproveTypeNames;
 pub use merge_types::TypeMerger;
 pub use nested_unions::NestedUnions;
+pub use preserve_edits::PreserveEdits;
 pub use preset::Preset;
 pub use rename_types::RenameTypes;
 pub use required::Required;
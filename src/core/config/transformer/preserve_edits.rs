@@ -0,0 +1,288 @@
+use tailcall_valid::Valid;
+
+use crate::core::config::{Config, Field, Type};
+use crate::core::merge_right::MergeRight;
+use crate::core::Transform;
+
+/// Carries the edits made by hand to a previously generated configuration
+/// over to the regenerated one, so that it can be regenerated without losing
+/// them. The caching, auth, docs and renames (`@modify`) of the types and
+/// fields that are still generated are kept, as well as the settings of the
+/// server, upstream and telemetry, the additional links and the types and
+/// fields added by hand. The types and fields of the previous generated
+/// output that aren't generated anymore are dropped. The resolvers are always
+/// taken from the regenerated configuration, with a warning when they were
+/// edited.
+pub struct PreserveEdits {
+    edited: Config,
+    previous: Config,
+}
+
+impl PreserveEdits {
+    /// Without the previous generated output, every type and field missing
+    /// from the regenerated configuration is taken as added by hand.
+    pub fn new(edited: Config) -> Self {
+        Self { edited, previous: Config::default() }
+    }
+
+    /// Sets the configuration the edited one was generated as, to tell the
+    /// types and fields added by hand from the stale generated ones.
+    pub fn previous(mut self, previous: Config) -> Self {
+        self.previous = previous;
+        self
+    }
+}
+
+fn preserve_type(type_name: &str, type_: &mut Type, edited: &Type, previous: Option<&Type>) {
+    type_.doc = edited.doc.clone().or(type_.doc.take());
+    type_.cache = edited.cache.clone().or(type_.cache.take());
+    type_.protected = edited.protected.clone().or(type_.protected.take());
+
+    for (field_name, edited) in edited.fields.iter() {
+        match type_.fields.get_mut(field_name) {
+            Some(field) => preserve_field(type_name, field_name, field, edited),
+            None if !previous.is_some_and(|type_| type_.fields.contains_key(field_name)) => {
+                type_.fields.insert(field_name.clone(), edited.clone());
+            }
+            None => {}
+        }
+    }
+}
+
+fn preserve_field(type_name: &str, field_name: &str, field: &mut Field, edited: &Field) {
+    if edited.resolvers != field.resolvers {
+        tracing::warn!(
+            "The resolver of {}.{} was replaced by the regenerated one",
+            type_name,
+            field_name
+        );
+    }
+
+    field.doc = edited.doc.clone().or(field.doc.take());
+    field.modify = edited.modify.clone().or(field.modify.take());
+    field.omit = edited.omit.clone().or(field.omit.take());
+    field.cache = edited.cache.clone().or(field.cache.take());
+    field.protected = edited.protected.clone().or(field.protected.take());
+    field.invalidates = edited.invalidates.clone().or(field.invalidates.take());
+    field.rest_expose = edited.rest_expose.clone().or(field.rest_expose.take());
+    field.mock = edited.mock.clone().or(field.mock.take());
+}
+
+impl Transform for PreserveEdits {
+    type Value = Config;
+    type Error = String;
+
+    fn transform(&self, mut config: Self::Value) -> Valid<Self::Value, Self::Error> {
+        let edited = &self.edited;
+        let previous = &self.previous;
+
+        config.server = config.server.merge_right(edited.server.clone());
+        config.upstream = config.upstream.merge_right(edited.upstream.clone());
+        config.telemetry = config.telemetry.merge_right(edited.telemetry.clone());
+        for link in edited.links.iter() {
+            if !config.links.contains(link) {
+                config.links.push(link.clone());
+            }
+        }
+
+        for (type_name, edited) in edited.types.iter() {
+            match config.types.get_mut(type_name) {
+                Some(type_) => {
+                    preserve_type(type_name, type_, edited, previous.types.get(type_name))
+                }
+                None if !previous.types.contains_key(type_name) => {
+                    config.types.insert(type_name.clone(), edited.clone());
+                }
+                None => {}
+            }
+        }
+        for (name, union_) in edited.unions.iter() {
+            if !previous.unions.contains_key(name) {
+                config
+                    .unions
+                    .entry(name.clone())
+                    .or_insert_with(|| union_.clone());
+            }
+        }
+        for (name, enum_) in edited.enums.iter() {
+            if !previous.enums.contains_key(name) {
+                config
+                    .enums
+                    .entry(name.clone())
+                    .or_insert_with(|| enum_.clone());
+            }
+        }
+
+        Valid::succeed(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::*;
+
+    #[test]
+    fn test_preserve_edits() {
+        let generated = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+            }
+
+            type User {
+              id: Int
+              name: String
+            }
+        "#,
+        )
+        .to_result()
+        .unwrap();
+        let edited = Config::from_sdl(
+            r#"
+            schema @server(port: 8000) @upstream {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @http(url: "http://jsonplaceholder.typicode.com/old") @cache(maxAge: 60)
+            }
+
+            type User @protected {
+              id: Int
+              name: String @modify(name: "fullName")
+              removed: String @cache(maxAge: 10)
+            }
+
+            type Post {
+              id: Int
+            }
+        "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let config = PreserveEdits::new(edited)
+            .transform(generated.clone())
+            .to_result()
+            .unwrap();
+        let users = &config.types["Query"].fields["users"];
+        let user = &config.types["User"];
+
+        assert_eq!(config.server.port, Some(8000));
+        assert_eq!(
+            users.resolvers,
+            generated.types["Query"].fields["users"].resolvers
+        );
+        assert!(users.cache.is_some());
+        assert!(user.protected.is_some());
+        assert_eq!(
+            user.fields["name"]
+                .modify
+                .as_ref()
+                .and_then(|m| m.name.as_deref()),
+            Some("fullName")
+        );
+        assert!(user.fields["removed"].cache.is_some());
+        assert!(config.types.contains_key("Post"));
+    }
+
+    #[test]
+    fn test_drop_stale_generated_items() {
+        let previous = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+              posts: [Post] @http(url: "http://jsonplaceholder.typicode.com/posts")
+            }
+
+            type User {
+              id: Int
+              email: String
+            }
+
+            type Post {
+              id: Int
+            }
+
+            enum Status {
+              ACTIVE
+            }
+        "#,
+        )
+        .to_result()
+        .unwrap();
+        let edited = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+              posts: [Post] @http(url: "http://jsonplaceholder.typicode.com/posts")
+            }
+
+            type User {
+              id: Int
+              email: String @cache(maxAge: 10)
+              fullName: String @expr(body: "{{.value.name}}")
+            }
+
+            type Post {
+              id: Int
+            }
+
+            type Comment {
+              id: Int
+            }
+
+            enum Status {
+              ACTIVE
+            }
+        "#,
+        )
+        .to_result()
+        .unwrap();
+        let generated = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+            }
+
+            type User {
+              id: Int
+            }
+        "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let config = PreserveEdits::new(edited)
+            .previous(previous)
+            .transform(generated)
+            .to_result()
+            .unwrap();
+        let user = &config.types["User"];
+
+        assert!(!config.types["Query"].fields.contains_key("posts"));
+        assert!(!config.types.contains_key("Post"));
+        assert!(!config.enums.contains_key("Status"));
+        assert!(!user.fields.contains_key("email"));
+        assert!(user.fields.contains_key("fullName"));
+        assert!(config.types.contains_key("Comment"));
+    }
+}
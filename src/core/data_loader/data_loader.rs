@@ -13,6 +13,13 @@ pub use super::cache::NoCache;
 pub use super::factory::CacheFactory;
 pub use super::loader::Loader;
 pub use super::storage::CacheStorage;
+use crate::core::metrics;
+
+/// Name of the loader without its module path, used to label the metrics.
+fn loader_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
 
 /// Data loader.
 ///
@@ -330,6 +337,7 @@ where
         T: Loader<K>,
     {
         let keys = keys.into_iter().collect::<Vec<_>>();
+        metrics::record_batch_size(loader_name::<T>(), keys.len());
 
         match self.loader.load(&keys).await {
             Ok(values) => {
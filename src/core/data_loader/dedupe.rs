@@ -5,6 +5,8 @@ use std::sync::{Arc, Mutex, Weak};
 use futures_util::Future;
 use tokio::sync::broadcast;
 
+use crate::core::metrics;
+
 pub trait Key: Send + Sync + Eq + Hash + Clone {}
 impl<A: Send + Sync + Eq + Hash + Clone> Key for A {}
 
@@ -91,12 +93,16 @@ impl<K: Key, V: Value> Dedupe<K, V> {
 
         if let Some(state) = this.get(key) {
             match state {
-                State::Ready(value) => return Step::Return(value.clone()),
+                State::Ready(value) => {
+                    metrics::record_dedupe_hit();
+                    return Step::Return(value.clone());
+                }
                 State::Pending(tx) => {
                     // We can upgrade from Weak to Arc only in case when
                     // original tx is still alive
                     // otherwise we will create in the code below
                     if let Some(tx) = tx.upgrade() {
+                        metrics::record_dedupe_hit();
                         return Step::Await(tx.subscribe());
                     }
                 }
@@ -0,0 +1,162 @@
+use serde::Deserialize;
+use url::Url;
+
+use super::{into_inputs, parse_body, parse_method, Capture};
+use crate::core::generator::Input;
+
+/// Subset of the HTTP Archive format, as exported by the browsers.
+#[derive(Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Deserialize)]
+struct Log {
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    request: Request,
+    response: Response,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<Header>,
+    post_data: Option<Content>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    status: u16,
+    content: Content,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Content {
+    #[serde(default)]
+    mime_type: String,
+    text: Option<String>,
+    encoding: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    name: String,
+    value: String,
+}
+
+/// Extracts the requests of a HAR file that have a successful JSON response.
+pub fn from_har(content: &str) -> anyhow::Result<Vec<Input>> {
+    let har: Har = serde_json::from_str(content)?;
+    let captures = har
+        .log
+        .entries
+        .into_iter()
+        .filter_map(|entry| {
+            let Entry { request, response } = entry;
+            if !(200..300).contains(&response.status)
+                || !response.content.mime_type.contains("json")
+                || response.content.encoding.is_some()
+            {
+                return None;
+            }
+
+            Some(Capture {
+                name: None,
+                method: parse_method(&request.method)?,
+                url: Url::parse(&request.url).ok()?,
+                headers: request
+                    .headers
+                    .into_iter()
+                    .map(|header| (header.name, header.value))
+                    .collect(),
+                req_body: parse_body(request.post_data.and_then(|data| data.text).as_deref())?,
+                res_body: parse_body(response.content.text.as_deref())?,
+            })
+        })
+        .collect();
+
+    Ok(into_inputs(captures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_har() {
+        let har = r#"{
+          "log": {
+            "entries": [
+              {
+                "request": {
+                  "method": "GET",
+                  "url": "https://example.com/users/1",
+                  "headers": [
+                    {"name": "Cookie", "value": "session=1"},
+                    {"name": "X-Tenant", "value": "acme"}
+                  ]
+                },
+                "response": {
+                  "status": 200,
+                  "content": {"mimeType": "application/json", "text": "{\"id\": 1}"}
+                }
+              },
+              {
+                "request": {"method": "GET", "url": "https://example.com/users/2"},
+                "response": {
+                  "status": 200,
+                  "content": {"mimeType": "application/json", "text": "{\"id\": 2}"}
+                }
+              },
+              {
+                "request": {"method": "GET", "url": "https://example.com/logo.png"},
+                "response": {"status": 200, "content": {"mimeType": "image/png"}}
+              },
+              {
+                "request": {
+                  "method": "POST",
+                  "url": "https://example.com/users",
+                  "postData": {"mimeType": "application/json", "text": "{\"name\": \"a\"}"}
+                },
+                "response": {
+                  "status": 201,
+                  "content": {"mimeType": "application/json", "text": "{\"id\": 3}"}
+                }
+              }
+            ]
+          }
+        }"#;
+
+        let inputs = from_har(har).unwrap();
+        let inputs = inputs
+            .iter()
+            .filter_map(|input| match input {
+                Input::Json { field_name, is_mutation, headers, .. } => {
+                    Some((field_name.as_str(), *is_mutation, headers.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            inputs,
+            vec![
+                (
+                    "usersById",
+                    false,
+                    Some([("X-Tenant".to_string(), "acme".to_string())].into())
+                ),
+                ("usersById2", false, None),
+                ("postUsers", true, None),
+            ]
+        );
+    }
+}
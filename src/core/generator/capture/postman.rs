@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use url::Url;
+
+use super::{into_inputs, parse_body, parse_method, Capture};
+use crate::core::generator::Input;
+
+/// Subset of the Postman collection format v2.1.
+#[derive(Deserialize)]
+struct Collection {
+    #[serde(default)]
+    item: Vec<Item>,
+    #[serde(default)]
+    variable: Vec<Variable>,
+}
+
+/// Folder, with nested items, or request, with its saved responses.
+#[derive(Deserialize)]
+struct Item {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    item: Vec<Item>,
+    request: Option<Request>,
+    #[serde(default)]
+    response: Vec<Example>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+enum Request {
+    Url(String),
+    Request {
+        #[serde(default = "default_method")]
+        method: String,
+        #[serde(default)]
+        header: Vec<Header>,
+        url: RequestUrl,
+        body: Option<Body>,
+    },
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+enum RequestUrl {
+    Raw(String),
+    Url {
+        raw: String,
+        #[serde(default)]
+        variable: Vec<Variable>,
+    },
+}
+
+#[derive(Clone, Deserialize)]
+struct Header {
+    key: String,
+    value: String,
+    #[serde(default)]
+    disabled: bool,
+}
+
+#[derive(Clone, Deserialize)]
+struct Body {
+    raw: Option<String>,
+}
+
+#[derive(Clone, Deserialize)]
+struct Variable {
+    key: String,
+    #[serde(default)]
+    value: serde_json::Value,
+}
+
+/// Saved response of a request.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Example {
+    original_request: Option<Request>,
+    code: Option<u16>,
+    body: Option<String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn variable_value(variable: &Variable) -> String {
+    match &variable.value {
+        serde_json::Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Extracts the saved JSON responses of the requests of a Postman
+/// collection, with the `{{variables}}` of the collection and the `:params`
+/// of the paths replaced by their values.
+pub fn from_postman(content: &str) -> anyhow::Result<Vec<Input>> {
+    let collection: Collection = serde_json::from_str(content)?;
+    let variables = collection
+        .variable
+        .iter()
+        .map(|variable| (variable.key.clone(), variable_value(variable)))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut captures = vec![];
+    collect(&collection.item, &variables, &mut captures);
+
+    Ok(into_inputs(captures))
+}
+
+fn collect(items: &[Item], variables: &BTreeMap<String, String>, captures: &mut Vec<Capture>) {
+    for item in items {
+        collect(&item.item, variables, captures);
+
+        for example in item.response.iter() {
+            if !(200..300).contains(&example.code.unwrap_or(200)) {
+                continue;
+            }
+            let Some(request) = example.original_request.as_ref().or(item.request.as_ref()) else {
+                continue;
+            };
+            if let Some(capture) = capture(&item.name, request, example, variables) {
+                captures.push(capture);
+            }
+        }
+    }
+}
+
+fn capture(
+    name: &str,
+    request: &Request,
+    example: &Example,
+    variables: &BTreeMap<String, String>,
+) -> Option<Capture> {
+    let (method, headers, url, body) = match request {
+        Request::Url(url) => ("GET", vec![], RequestUrl::Raw(url.clone()), None),
+        Request::Request { method, header, url, body } => {
+            (method.as_str(), header.clone(), url.clone(), body.clone())
+        }
+    };
+    let url = match url {
+        RequestUrl::Raw(raw) => substitute(&raw, variables),
+        RequestUrl::Url { raw, variable } => {
+            let path_variables = variable
+                .iter()
+                .map(|variable| (format!(":{}", variable.key), variable_value(variable)))
+                .collect::<BTreeMap<_, _>>();
+            substitute(&raw, variables)
+                .split('/')
+                .map(|segment| path_variables.get(segment).map_or(segment, |v| v.as_str()))
+                .collect::<Vec<_>>()
+                .join("/")
+        }
+    };
+
+    Some(Capture {
+        name: Some(name.to_string()),
+        method: parse_method(method)?,
+        url: Url::parse(&url).ok()?,
+        headers: headers
+            .into_iter()
+            .filter(|header| !header.disabled)
+            .map(|header| (header.key, substitute(&header.value, variables)))
+            .collect(),
+        req_body: parse_body(body.and_then(|body| body.raw).as_deref())?,
+        res_body: parse_body(example.body.as_deref())?,
+    })
+}
+
+/// Replaces the `{{variables}}` of the collection by their values.
+fn substitute(value: &str, variables: &BTreeMap<String, String>) -> String {
+    variables
+        .iter()
+        .fold(value.to_string(), |value, (key, variable)| {
+            value.replace(&format!("{{{{{key}}}}}"), variable)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_postman() {
+        let collection = r#"{
+          "info": {"name": "Blog"},
+          "variable": [{"key": "baseUrl", "value": "https://example.com"}],
+          "item": [
+            {
+              "name": "Users",
+              "item": [
+                {
+                  "name": "Get user",
+                  "request": {
+                    "method": "GET",
+                    "url": {
+                      "raw": "{{baseUrl}}/users/:id",
+                      "variable": [{"key": "id", "value": "1"}]
+                    }
+                  },
+                  "response": [
+                    {"name": "OK", "code": 200, "body": "{\"id\": 1, \"name\": \"a\"}"},
+                    {"name": "Missing", "code": 404, "body": "{}"}
+                  ]
+                }
+              ]
+            },
+            {
+              "name": "Create post",
+              "request": {
+                "method": "POST",
+                "header": [{"key": "X-Tenant", "value": "acme"}],
+                "url": "{{baseUrl}}/posts",
+                "body": {"mode": "raw", "raw": "{\"title\": \"a\"}"}
+              },
+              "response": [{"name": "Created", "code": 201, "body": "{\"id\": 1}"}]
+            },
+            {"name": "Without examples", "request": "{{baseUrl}}/health"}
+          ]
+        }"#;
+
+        let inputs = from_postman(collection).unwrap();
+        let inputs = inputs
+            .iter()
+            .filter_map(|input| match input {
+                Input::Json { url, field_name, req_body, .. } => {
+                    Some((url.as_str(), field_name.as_str(), req_body.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            inputs,
+            vec![
+                (
+                    "https://example.com/users/1",
+                    "getUser",
+                    serde_json::Value::Null
+                ),
+                (
+                    "https://example.com/posts",
+                    "createPost",
+                    serde_json::json!({"title": "a"})
+                ),
+            ]
+        );
+    }
+}
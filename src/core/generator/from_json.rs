@@ -13,6 +13,7 @@ use crate::core::http::Method;
 use crate::core::merge_right::MergeRight;
 use crate::core::transform::{Transform, TransformerOps};
 
+#[derive(Clone)]
 pub struct RequestSample {
     pub url: Url,
     pub method: Method,
@@ -21,6 +22,9 @@ pub struct RequestSample {
     pub field_name: String,
     pub operation_type: GraphQLOperationType,
     pub headers: Option<BTreeMap<String, String>>,
+    /// Urls of the similar samples merged into this one, e.g. `/users/2` for
+    /// `/users/1`.
+    pub similar_urls: Vec<Url>,
 }
 
 impl RequestSample {
@@ -33,6 +37,7 @@ impl RequestSample {
             req_body: Default::default(),
             headers: Default::default(),
             operation_type: Default::default(),
+            similar_urls: Default::default(),
         }
     }
 
@@ -60,6 +65,116 @@ impl RequestSample {
         self.operation_type = operation_type;
         self
     }
+
+    /// Checks if both samples are requests to the same endpoint with
+    /// different params, e.g. `/users/1` and `/users/2`, or `/users?page=1`
+    /// and `/users?page=2`. Segments that aren't ids, e.g. `/users/alice` and
+    /// `/users/bob`, are params only when the responses have the same shape,
+    /// so that `/posts` and `/users` remain separate fields.
+    fn is_similar(&self, other: &RequestSample) -> bool {
+        let query_keys = |url: &Url| {
+            url.query_pairs()
+                .map(|(key, _)| key.into_owned())
+                .collect::<BTreeSet<_>>()
+        };
+        let (left, right) = (
+            json::path_segments(&self.url),
+            json::path_segments(&other.url),
+        );
+        if self.operation_type != other.operation_type
+            || self.method != other.method
+            || self.url.origin() != other.url.origin()
+            || left.len() != right.len()
+            || query_keys(&self.url) != query_keys(&other.url)
+        {
+            return false;
+        }
+
+        let left_shape = shape(&self.res_body);
+        let same_shape = !left_shape.1.is_empty() && left_shape == shape(&other.res_body);
+        let mut varying = left
+            .iter()
+            .zip(right.iter())
+            .enumerate()
+            .filter(|(_, (l, r))| l != r)
+            .peekable();
+        if varying.peek().is_none() {
+            return same_shape;
+        }
+
+        varying.all(|(index, (l, r))| {
+            (json::is_path_param(l) && json::is_path_param(r)) || (index > 0 && same_shape)
+        })
+    }
+
+    /// Merges a similar sample into this one, so that both are resolved by a
+    /// single field.
+    fn merge(&mut self, other: RequestSample) {
+        self.res_body = merge_json(std::mem::take(&mut self.res_body), other.res_body);
+        self.req_body = merge_json(std::mem::take(&mut self.req_body), other.req_body);
+        self.headers = match (self.headers.take(), other.headers) {
+            (Some(mut headers), Some(other_headers)) => {
+                for (key, value) in other_headers {
+                    headers.entry(key).or_insert(value);
+                }
+                Some(headers)
+            }
+            (headers, other_headers) => headers.or(other_headers),
+        };
+        self.similar_urls.push(other.url);
+        self.similar_urls.extend(other.similar_urls);
+    }
+}
+
+/// Whether the body is a list, with the keys of its objects.
+fn shape(value: &Value) -> (bool, Vec<&String>) {
+    let object = match value {
+        Value::Array(items) => items.first(),
+        value => Some(value),
+    };
+    let mut keys = object
+        .and_then(Value::as_object)
+        .map(|object| object.keys().collect::<Vec<_>>())
+        .unwrap_or_default();
+    keys.sort();
+
+    (value.is_array(), keys)
+}
+
+/// Merges the bodies of similar samples, so that the generated types have the
+/// fields of both.
+fn merge_json(left: Value, right: Value) -> Value {
+    match (left, right) {
+        (Value::Object(mut left), Value::Object(right)) => {
+            for (key, value) in right {
+                let value = match left.remove(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => value,
+                };
+                left.insert(key, value);
+            }
+            Value::Object(left)
+        }
+        (Value::Array(mut left), Value::Array(right)) => {
+            left.extend(right);
+            Value::Array(left)
+        }
+        (Value::Null, right) => right,
+        (left, _) => left,
+    }
+}
+
+/// Groups the similar samples, keeping the field name of the first sample of
+/// each group.
+fn group_similar(samples: &[RequestSample]) -> Vec<RequestSample> {
+    let mut groups: Vec<RequestSample> = vec![];
+    for sample in samples {
+        match groups.iter_mut().find(|group| group.is_similar(sample)) {
+            Some(group) => group.merge(sample.clone()),
+            None => groups.push(sample.clone()),
+        }
+    }
+    groups
 }
 
 pub struct FromJsonGenerator<'a> {
@@ -89,10 +204,10 @@ impl Transform for FromJsonGenerator<'_> {
     type Value = Config;
     type Error = String;
     fn transform(&self, config: Self::Value) -> Valid<Self::Value, Self::Error> {
-        let config_gen_req = self.request_samples;
+        let config_gen_req = group_similar(self.request_samples);
         let type_name_gen = self.type_name_generator;
 
-        Valid::from_iter(config_gen_req, |sample| {
+        Valid::from_iter(config_gen_req.iter(), |sample| {
             let (existing_name, suggested_name) = match sample.operation_type {
                 GraphQLOperationType::Query => (
                     GraphQLOperationType::Query
@@ -138,6 +253,7 @@ impl Transform for FromJsonGenerator<'_> {
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
     use tailcall_valid::Validator;
 
     use crate::core::config::transformer::Preset;
@@ -176,4 +292,51 @@ mod tests {
         insta::assert_snapshot!(config.to_sdl());
         Ok(())
     }
+
+    #[test]
+    fn generate_single_field_from_similar_urls() {
+        let sample = |url: &str, body: serde_json::Value, field_name: &str| {
+            RequestSample::new(url.parse().unwrap(), body, field_name.to_string())
+        };
+        let request_samples = [
+            sample(
+                "https://example.com/users/1",
+                json!({"id": 1, "name": "a"}),
+                "user",
+            ),
+            sample(
+                "https://example.com/users/2",
+                json!({"id": 2, "email": "b@example.com"}),
+                "user2",
+            ),
+            sample(
+                "https://example.com/posts",
+                json!([{"title": "a"}]),
+                "posts",
+            ),
+            sample("https://example.com/users", json!([{"id": 1}]), "users"),
+        ];
+
+        let config =
+            FromJsonGenerator::new(&request_samples, &NameGenerator::new("T"), "Query", &None)
+                .generate()
+                .to_result()
+                .unwrap();
+        let query = &config.types["Query"];
+
+        assert_eq!(
+            query.fields.keys().collect::<Vec<_>>(),
+            vec!["posts", "user", "users"]
+        );
+
+        let user = &query.fields["user"];
+        assert_eq!(user.args.keys().collect::<Vec<_>>(), vec!["id"]);
+        assert_eq!(format!("{:?}", user.args["id"].type_of), "Int!");
+
+        let user_type = &config.types[user.type_of.name()];
+        assert_eq!(
+            user_type.fields.keys().collect::<Vec<_>>(),
+            vec!["email", "id", "name"]
+        );
+    }
 }
@@ -207,7 +207,7 @@ impl Context {
 
             let variants_with_comments = variants_with_comments
                 .into_iter()
-                .map(|v| Variant { name: v, alias: None })
+                .map(|v| Variant { name: v, alias: None, doc: None })
                 .collect();
 
             self.config
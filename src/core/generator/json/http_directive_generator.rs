@@ -1,12 +1,13 @@
 use std::collections::HashSet;
 
 use convert_case::{Case, Casing};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use url::Url;
 
 use crate::core::config::{Arg, Field, Http, URLQuery};
 use crate::core::generator::PREFIX;
-use crate::core::helpers::gql_type::detect_gql_data_type;
+use crate::core::helpers::gql_type::{detect_gql_data_type, is_valid_field_name};
 use crate::core::Type;
 
 #[derive(Debug)]
@@ -51,14 +52,63 @@ impl<'a> UrlUtility<'a> {
     }
 }
 
+/// Segments of paths that identify a resource: numbers, UUIDs and hashes,
+/// e.g. `42` in `/users/42`.
+static PATH_PARAM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(\d+|[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}|[0-9a-f]{16,})$",
+    )
+    .unwrap()
+});
+
+/// Checks if the segment of a path identifies a resource, e.g. `42` in
+/// `/users/42`.
+pub fn is_path_param(segment: &str) -> bool {
+    PATH_PARAM.is_match(segment)
+}
+
+/// Picks the scalar that fits the values of both types.
+fn unify_types(left: String, right: String) -> String {
+    if left == right {
+        left
+    } else if matches!(
+        (left.as_str(), right.as_str()),
+        ("Int", "Float") | ("Float", "Int")
+    ) {
+        "Float".to_string()
+    } else {
+        "String".to_string()
+    }
+}
+
+/// Segments of the path of the url, e.g. `["users", "42"]` for `/users/42`.
+pub fn path_segments(url: &Url) -> Vec<&str> {
+    url.path_segments()
+        .map(|segments| segments.collect())
+        .unwrap_or_default()
+}
+
 pub struct HttpDirectiveGenerator<'a> {
     url: &'a Url,
+    similar_urls: &'a [Url],
     http: Http,
 }
 
 impl<'a> HttpDirectiveGenerator<'a> {
     pub fn new(url: &'a Url) -> Self {
-        Self { url, http: Http::default() }
+        Self { url, similar_urls: &[], http: Http::default() }
+    }
+
+    /// Sets the urls of the other requests resolved by the same field. The
+    /// segments of the path and the query params that vary across the urls
+    /// become arguments of the field.
+    pub fn with_similar_urls(mut self, similar_urls: &'a [Url]) -> Self {
+        self.similar_urls = similar_urls;
+        self
+    }
+
+    fn urls(&self) -> impl Iterator<Item = &'a Url> {
+        std::iter::once(self.url).chain(self.similar_urls.iter())
     }
 
     fn add_path_variables(&mut self, field: &mut Field) {
@@ -93,9 +143,83 @@ impl<'a> HttpDirectiveGenerator<'a> {
                         .to_string()
                 });
 
-        // add path in http directive.
+        self.set_path(&mustache_compatible_url);
+    }
+
+    /// Replaces the segments that vary across the urls, or that look like
+    /// ids, with arguments named after the resource they identify, e.g.
+    /// `/users/1` and `/users/2` become `/users/{{.args.id}}`.
+    fn add_inferred_path_variables(&mut self, field: &mut Field) {
+        let paths = self.urls().map(path_segments).collect::<Vec<_>>();
+        let segments = &paths[0];
+        let variables = (0..segments.len())
+            .filter(|&index| {
+                is_path_param(segments[index])
+                    || paths
+                        .iter()
+                        .any(|path| path.get(index) != segments.get(index))
+            })
+            .collect::<Vec<_>>();
+
+        let mut arg_index = 1;
+        let path = segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                if !variables.contains(&index) {
+                    return segment.to_string();
+                }
+
+                let type_of = paths
+                    .iter()
+                    .filter_map(|path| path.get(index))
+                    .map(|value| detect_gql_data_type(value))
+                    .reduce(unify_types)
+                    .unwrap_or_else(|| "String".to_string());
+                let arg_key = Self::arg_name(segments, index, &variables)
+                    .filter(|name| !field.args.contains_key(name))
+                    .unwrap_or_else(|| {
+                        let name = format!("{}{}", PREFIX, arg_index);
+                        arg_index += 1;
+                        name
+                    });
+                let arg = Arg {
+                    type_of: Type::from(type_of).into_required(),
+                    ..Default::default()
+                };
+                let placeholder = format!("{{{{.args.{}}}}}", arg_key);
+                field.args.insert(arg_key, arg);
+
+                placeholder
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        self.set_path(&format!("/{}", path));
+    }
+
+    /// Names the argument after the static segment that precedes it: `id` for
+    /// the last argument, e.g. `/users/1`, and `userId` for the others, e.g.
+    /// `/users/1/posts/2`.
+    fn arg_name(segments: &[&str], index: usize, variables: &[usize]) -> Option<String> {
+        let previous = index.checked_sub(1)?;
+        if variables.contains(&previous) {
+            return None;
+        }
+        let name = if variables.last() == Some(&index) {
+            "id".to_string()
+        } else {
+            let resource = pluralizer::pluralize(segments[previous], 1, false);
+            format!("{} id", resource).to_case(Case::Camel)
+        };
+
+        is_valid_field_name(&name).then_some(name)
+    }
+
+    /// Sets the url of the http directive, with the given path.
+    fn set_path(&mut self, path: &str) {
         let mut url = self.url.clone();
-        url.set_path(&mustache_compatible_url);
+        url.set_path(path);
         url.set_query(None);
 
         let url = url.to_string();
@@ -105,9 +229,23 @@ impl<'a> HttpDirectiveGenerator<'a> {
     }
 
     fn add_query_variables(&mut self, field: &mut Field) {
-        let url_utility = UrlUtility::new(self.url);
+        let mut queries: Vec<QueryParamInfo> = Vec::new();
+        for url in self.urls() {
+            for query in UrlUtility::new(url).get_query_params() {
+                match queries.iter_mut().find(|q| q.key == query.key) {
+                    Some(existing_query) => {
+                        existing_query.is_list |= query.is_list;
+                        existing_query.data_type = unify_types(
+                            std::mem::take(&mut existing_query.data_type),
+                            query.data_type,
+                        );
+                    }
+                    None => queries.push(query),
+                }
+            }
+        }
 
-        for query in url_utility.get_query_params() {
+        for query in queries {
             let type_of = Type::from(query.data_type.clone());
             let type_of = if query.is_list {
                 type_of.into_list()
@@ -129,7 +267,11 @@ impl<'a> HttpDirectiveGenerator<'a> {
     }
 
     pub fn generate_http_directive(mut self, field: &mut Field) -> Http {
-        self.add_path_variables(field);
+        if self.similar_urls.is_empty() {
+            self.add_path_variables(field);
+        } else {
+            self.add_inferred_path_variables(field);
+        }
         self.add_query_variables(field);
 
         self.http
@@ -142,10 +284,21 @@ mod test {
 
     use url::Url;
 
-    use super::HttpDirectiveGenerator;
+    use super::{is_path_param, HttpDirectiveGenerator};
     use crate::core::config::Field;
     use crate::core::generator::json::http_directive_generator::UrlUtility;
 
+    #[test]
+    fn test_is_path_param() {
+        assert!(is_path_param("42"));
+        assert!(is_path_param("0b9c8d6e-1f2a-4b3c-8d4e-5f6a7b8c9d0e"));
+        assert!(is_path_param("0B9C8D6E-1F2A-4B3C-8D4E-5F6A7B8C9D0E"));
+        assert!(is_path_param("5f2b6c1e9a3d4e7f"));
+        assert!(!is_path_param("users"));
+        assert!(!is_path_param("v1"));
+        assert!(!is_path_param(""));
+    }
+
     #[test]
     fn test_new_url_query_parser() {
         let url = Url::parse(
@@ -224,4 +377,36 @@ mod test {
         );
         assert_eq!(test_args, args);
     }
+
+    #[test]
+    fn test_http_directive_similar_urls() {
+        let url = Url::parse("http://example.com/users/1/posts/alice?page=1").unwrap();
+        let similar_urls = [
+            Url::parse("http://example.com/users/2/posts/bob?page=2.5").unwrap(),
+            Url::parse("http://example.com/users/3/posts/carol?page=3").unwrap(),
+        ];
+        let field = &mut Field { ..Default::default() };
+        let http = HttpDirectiveGenerator::new(&url)
+            .with_similar_urls(&similar_urls)
+            .generate_http_directive(field);
+        let args = field
+            .args
+            .iter()
+            .map(|(name, arg)| (name.as_str(), format!("{:?}", arg.type_of)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            "http://example.com/users/{{.args.userId}}/posts/{{.args.id}}",
+            http.url
+        );
+        assert_eq!(
+            vec![
+                ("userId", "Int!".to_string()),
+                ("id", "String!".to_string()),
+                ("page", "Float".to_string())
+            ],
+            args
+        );
+        assert_eq!(http.query[0].value, "{{.args.page}}");
+    }
 }
@@ -29,7 +29,8 @@ impl OperationTypeGenerator {
         };
 
         // generate required http directive.
-        let http_directive_gen = HttpDirectiveGenerator::new(&request_sample.url);
+        let http_directive_gen = HttpDirectiveGenerator::new(&request_sample.url)
+            .with_similar_urls(&request_sample.similar_urls);
         let mut http_resolver = http_directive_gen.generate_http_directive(&mut field);
 
         if let GraphQLOperationType::Mutation = request_sample.operation_type {
@@ -1,3 +1,4 @@
+mod capture;
 mod from_json;
 mod from_proto;
 mod generator;
@@ -5,6 +6,8 @@ mod graphql_type;
 mod json;
 mod proto;
 
+pub(crate) use capture::strip_credentials;
+pub use capture::{from_har, from_postman};
 pub use from_json::{FromJsonGenerator, RequestSample};
 pub use generator::{Generator, Input};
 
@@ -52,6 +52,7 @@ impl From<Grpc> for Http {
         let dedupe = grpc.dedupe;
         let select = grpc.select;
         let on_response_body = grpc.on_response_body;
+        let auth = grpc.auth;
 
         Self {
             url: new_url,
@@ -62,6 +63,7 @@ impl From<Grpc> for Http {
             dedupe,
             select,
             on_response_body,
+            auth,
             ..Default::default()
         }
     }
@@ -81,6 +83,7 @@ mod tests {
             method: "package.service.method".to_string(),
             body: Some(json!({"key": "value"})),
             headers: Default::default(),
+            auth: Default::default(),
             batch_key: Default::default(),
             dedupe: Default::default(),
             select: Default::default(),
@@ -101,6 +104,7 @@ mod tests {
             method: "package.service.method".to_string(),
             body: Default::default(),
             headers: Default::default(),
+            auth: Default::default(),
             batch_key: Default::default(),
             dedupe: Default::default(),
             select: Default::default(),
@@ -119,6 +123,7 @@ mod tests {
             method: "a.b.c".to_string(),
             body: None,
             headers: vec![KeyValue { key: "X-Foo".to_string(), value: "bar".to_string() }],
+            auth: Default::default(),
             batch_key: Default::default(),
             dedupe: Default::default(),
             select: Default::default(),
@@ -146,6 +151,7 @@ mod tests {
             method: "package.service.method".to_string(),
             body: Some(json!({"key": "value"})),
             headers: vec![KeyValue { key: "X-Foo".to_string(), value: "bar".to_string() }],
+            auth: Default::default(),
             batch_key: vec!["batch_key_value".to_string()],
             dedupe: Some(true),
             select: Some(Value::String("select_value".to_string())),
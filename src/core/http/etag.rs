@@ -0,0 +1,132 @@
+use std::hash::Hasher;
+
+use anyhow::Result;
+use hyper::header::{self, HeaderValue};
+use hyper::{Body, HeaderMap, Response, StatusCode};
+use tailcall_hasher::TailcallHasher;
+
+/// Returns a strong `ETag` computed from the content of the body.
+pub fn etag(body: &[u8]) -> HeaderValue {
+    let mut hasher = TailcallHasher::default();
+    hasher.write(body);
+    // the quoted hex digits are always a valid header value
+    HeaderValue::from_str(&format!("\"{:016x}\"", hasher.finish())).unwrap()
+}
+
+/// Checks if the `If-None-Match` header of the request matches the `ETag`.
+/// The weak comparison is used, as required for `If-None-Match`.
+fn matches(request_headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    let etag = etag.trim_start_matches("W/");
+
+    request_headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// Adds the `ETag` header to a successful response and replaces it with
+/// `304 Not Modified` when it matches the `If-None-Match` header of the
+/// request. The headers of the response, e.g. `Cache-Control`, are kept, so
+/// that the clients can refresh the cached copy.
+pub async fn conditional(
+    request_headers: &HeaderMap,
+    response: Response<Body>,
+) -> Result<Response<Body>> {
+    if response.status() != StatusCode::OK {
+        return Ok(response);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+    let etag = etag(&body);
+
+    let body = if matches(request_headers, &etag) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        Body::empty()
+    } else {
+        Body::from(body)
+    };
+    parts.headers.insert(header::ETAG, etag);
+
+    Ok(Response::from_parts(parts, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str) -> Response<Body> {
+        Response::builder()
+            .header(header::CACHE_CONTROL, "max-age=60")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_etag_is_stable() {
+        assert_eq!(etag(b"{\"id\":1}"), etag(b"{\"id\":1}"));
+        assert_ne!(etag(b"{\"id\":1}"), etag(b"{\"id\":2}"));
+    }
+
+    #[tokio::test]
+    async fn test_adds_etag() {
+        let response = conditional(&HeaderMap::new(), response("{}"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], etag(b"{}"));
+        assert_eq!(
+            hyper::body::to_bytes(response.into_body()).await.unwrap(),
+            "{}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_not_modified() {
+        let etag = etag(b"{}");
+        let headers = if_none_match(&format!("\"other\", W/{}", etag.to_str().unwrap()));
+        let response = conditional(&headers, response("{}")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "max-age=60");
+        assert!(hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_modified() {
+        let headers = if_none_match("\"other\"");
+        let response = conditional(&headers, response("{}")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_skips_errors() {
+        let response = Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = conditional(&if_none_match("*"), response).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response.headers().get(header::ETAG).is_none());
+    }
+}
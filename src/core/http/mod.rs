@@ -3,7 +3,9 @@ pub use data_loader::*;
 pub use data_loader_request::*;
 use http::HeaderValue;
 pub use method::Method;
+pub use oauth2::OAuth2Tokens;
 pub use query_encoder::QueryEncoder;
+pub use query_plan::{IoCall, QueryPlanTrace};
 pub use request_context::RequestContext;
 pub use request_handler::{handle_request, API_URL_PREFIX};
 pub use request_template::RequestTemplate;
@@ -12,8 +14,11 @@ pub use response::*;
 mod cache;
 mod data_loader;
 mod data_loader_request;
+mod etag;
 mod method;
+mod oauth2;
 mod query_encoder;
+mod query_plan;
 mod request_context;
 mod request_handler;
 mod request_template;
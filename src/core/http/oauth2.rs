@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use http::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use serde::Deserialize;
+
+use crate::core::blueprint::{OAuth2, UpstreamAuth};
+use crate::core::data_loader::DedupeResult;
+use crate::core::ir::Error;
+use crate::core::path::PathString;
+use crate::core::runtime::TargetRuntime;
+
+/// Tokens are refreshed once they are this close to their expiry, so that a
+/// request never leaves with a token that expires while it is in flight.
+const REFRESH_BEFORE_EXPIRY_SECS: i64 = 30;
+
+/// Lifetime assumed for tokens when the authorization server doesn't specify
+/// `expires_in` in its response.
+const DEFAULT_EXPIRES_IN_SECS: i64 = 3600;
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+#[derive(Clone, Debug)]
+struct AccessToken {
+    header: HeaderValue,
+    expires_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        now + Duration::seconds(REFRESH_BEFORE_EXPIRY_SECS) < self.expires_at
+    }
+}
+
+/// Identifies the client that a token was issued to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct TokenKey {
+    token_url: String,
+    client_id: String,
+    scopes: Vec<String>,
+}
+
+///
+/// Acquires access tokens for the upstream requests using the OAuth2 client
+/// credentials grant. Tokens are shared between all the requests that use the
+/// same client and are fetched again shortly before they expire. Concurrent
+/// requests for the same client share a single call to the token endpoint.
+pub struct OAuth2Tokens {
+    tokens: Mutex<HashMap<TokenKey, AccessToken>>,
+    pending: DedupeResult<TokenKey, AccessToken, Error>,
+}
+
+impl Default for OAuth2Tokens {
+    fn default() -> Self {
+        Self {
+            tokens: Mutex::new(HashMap::new()),
+            pending: DedupeResult::new(false),
+        }
+    }
+}
+
+impl OAuth2Tokens {
+    /// Sets the `Authorization` header required by the given auth settings.
+    pub async fn authorize<C: PathString>(
+        &self,
+        runtime: &TargetRuntime,
+        auth: &UpstreamAuth,
+        ctx: &C,
+        headers: &mut HeaderMap,
+    ) -> Result<(), Error> {
+        match auth {
+            UpstreamAuth::OAuth2(oauth2) => {
+                let token = self.get(runtime, oauth2, ctx).await?;
+                headers.insert(AUTHORIZATION, token.header);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get<C: PathString>(
+        &self,
+        runtime: &TargetRuntime,
+        oauth2: &OAuth2,
+        ctx: &C,
+    ) -> Result<AccessToken, Error> {
+        let key = TokenKey {
+            token_url: oauth2.token_url.render(ctx),
+            client_id: oauth2.client_id.render(ctx),
+            scopes: oauth2.scopes.clone(),
+        };
+
+        let cached = self.tokens.lock().unwrap().get(&key).cloned();
+        if let Some(token) = cached.filter(|token| token.is_fresh(Utc::now())) {
+            return Ok(token);
+        }
+
+        self.pending
+            .dedupe(&key, || async {
+                let client_secret = oauth2.client_secret.render(ctx);
+                let token = fetch_token(runtime, &key, &client_secret).await?;
+                self.tokens
+                    .lock()
+                    .unwrap()
+                    .insert(key.clone(), token.clone());
+                Ok(token)
+            })
+            .await
+    }
+}
+
+async fn fetch_token(
+    runtime: &TargetRuntime,
+    key: &TokenKey,
+    client_secret: &str,
+) -> Result<AccessToken, Error> {
+    let scope = key.scopes.join(" ");
+    let mut form = vec![
+        ("grant_type", "client_credentials"),
+        ("client_id", key.client_id.as_str()),
+        ("client_secret", client_secret),
+    ];
+    if !scope.is_empty() {
+        form.push(("scope", scope.as_str()));
+    }
+    let body = serde_urlencoded::to_string(form).map_err(|e| Error::IO(e.to_string()))?;
+
+    let url = url::Url::parse(&key.token_url).map_err(|e| Error::IO(e.to_string()))?;
+    let mut request = reqwest::Request::new(reqwest::Method::POST, url);
+    let headers = request.headers_mut();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("application/x-www-form-urlencoded"),
+    );
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    request.body_mut().replace(body.into());
+
+    let response = runtime.http.execute(request).await?;
+    if !response.status.is_success() {
+        // the body may echo the client credentials, so it is only logged
+        tracing::debug!(
+            "OAuth2 token response: {}",
+            String::from_utf8_lossy(&response.body)
+        );
+        return Err(Error::IO(format!(
+            "OAuth2 token request failed with {}",
+            response.status
+        )));
+    }
+    let token: TokenResponse = serde_json::from_slice(&response.body)
+        .map_err(|e| Error::Deserialize(format!("Invalid OAuth2 token response: {}", e)))?;
+
+    let header = HeaderValue::from_str(&format!("Bearer {}", token.access_token))
+        .map_err(|e| Error::IO(e.to_string()))?;
+    let expires_in = token.expires_in.unwrap_or(DEFAULT_EXPIRES_IN_SECS);
+
+    Ok(AccessToken {
+        header,
+        expires_at: Utc::now() + Duration::seconds(expires_in),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use hyper::body::Bytes;
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::http::Response;
+    use crate::core::mustache::Mustache;
+    use crate::core::HttpIO;
+
+    struct TokenServer {
+        hits: Arc<AtomicUsize>,
+        expires_in: i64,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpIO for TokenServer {
+        async fn execute(&self, request: reqwest::Request) -> anyhow::Result<Response<Bytes>> {
+            let hit = self.hits.fetch_add(1, Ordering::SeqCst) + 1;
+            let body = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| String::from_utf8_lossy(body).to_string())
+                .unwrap_or_default();
+
+            assert_eq!(request.url().as_str(), "http://auth.local/token");
+            assert_eq!(
+                body,
+                "grant_type=client_credentials&client_id=tailcall&client_secret=secret&scope=read+write"
+            );
+
+            let body = json!({
+                "access_token": format!("token-{}", hit),
+                "token_type": "bearer",
+                "expires_in": self.expires_in
+            });
+            Ok(Response::default().body(Bytes::from(body.to_string())))
+        }
+    }
+
+    fn setup(expires_in: i64) -> (TargetRuntime, Arc<AtomicUsize>, UpstreamAuth) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let mut runtime = crate::core::runtime::test::init(None);
+        runtime.http = Arc::new(TokenServer { hits: hits.clone(), expires_in });
+
+        let auth = UpstreamAuth::OAuth2(OAuth2 {
+            token_url: Mustache::parse("http://auth.local/token"),
+            client_id: Mustache::parse("tailcall"),
+            client_secret: Mustache::parse("{{.secret}}"),
+            scopes: vec!["read".to_string(), "write".to_string()],
+        });
+
+        (runtime, hits, auth)
+    }
+
+    async fn authorize(
+        tokens: &OAuth2Tokens,
+        runtime: &TargetRuntime,
+        auth: &UpstreamAuth,
+    ) -> HeaderMap {
+        let ctx = json!({"secret": "secret"});
+        let mut headers = HeaderMap::new();
+        tokens
+            .authorize(runtime, auth, &ctx, &mut headers)
+            .await
+            .unwrap();
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_token_is_reused_until_expiry() {
+        let (runtime, hits, auth) = setup(3600);
+        let tokens = OAuth2Tokens::default();
+
+        let first = authorize(&tokens, &runtime, &auth).await;
+        let second = authorize(&tokens, &runtime, &auth).await;
+
+        assert_eq!(first.get(AUTHORIZATION).unwrap(), "Bearer token-1");
+        assert_eq!(second.get(AUTHORIZATION).unwrap(), "Bearer token-1");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_refreshed_before_expiry() {
+        let (runtime, hits, auth) = setup(REFRESH_BEFORE_EXPIRY_SECS - 1);
+        let tokens = OAuth2Tokens::default();
+
+        let first = authorize(&tokens, &runtime, &auth).await;
+        let second = authorize(&tokens, &runtime, &auth).await;
+
+        assert_eq!(first.get(AUTHORIZATION).unwrap(), "Bearer token-1");
+        assert_eq!(second.get(AUTHORIZATION).unwrap(), "Bearer token-2");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    struct RejectingTokenServer;
+
+    #[async_trait::async_trait]
+    impl HttpIO for RejectingTokenServer {
+        async fn execute(&self, _: reqwest::Request) -> anyhow::Result<Response<Bytes>> {
+            let body = json!({"error": "invalid_client"});
+            Ok(Response {
+                status: reqwest::StatusCode::UNAUTHORIZED,
+                ..Default::default()
+            }
+            .body(Bytes::from(body.to_string())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_error_response() {
+        let (mut runtime, _, auth) = setup(3600);
+        runtime.http = Arc::new(RejectingTokenServer);
+        let tokens = OAuth2Tokens::default();
+
+        let ctx = json!({"secret": "secret"});
+        let mut headers = HeaderMap::new();
+        let error = tokens
+            .authorize(&runtime, &auth, &ctx, &mut headers)
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("401 Unauthorized"));
+        assert!(!error.to_string().contains("invalid_client"));
+        assert!(headers.get(AUTHORIZATION).is_none());
+    }
+}
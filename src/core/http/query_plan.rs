@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_graphql_value::ConstValue;
+use serde::Serialize;
+
+/// Records how a request is executed, for the `queryPlan` extension of the
+/// response.
+#[derive(Debug)]
+pub struct QueryPlanTrace {
+    started_at: Instant,
+    // Number of the IO resolvers that were evaluated, including the ones that
+    // were served by another in-flight call.
+    resolved: AtomicUsize,
+    calls: Mutex<Vec<IoCall>>,
+}
+
+/// Call made to the upstream while executing the request.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IoCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// Data loader the call was sent through, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_loader: Option<String>,
+    /// Status of the `@cache` of the field when the value was served by the
+    /// cache, in which case no call was made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<&'static str>,
+    pub start_ms: f64,
+    pub duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Number of the calls of the fields that were sent through the data loader.
+/// The data loader groups them into fewer requests to the upstream.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DataLoaderCalls<'a> {
+    data_loader: &'a str,
+    calls: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report<'a> {
+    duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plan: Option<serde_json::Value>,
+    calls: &'a [IoCall],
+    data_loaders: Vec<DataLoaderCalls<'a>>,
+    deduped: usize,
+}
+
+impl Default for QueryPlanTrace {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            resolved: AtomicUsize::new(0),
+            calls: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl QueryPlanTrace {
+    /// Time elapsed since the start of the request.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn add_resolved(&self) {
+        self.resolved.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a call that started at the given time since the start of the
+    /// request and just completed.
+    pub fn add_call(&self, mut call: IoCall, started_at: Duration) {
+        call.start_ms = as_millis(started_at);
+        call.duration_ms = as_millis(self.elapsed().saturating_sub(started_at));
+        self.calls.lock().unwrap().push(call);
+    }
+
+    /// Returns the value of the `queryPlan` extension, with the given
+    /// description of the plan of the operation.
+    pub fn to_value(&self, plan: Option<serde_json::Value>) -> ConstValue {
+        let mut calls = self.calls.lock().unwrap().clone();
+        calls.sort_by(|left, right| left.start_ms.total_cmp(&right.start_ms));
+
+        let upstream_calls = calls.iter().filter(|call| call.cache.is_none());
+        let mut data_loaders = BTreeMap::<&str, usize>::new();
+        for data_loader in upstream_calls
+            .clone()
+            .filter_map(|call| call.data_loader.as_deref())
+        {
+            *data_loaders.entry(data_loader).or_default() += 1;
+        }
+
+        let report = Report {
+            duration_ms: as_millis(self.elapsed()),
+            plan,
+            calls: &calls,
+            data_loaders: data_loaders
+                .into_iter()
+                .map(|(data_loader, calls)| DataLoaderCalls { data_loader, calls })
+                .collect(),
+            deduped: self
+                .resolved
+                .load(Ordering::Relaxed)
+                .saturating_sub(upstream_calls.count()),
+        };
+
+        serde_json::to_value(report)
+            .ok()
+            .and_then(|value| ConstValue::from_json(value).ok())
+            .unwrap_or_default()
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_to_value() {
+        let trace = QueryPlanTrace::default();
+        for _ in 0..4 {
+            trace.add_resolved();
+        }
+        for id in 1..=3 {
+            let call = IoCall {
+                kind: "http",
+                method: Some("GET".to_string()),
+                url: Some(format!("http://localhost/users/{id}")),
+                data_loader: (id > 1).then(|| "http#0".to_string()),
+                ..Default::default()
+            };
+            trace.add_call(call, Duration::from_millis(4 - id));
+        }
+        trace.add_call(
+            IoCall { kind: "http", cache: Some("hit"), ..Default::default() },
+            Duration::from_millis(4),
+        );
+
+        let value = trace.to_value(None).into_json().unwrap();
+
+        assert_eq!(value["deduped"], json!(1));
+        assert_eq!(
+            value["dataLoaders"],
+            json!([{"dataLoader": "http#0", "calls": 2}])
+        );
+        assert_eq!(value["calls"][3]["cache"], json!("hit"));
+        assert_eq!(value["calls"][0]["url"], json!("http://localhost/users/3"));
+        assert!(value.get("plan").is_none());
+    }
+}
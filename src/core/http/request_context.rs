@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroU64;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
@@ -8,13 +9,14 @@ use derive_setters::Setters;
 use http::header::{HeaderMap, HeaderName, HeaderValue};
 
 use crate::core::app_context::AppContext;
+use crate::core::auth::client_cert::ClientIdentity;
 use crate::core::blueprint::{Server, Upstream};
 use crate::core::data_loader::{DataLoader, DedupeResult};
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc::data_loader::GrpcDataLoader;
-use crate::core::http::{DataLoaderRequest, HttpDataLoader};
+use crate::core::http::{DataLoaderRequest, HttpDataLoader, OAuth2Tokens, QueryPlanTrace};
 use crate::core::ir::model::IoId;
-use crate::core::ir::Error;
+use crate::core::ir::{CacheStatus, Error};
 use crate::core::runtime::TargetRuntime;
 use crate::core::{cache, grpc};
 
@@ -35,6 +37,17 @@ pub struct RequestContext {
     pub runtime: TargetRuntime,
     pub cache: DedupeResult<IoId, ConstValue, Error>,
     pub dedupe_handler: Arc<DedupeResult<IoId, ConstValue, Error>>,
+    // Access tokens used to authenticate the requests sent to the upstream.
+    pub oauth2_tokens: Arc<OAuth2Tokens>,
+    // Identity from the client certificate verified during the TLS handshake.
+    pub client_identity: Option<ClientIdentity>,
+    // Number of the values of `@cache` resolved with each status.
+    pub cache_status: Arc<Mutex<BTreeMap<CacheStatus, usize>>>,
+    // Tags of the values of `@cache` resolved by the request, so that the
+    // cached response is purged along with them.
+    pub cache_tags: Arc<Mutex<BTreeSet<String>>>,
+    // Trace of the execution, when the `queryPlan` extension is requested.
+    pub query_plan: Option<Arc<QueryPlanTrace>>,
 }
 
 impl RequestContext {
@@ -53,8 +66,76 @@ impl RequestContext {
             cache: DedupeResult::new(true),
             dedupe_handler: Arc::new(DedupeResult::new(false)),
             allowed_headers: HeaderMap::new(),
+            oauth2_tokens: Arc::new(OAuth2Tokens::default()),
+            client_identity: None,
+            cache_status: Arc::new(Mutex::new(BTreeMap::new())),
+            cache_tags: Arc::new(Mutex::new(BTreeSet::new())),
+            query_plan: None,
         }
     }
+
+    /// Creates a context that shares the server, the runtime and the data
+    /// loaders with this one, but none of the state of the request, so that it
+    /// can be used by the work that outlives the request.
+    pub fn detached(&self) -> RequestContext {
+        RequestContext {
+            server: self.server.clone(),
+            upstream: self.upstream.clone(),
+            x_response_headers: Arc::new(Mutex::new(HeaderMap::new())),
+            cookie_headers: None,
+            allowed_headers: self.allowed_headers.clone(),
+            http_data_loaders: self.http_data_loaders.clone(),
+            gql_data_loaders: self.gql_data_loaders.clone(),
+            grpc_data_loaders: self.grpc_data_loaders.clone(),
+            min_max_age: Arc::new(Mutex::new(None)),
+            cache_public: Arc::new(Mutex::new(None)),
+            runtime: self.runtime.clone(),
+            cache: DedupeResult::new(true),
+            dedupe_handler: self.dedupe_handler.clone(),
+            oauth2_tokens: self.oauth2_tokens.clone(),
+            client_identity: self.client_identity.clone(),
+            cache_status: Arc::new(Mutex::new(BTreeMap::new())),
+            cache_tags: Arc::new(Mutex::new(BTreeSet::new())),
+            query_plan: None,
+        }
+    }
+
+    pub fn add_cache_status(&self, status: CacheStatus) {
+        if self.server.enable_cache_status {
+            *self.cache_status.lock().unwrap().entry(status).or_default() += 1;
+        }
+    }
+
+    pub fn add_cache_tags(&self, tags: &[String]) {
+        self.cache_tags.lock().unwrap().extend(tags.iter().cloned());
+    }
+
+    pub fn cache_tags(&self) -> Vec<String> {
+        self.cache_tags.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Returns the value of the `cacheStatus` extension of the response, if
+    /// any value of `@cache` was resolved.
+    pub fn cache_status(&self) -> Option<ConstValue> {
+        let cache_status = self.cache_status.lock().unwrap();
+        if cache_status.is_empty() {
+            return None;
+        }
+
+        let counts = CacheStatus::ALL
+            .iter()
+            .map(|status| {
+                let count = cache_status.get(status).copied().unwrap_or_default();
+                (
+                    async_graphql::Name::new(status.as_str()),
+                    ConstValue::from(count as u64),
+                )
+            })
+            .collect();
+
+        Some(ConstValue::Object(counts))
+    }
+
     fn set_min_max_age_conc(&self, min_max_age: i32) {
         *self.min_max_age.lock().unwrap() = Some(min_max_age);
     }
@@ -201,6 +282,11 @@ impl From<&AppContext> for RequestContext {
             runtime: app_ctx.runtime.clone(),
             cache: DedupeResult::new(true),
             dedupe_handler: app_ctx.dedupe_handler.clone(),
+            oauth2_tokens: app_ctx.oauth2_tokens.clone(),
+            client_identity: None,
+            cache_status: Arc::new(Mutex::new(BTreeMap::new())),
+            cache_tags: Arc::new(Mutex::new(BTreeSet::new())),
+            query_plan: None,
         }
     }
 }
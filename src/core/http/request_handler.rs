@@ -3,6 +3,7 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use anyhow::Result;
+use async_graphql::parser::types::OperationType;
 use async_graphql::ServerError;
 use hyper::header::{self, HeaderValue, CONTENT_TYPE};
 use hyper::http::request::Parts;
@@ -12,17 +13,28 @@ use opentelemetry::trace::SpanKind;
 use opentelemetry_semantic_conventions::trace::{HTTP_REQUEST_METHOD, HTTP_ROUTE};
 use prometheus::{Encoder, ProtobufEncoder, TextEncoder, TEXT_FORMAT};
 use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
 use tracing::Instrument;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use super::request_context::RequestContext;
 use super::telemetry::{get_response_status_code, RequestCounter};
-use super::{showcase, telemetry, TAILCALL_HTTPS_ORIGIN, TAILCALL_HTTP_ORIGIN};
+use super::{
+    etag, showcase, telemetry, QueryPlanTrace, TAILCALL_HTTPS_ORIGIN, TAILCALL_HTTP_ORIGIN,
+};
 use crate::core::app_context::AppContext;
-use crate::core::async_graphql_hyper::{GraphQLRequestLike, GraphQLResponse};
+use crate::core::async_graphql_hyper::{
+    GraphQLQuery, GraphQLRequest, GraphQLRequestLike, GraphQLResponse,
+};
+use crate::core::auth::client_cert::ClientIdentity;
+use crate::core::auth::verify::{AuthVerifier, Verify};
 use crate::core::blueprint::telemetry::TelemetryExporter;
+use crate::core::blueprint::CachePurge;
 use crate::core::config::{PrometheusExporter, PrometheusFormat};
 use crate::core::jit::JITExecutor;
+use crate::core::metrics;
+use crate::core::rest::{self, PartialRequest, OPENAPI_PATH};
 
 pub const API_URL_PREFIX: &str = "/api";
 
@@ -54,10 +66,82 @@ fn not_found() -> Result<Response<Body>> {
         .body(Body::empty())?)
 }
 
+#[derive(Deserialize)]
+struct CachePurgeRequest {
+    tags: Vec<String>,
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Result<Response<Body>> {
+    Ok(Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))?)
+}
+
+/// Purges the cached values with the tags listed in the request body.
+async fn handle_cache_purge(
+    req: Request<Body>,
+    app_ctx: &AppContext,
+    cache_purge: &CachePurge,
+) -> Result<Response<Body>> {
+    let req_ctx = create_request_context(&req, app_ctx);
+    let verification = AuthVerifier::from(cache_purge.auth.clone())
+        .verify(&req_ctx)
+        .await;
+    if let Err(err) = verification.to_result() {
+        return json_response(
+            StatusCode::UNAUTHORIZED,
+            json!({"message": err.to_string()}),
+        );
+    }
+
+    let bytes = hyper::body::to_bytes(req.into_body()).await?;
+    let request = match serde_json::from_slice::<CachePurgeRequest>(&bytes) {
+        Ok(request) => request,
+        Err(err) => {
+            return json_response(StatusCode::BAD_REQUEST, json!({"message": err.to_string()}))
+        }
+    };
+
+    let mut purged = 0;
+    for tag in request.tags.iter() {
+        purged += app_ctx.runtime.cache.purge_by_tag(tag).await?;
+    }
+
+    json_response(StatusCode::OK, json!({"purged": purged}))
+}
+
 fn create_request_context(req: &Request<Body>, app_ctx: &AppContext) -> RequestContext {
     let allowed_headers =
         create_allowed_headers(req.headers(), &app_ctx.blueprint.upstream.allowed_headers);
-    RequestContext::from(app_ctx).allowed_headers(allowed_headers)
+    let client_identity = req.extensions().get::<ClientIdentity>().cloned();
+    RequestContext::from(app_ctx)
+        .allowed_headers(allowed_headers)
+        .client_identity(client_identity)
+}
+
+/// Starts tracing the execution of the request when it asks for the
+/// `queryPlan` extension with the configured header. Fails when the request
+/// isn't authorized to see the plan.
+async fn trace_query_plan(
+    req: &Request<Body>,
+    req_ctx: &RequestContext,
+    app_ctx: &AppContext,
+) -> std::result::Result<Option<Arc<QueryPlanTrace>>, String> {
+    let Some(query_plan) = app_ctx.blueprint.server.query_plan.as_ref() else {
+        return Ok(None);
+    };
+    if !req.headers().contains_key(&query_plan.header) {
+        return Ok(None);
+    }
+
+    AuthVerifier::from(query_plan.auth.clone())
+        .verify(req_ctx)
+        .await
+        .to_result()
+        .map_err(|err| err.to_string())?;
+
+    Ok(Some(Arc::new(QueryPlanTrace::default())))
 }
 
 pub fn update_response_headers(
@@ -88,7 +172,14 @@ pub async fn graphql_request<T: DeserializeOwned + GraphQLRequestLike>(
     req_counter: &mut RequestCounter,
 ) -> Result<Response<Body>> {
     req_counter.set_http_route("/graphql");
-    let req_ctx = Arc::new(create_request_context(&req, app_ctx));
+    let mut req_ctx = create_request_context(&req, app_ctx);
+    match trace_query_plan(&req, &req_ctx, app_ctx).await {
+        Ok(query_plan) => req_ctx.query_plan = query_plan,
+        Err(message) => {
+            return json_response(StatusCode::UNAUTHORIZED, json!({ "message": message }))
+        }
+    }
+    let req_ctx = Arc::new(req_ctx);
     let (req, body) = req.into_parts();
     let bytes = hyper::body::to_bytes(body).await?;
     let graphql_request = serde_json::from_slice::<T>(&bytes);
@@ -113,37 +204,118 @@ pub async fn graphql_request<T: DeserializeOwned + GraphQLRequestLike>(
     }
 }
 
+/// Executes the GraphQL request encoded in the query string of a `GET`
+/// request. Only queries are allowed, since `GET` requests must not have side
+/// effects, which also allows caching the responses with `ETag`.
+#[tracing::instrument(skip_all, fields(otel.name = "graphQL", otel.kind = ?SpanKind::Server))]
+async fn graphql_get_request(
+    req: Request<Body>,
+    app_ctx: &Arc<AppContext>,
+    req_counter: &mut RequestCounter,
+) -> Result<Response<Body>> {
+    req_counter.set_http_route("/graphql");
+    let mut req_ctx = create_request_context(&req, app_ctx);
+    match trace_query_plan(&req, &req_ctx, app_ctx).await {
+        Ok(query_plan) => req_ctx.query_plan = query_plan,
+        Err(message) => {
+            return json_response(StatusCode::UNAUTHORIZED, json!({ "message": message }))
+        }
+    }
+    let req_ctx = Arc::new(req_ctx);
+    let (req, _) = req.into_parts();
+    let query = req.uri.query().unwrap_or_default();
+    let mut request = match serde_urlencoded::from_str::<GraphQLQuery>(query) {
+        Ok(query) => GraphQLRequest::from(query),
+        Err(err) => {
+            tracing::error!("Failed to parse request: {}", query);
+
+            let mut response = async_graphql::Response::default();
+            let server_error =
+                ServerError::new(format!("Unexpected GraphQL Request: {}", err), None);
+            response.errors = vec![server_error];
+
+            return GraphQLResponse::from(response).into_response();
+        }
+    };
+
+    if let Some(operation_type) = request.operation_type() {
+        if operation_type != OperationType::Query {
+            let mut response = async_graphql::Response::default();
+            let server_error = ServerError::new(
+                format!("{} operations are only allowed with POST", operation_type),
+                None,
+            );
+            response.errors = vec![server_error];
+
+            let mut response = GraphQLResponse::from(response).into_response()?;
+            *response.status_mut() = StatusCode::METHOD_NOT_ALLOWED;
+            response
+                .headers_mut()
+                .insert(header::ALLOW, HeaderValue::from_static("POST"));
+            return Ok(response);
+        }
+    }
+
+    let request_headers = app_ctx
+        .blueprint
+        .server
+        .enable_etag
+        .then(|| req.headers.clone());
+    let response = execute_query(app_ctx, &req_ctx, request, req).await?;
+    match request_headers {
+        Some(request_headers) => etag::conditional(&request_headers, response).await,
+        None => Ok(response),
+    }
+}
+
 async fn execute_query<T: DeserializeOwned + GraphQLRequestLike>(
     app_ctx: &Arc<AppContext>,
     req_ctx: &Arc<RequestContext>,
-    request: T,
+    mut request: T,
     req: Parts,
 ) -> anyhow::Result<Response<Body>> {
-    let mut response = if app_ctx.blueprint.server.enable_jit {
+    let timer = metrics::Timer::start();
+    let operation_type = if metrics::is_enabled() {
+        request.operation_type()
+    } else {
+        None
+    };
+
+    let (mut response, is_ok) = if app_ctx.blueprint.server.enable_jit {
         let operation_id = request.operation_id(&req.headers);
-        let exec = JITExecutor::new(app_ctx.clone(), req_ctx.clone(), operation_id);
-        request
-            .execute_with_jit(exec)
-            .await
+        let exec =
+            JITExecutor::new(app_ctx.clone(), req_ctx.clone(), operation_id).vary(&req.headers);
+        let response = request.execute_with_jit(exec).await;
+        let is_ok = response.is_ok();
+        let response = response
             .set_cache_control(
                 app_ctx.blueprint.server.enable_cache_control_header,
                 req_ctx.get_min_max_age().unwrap_or(0),
                 req_ctx.is_cache_public().unwrap_or(true),
             )
-            .into_response()?
+            .into_response()?;
+        (response, is_ok)
     } else {
-        request
-            .data(req_ctx.clone())
-            .execute(&app_ctx.schema)
-            .await
+        let mut response = request.data(req_ctx.clone()).execute(&app_ctx.schema).await;
+        if let Some(cache_status) = req_ctx.cache_status() {
+            response = response.set_extension("cacheStatus", cache_status);
+        }
+        if let Some(query_plan) = req_ctx.query_plan.as_ref() {
+            response = response.set_extension("queryPlan", query_plan.to_value(None));
+        }
+        let is_ok = response.0.is_ok();
+        let response = response
             .set_cache_control(
                 app_ctx.blueprint.server.enable_cache_control_header,
                 req_ctx.get_min_max_age().unwrap_or(0),
                 req_ctx.is_cache_public().unwrap_or(true),
             )
-            .into_response()?
+            .into_response()?;
+        (response, is_ok)
     };
 
+    metrics::record_operation(timer, operation_type, !is_ok);
+
     update_response_headers(&mut response, req_ctx, app_ctx);
     Ok(response)
 }
@@ -247,42 +419,63 @@ async fn handle_request_with_cors<T: DeserializeOwned + GraphQLRequestLike>(
 }
 
 async fn handle_rest_apis(
-    mut request: Request<Body>,
-    app_ctx: Arc<AppContext>,
+    request: Request<Body>,
+    p_request: rest::Result<PartialRequest<'_>>,
+    app_ctx: &AppContext,
     req_counter: &mut RequestCounter,
 ) -> Result<Response<Body>> {
-    *request.uri_mut() = request.uri().path().replace(API_URL_PREFIX, "").parse()?;
-    let req_ctx = Arc::new(create_request_context(&request, app_ctx.as_ref()));
-    if let Some(p_request) = app_ctx.endpoints.matches(&request) {
-        let http_route = format!("{API_URL_PREFIX}{}", p_request.path.as_str());
-        req_counter.set_http_route(&http_route);
-        let span = tracing::info_span!(
-            "REST",
-            otel.name = format!("REST {} {}", request.method(), p_request.path.as_str()),
-            otel.kind = ?SpanKind::Server,
-            { HTTP_REQUEST_METHOD } = %request.method(),
-            { HTTP_ROUTE } = http_route
-        );
-        return async {
-            let graphql_request = p_request.into_request(request).await?;
-            let mut response = graphql_request
-                .data(req_ctx.clone())
-                .execute(&app_ctx.schema)
-                .await
-                .set_cache_control(
-                    app_ctx.blueprint.server.enable_cache_control_header,
-                    req_ctx.get_min_max_age().unwrap_or(0),
-                    req_ctx.is_cache_public().unwrap_or(true),
-                )
-                .into_rest_response()?;
-            update_response_headers(&mut response, &req_ctx, &app_ctx);
-            Ok(response)
+    let p_request = match p_request {
+        Ok(p_request) => p_request,
+        Err(err) => return rest_bad_request(err),
+    };
+    let req_ctx = Arc::new(create_request_context(&request, app_ctx));
+    let http_route = format!("{}{}", p_request.prefix, p_request.path.as_str());
+    req_counter.set_http_route(&http_route);
+    let span = tracing::info_span!(
+        "REST",
+        otel.name = format!("REST {} {}", request.method(), p_request.path.as_str()),
+        otel.kind = ?SpanKind::Server,
+        { HTTP_REQUEST_METHOD } = %request.method(),
+        { HTTP_ROUTE } = http_route
+    );
+    async {
+        // the headers are needed for the conditional requests only
+        let request_headers = (app_ctx.blueprint.server.enable_etag
+            && request.method() == Method::GET)
+            .then(|| request.headers().clone());
+        let (status, unwrap) = (p_request.status, p_request.unwrap);
+        let graphql_request = match p_request.into_request(request).await {
+            Ok(graphql_request) => graphql_request,
+            Err(err @ rest::Error::SerdeJson(_)) => return rest_bad_request(err),
+            Err(err) => return Err(err.into()),
+        };
+        let mut response = graphql_request
+            .data(req_ctx.clone())
+            .execute(&app_ctx.schema)
+            .await
+            .set_cache_control(
+                app_ctx.blueprint.server.enable_cache_control_header,
+                req_ctx.get_min_max_age().unwrap_or(0),
+                req_ctx.is_cache_public().unwrap_or(true),
+            )
+            .into_rest_response(status, unwrap)?;
+        update_response_headers(&mut response, &req_ctx, app_ctx);
+        if let Some(request_headers) = request_headers {
+            response = etag::conditional(&request_headers, response).await?;
         }
-        .instrument(span)
-        .await;
+        Ok(response)
     }
+    .instrument(span)
+    .await
+}
 
-    not_found()
+/// Rejects REST requests whose params or body can't be turned into the
+/// variables of the operation.
+fn rest_bad_request(err: rest::Error) -> Result<Response<Body>> {
+    json_response(
+        StatusCode::BAD_REQUEST,
+        json!({ "data": null, "errors": [{ "message": err.to_string() }] }),
+    )
 }
 
 async fn handle_request_inner<T: DeserializeOwned + GraphQLRequestLike>(
@@ -290,8 +483,21 @@ async fn handle_request_inner<T: DeserializeOwned + GraphQLRequestLike>(
     app_ctx: Arc<AppContext>,
     req_counter: &mut RequestCounter,
 ) -> Result<Response<Body>> {
+    if let Some(p_request) = app_ctx.endpoints.matches(&req) {
+        return handle_rest_apis(req, p_request, &app_ctx, req_counter).await;
+    }
+
     if req.uri().path().starts_with(API_URL_PREFIX) {
-        return handle_rest_apis(req, app_ctx, req_counter).await;
+        let openapi_path = format!("{API_URL_PREFIX}{OPENAPI_PATH}");
+        if app_ctx.blueprint.server.enable_openapi
+            && req.method() == Method::GET
+            && req.uri().path() == openapi_path
+        {
+            let openapi = app_ctx.endpoints.to_openapi(&app_ctx.blueprint);
+            return json_response(StatusCode::OK, openapi);
+        }
+
+        return not_found();
     }
 
     let health_check_endpoint = app_ctx.blueprint.server.routes.status();
@@ -316,6 +522,18 @@ async fn handle_request_inner<T: DeserializeOwned + GraphQLRequestLike>(
 
             graphql_request::<T>(req, &Arc::new(app_ctx), req_counter).await
         }
+        Method::POST
+            if app_ctx
+                .blueprint
+                .server
+                .cache_purge
+                .as_ref()
+                .is_some_and(|cache_purge| req.uri().path() == cache_purge.path) =>
+        {
+            // the guard above ensures that the endpoint is configured
+            let cache_purge = app_ctx.blueprint.server.cache_purge.clone().unwrap();
+            handle_cache_purge(req, &app_ctx, &cache_purge).await
+        }
         Method::GET if req.uri().path() == health_check_endpoint => {
             let status_response = Response::builder()
                 .status(StatusCode::OK)
@@ -323,6 +541,9 @@ async fn handle_request_inner<T: DeserializeOwned + GraphQLRequestLike>(
                 .body(Body::from(r#"{"message": "ready"}"#))?;
             Ok(status_response)
         }
+        Method::GET if req.uri().path() == graphql_endpoint => {
+            graphql_get_request(req, &app_ctx, req_counter).await
+        }
         Method::GET => {
             if let Some(TelemetryExporter::Prometheus(prometheus)) =
                 app_ctx.blueprint.telemetry.export.as_ref()
@@ -377,11 +598,12 @@ pub async fn handle_request<T: DeserializeOwned + GraphQLRequestLike>(
 
 #[cfg(test)]
 mod test {
+    use hyper::header::HeaderName;
     use tailcall_valid::Validator;
 
     use super::*;
     use crate::core::async_graphql_hyper::GraphQLRequest;
-    use crate::core::blueprint::Blueprint;
+    use crate::core::blueprint::{Auth, Basic, Blueprint, Provider, QueryPlan};
     use crate::core::config::{Config, ConfigModule, Routes};
     use crate::core::rest::EndpointSet;
     use crate::core::runtime::test::init;
@@ -442,6 +664,137 @@ mod test {
         Ok(())
     }
 
+    async fn get_graphql(query: &str) -> anyhow::Result<Response<Body>> {
+        let sdl = tokio::fs::read_to_string(tailcall_fixtures::configs::JSONPLACEHOLDER).await?;
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(None),
+            EndpointSet::default(),
+        ));
+
+        let query = serde_urlencoded::to_string([("query", query)])?;
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("http://localhost:8000/graphql?{}", query))
+            .body(Body::empty())?;
+
+        handle_request::<GraphQLRequest>(req, app_ctx).await
+    }
+
+    #[tokio::test]
+    async fn test_graphql_get_endpoint() -> anyhow::Result<()> {
+        let resp = get_graphql("{ __schema { queryType { name } } }").await?;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        let body_str = String::from_utf8(body.to_vec())?;
+        assert!(body_str.contains("queryType"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_graphql_get_endpoint_mutation() -> anyhow::Result<()> {
+        let resp = get_graphql("mutation { createUser { id } }").await?;
+
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(resp.headers()[header::ALLOW], "POST");
+
+        Ok(())
+    }
+
+    async fn get_openapi(enable_openapi: bool) -> anyhow::Result<Response<Body>> {
+        let sdl = tokio::fs::read_to_string(tailcall_fixtures::configs::JSONPLACEHOLDER).await?;
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        blueprint.server.enable_openapi = enable_openapi;
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(None),
+            EndpointSet::default(),
+        ));
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("http://localhost:8000/api/openapi.json")
+            .body(Body::empty())?;
+
+        handle_request::<GraphQLRequest>(req, app_ctx).await
+    }
+
+    #[tokio::test]
+    async fn test_openapi_endpoint_disabled() -> anyhow::Result<()> {
+        let resp = get_openapi(false).await?;
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_openapi_endpoint() -> anyhow::Result<()> {
+        let resp = get_openapi(true).await?;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["openapi"], "3.0.3");
+
+        Ok(())
+    }
+
+    async fn traced_graphql(authorization: Option<&str>) -> anyhow::Result<Response<Body>> {
+        let sdl = tokio::fs::read_to_string(tailcall_fixtures::configs::JSONPLACEHOLDER).await?;
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        blueprint.upstream.allowed_headers = BTreeSet::from(["authorization".to_string()]);
+        blueprint.server.query_plan = Some(QueryPlan {
+            header: HeaderName::from_static("x-tailcall-query-plan"),
+            auth: Auth::Provider(Provider::Basic(Basic::test_value())),
+        });
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(None),
+            EndpointSet::default(),
+        ));
+
+        let mut req = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost:8000/graphql")
+            .header("Content-Type", "application/json")
+            .header("x-tailcall-query-plan", "true");
+        if let Some(authorization) = authorization {
+            req = req.header(header::AUTHORIZATION, authorization);
+        }
+        let req = req.body(Body::from(r#"{"query": "{ __typename }"}"#))?;
+
+        handle_request::<GraphQLRequest>(req, app_ctx).await
+    }
+
+    #[tokio::test]
+    async fn test_query_plan() -> anyhow::Result<()> {
+        // testuser1:password123
+        let resp = traced_graphql(Some("Basic dGVzdHVzZXIxOnBhc3N3b3JkMTIz")).await?;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["extensions"]["queryPlan"]["calls"], json!([]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_plan_unauthorized() -> anyhow::Result<()> {
+        let resp = traced_graphql(None).await?;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+
     #[test]
     fn test_create_allowed_headers() {
         use std::collections::BTreeSet;
@@ -462,4 +815,111 @@ mod test {
         assert_eq!(new_headers.get("x-foo").unwrap(), "bar");
         assert_eq!(new_headers.get("x-bar").unwrap(), "foo");
     }
+
+    const RESPONSE_CACHE_CONFIG: &str = r#"
+        schema
+          @server(enableJIT: true, responseCache: {})
+          @upstream(allowedHeaders: ["authorization"]) {
+          query: Query
+          mutation: Mutation
+        }
+
+        type Query {
+          me: Me @http(url: "http://upstream.local/me") @cache(maxAge: 60000, tags: ["me"])
+        }
+
+        type Mutation {
+          updateMe: Me @http(method: POST, url: "http://upstream.local/me") @invalidates(tags: ["me"])
+        }
+
+        type Me {
+          user: String
+          hit: Int
+        }
+    "#;
+
+    /// Answers with the `Authorization` header of the request and the number
+    /// of requests received.
+    struct EchoUpstream(std::sync::atomic::AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl crate::core::HttpIO for EchoUpstream {
+        async fn execute(
+            &self,
+            request: reqwest::Request,
+        ) -> anyhow::Result<crate::core::http::Response<hyper::body::Bytes>> {
+            let hit = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            let user = request
+                .headers()
+                .get("authorization")
+                .and_then(|value| value.to_str().ok());
+            let body = json!({"user": user, "hit": hit});
+
+            Ok(crate::core::http::Response::default()
+                .body(hyper::body::Bytes::from(body.to_string())))
+        }
+    }
+
+    fn response_cache_app() -> anyhow::Result<Arc<AppContext>> {
+        let config = Config::from_sdl(RESPONSE_CACHE_CONFIG).to_result()?;
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        let mut runtime = init(None);
+        runtime.http = Arc::new(EchoUpstream(Default::default()));
+
+        Ok(Arc::new(AppContext::new(
+            blueprint,
+            runtime,
+            EndpointSet::default(),
+        )))
+    }
+
+    async fn post_graphql(
+        app_ctx: &Arc<AppContext>,
+        query: &str,
+        authorization: &str,
+    ) -> anyhow::Result<serde_json::Value> {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost:8000/graphql")
+            .header("Content-Type", "application/json")
+            .header("Authorization", authorization)
+            .body(Body::from(json!({ "query": query }).to_string()))?;
+
+        let resp = handle_request::<GraphQLRequest>(req, app_ctx.clone()).await?;
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_varies_by_forwarded_headers() -> anyhow::Result<()> {
+        let app_ctx = response_cache_app()?;
+        let query = "{ me { user hit } }";
+
+        let a = post_graphql(&app_ctx, query, "Bearer a").await?;
+        let a_again = post_graphql(&app_ctx, query, "Bearer a").await?;
+        let b = post_graphql(&app_ctx, query, "Bearer b").await?;
+
+        assert_eq!(a["data"]["me"], json!({"user": "Bearer a", "hit": 1}));
+        assert_eq!(a_again, a);
+        assert_eq!(b["data"]["me"], json!({"user": "Bearer b", "hit": 2}));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_purged_by_invalidates() -> anyhow::Result<()> {
+        let app_ctx = response_cache_app()?;
+        let query = "{ me { hit } }";
+
+        let before = post_graphql(&app_ctx, query, "Bearer a").await?;
+        let cached = post_graphql(&app_ctx, query, "Bearer a").await?;
+        post_graphql(&app_ctx, "mutation { updateMe { hit } }", "Bearer a").await?;
+        let after = post_graphql(&app_ctx, query, "Bearer a").await?;
+
+        assert_eq!(before["data"]["me"]["hit"], json!(1));
+        assert_eq!(cached, before);
+        assert_eq!(after["data"]["me"]["hit"], json!(3));
+
+        Ok(())
+    }
 }
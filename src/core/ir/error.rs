@@ -72,21 +72,26 @@ impl From<Error> for Errata {
     }
 }
 
+/// Code set in the extensions of the errors of the requests that failed to
+/// authenticate.
+pub const UNAUTHENTICATED: &str = "UNAUTHENTICATED";
+
 impl ErrorExtensions for Error {
     fn extend(&self) -> ExtensionError {
-        ExtensionError::new(format!("{}", self)).extend_with(|_err, e| {
-            if let Error::GRPC {
+        ExtensionError::new(format!("{}", self)).extend_with(|_err, e| match self {
+            Error::GRPC {
                 grpc_code,
                 grpc_description,
                 grpc_status_message,
                 grpc_status_details,
-            } = self
-            {
+            } => {
                 e.set("grpcCode", *grpc_code);
                 e.set("grpcDescription", grpc_description);
                 e.set("grpcStatusMessage", grpc_status_message);
                 e.set("grpcStatusDetails", grpc_status_details.clone());
             }
+            Error::Auth(_) => e.set("code", UNAUTHENTICATED),
+            _ => {}
         })
     }
 }
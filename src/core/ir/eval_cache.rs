@@ -0,0 +1,479 @@
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroU64;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use async_graphql::Name;
+use async_graphql_value::ConstValue;
+use indexmap::IndexMap;
+use tailcall_hasher::TailcallHasher;
+use tokio::sync::Notify;
+
+use super::eval_io::{eval_io, trace_cached};
+use super::model::{Cache, CacheKey, IoId, Stale, IO};
+use super::{Error, EvalContext, ResolverContextLike};
+use crate::core::http::RequestContext;
+
+/// Keys of the entries of a cache that are being refreshed in the background,
+/// so that the concurrent requests for a stale entry refresh it only once.
+#[derive(Clone, Debug, Default)]
+pub struct Revalidating(Arc<RevalidatingInner>);
+
+#[derive(Debug, Default)]
+struct RevalidatingInner {
+    keys: Mutex<HashSet<IoId>>,
+    idle: Notify,
+}
+
+impl Revalidating {
+    /// Returns `false` if the entry is already being refreshed.
+    fn start(&self, key: IoId) -> bool {
+        self.0.keys.lock().unwrap().insert(key)
+    }
+
+    fn finish(&self, key: &IoId) {
+        let mut keys = self.0.keys.lock().unwrap();
+        keys.remove(key);
+        if keys.is_empty() {
+            self.0.idle.notify_waiters();
+        }
+    }
+
+    /// Waits until none of the entries is being refreshed.
+    #[cfg(test)]
+    async fn idle(&self) {
+        loop {
+            let idle = self.0.idle.notified();
+            if self.0.keys.lock().unwrap().is_empty() {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// Describes how the value of `@cache` was resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+    Stale,
+    StaleIfError,
+}
+
+impl CacheStatus {
+    pub const ALL: [CacheStatus; 4] = [
+        CacheStatus::Hit,
+        CacheStatus::Miss,
+        CacheStatus::Stale,
+        CacheStatus::StaleIfError,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+            CacheStatus::Stale => "stale",
+            CacheStatus::StaleIfError => "staleIfError",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Freshness {
+    Fresh,
+    Revalidate,
+    StaleIfError,
+    Expired,
+}
+
+impl Freshness {
+    fn new(age: u64, max_age: NonZeroU64, stale: &Stale) -> Self {
+        let max_age = max_age.get();
+        let within = |window: Option<NonZeroU64>| {
+            window.is_some_and(|window| age < max_age.saturating_add(window.get()))
+        };
+
+        if age < max_age {
+            Freshness::Fresh
+        } else if within(stale.while_revalidate) {
+            Freshness::Revalidate
+        } else if within(stale.if_error) {
+            Freshness::StaleIfError
+        } else {
+            Freshness::Expired
+        }
+    }
+}
+
+/// Value stored by the caches with a stale mode, along with the time it was
+/// stored at, so that its age can be computed once it's read.
+struct Entry {
+    value: ConstValue,
+    stored_at: i64,
+}
+
+impl Entry {
+    fn new(value: ConstValue) -> Self {
+        Self { value, stored_at: now() }
+    }
+
+    fn age(&self) -> u64 {
+        now().saturating_sub(self.stored_at).max(0) as u64
+    }
+
+    fn from_value(value: ConstValue) -> Option<Self> {
+        let ConstValue::Object(mut entry) = value else {
+            return None;
+        };
+        let stored_at = match entry.get("storedAt") {
+            Some(ConstValue::Number(stored_at)) => stored_at.as_i64()?,
+            _ => return None,
+        };
+        let value = entry.swap_remove("value")?;
+
+        Some(Self { value, stored_at })
+    }
+
+    fn into_value(self) -> ConstValue {
+        let mut entry = IndexMap::new();
+        entry.insert(Name::new("value"), self.value);
+        entry.insert(Name::new("storedAt"), ConstValue::from(self.stored_at));
+        ConstValue::Object(entry)
+    }
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// The entries of the caches with a stale mode are stored under a different
+/// key, because the caches without one store the value as is.
+fn entry_key(key: &IoId) -> IoId {
+    let mut hasher = TailcallHasher::default();
+    key.as_u64().hash(&mut hasher);
+    "stale".hash(&mut hasher);
+    IoId::new(hasher.finish())
+}
+
+fn entry_ttl(max_age: NonZeroU64, stale: &Stale) -> NonZeroU64 {
+    let window = stale
+        .while_revalidate
+        .max(stale.if_error)
+        .map_or(0, NonZeroU64::get);
+    max_age.saturating_add(window)
+}
+
+async fn store(
+    req_ctx: &RequestContext,
+    key: IoId,
+    tags: &[String],
+    value: ConstValue,
+    ttl: NonZeroU64,
+) -> Result<(), Error> {
+    let cache = &req_ctx.runtime.cache;
+    if !tags.is_empty() {
+        cache.tag(&key, tags, ttl).await?;
+    }
+    cache.set(key, value, ttl).await?;
+
+    Ok(())
+}
+
+pub async fn eval_cache<Ctx>(
+    cache: &Cache,
+    ctx: &mut EvalContext<'_, Ctx>,
+) -> Result<ConstValue, Error>
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    let io = cache.io.deref();
+    let Some(key) = io.cache_key(ctx) else {
+        return eval_io(io, ctx).await;
+    };
+    let tags = cache
+        .tags
+        .iter()
+        .map(|tag| tag.render(&*ctx))
+        .collect::<Vec<_>>();
+    ctx.request_ctx.add_cache_tags(&tags);
+
+    if cache.stale.is_empty() {
+        if let Some(value) = ctx.request_ctx.runtime.cache.get(&key).await? {
+            add_cache_status(io, ctx, CacheStatus::Hit);
+            return Ok(value);
+        }
+
+        let value = eval_io(io, ctx).await?;
+        store(ctx.request_ctx, key, &tags, value.clone(), cache.max_age).await?;
+        add_cache_status(io, ctx, CacheStatus::Miss);
+        return Ok(value);
+    }
+
+    let key = entry_key(&key);
+    let entry = ctx
+        .request_ctx
+        .runtime
+        .cache
+        .get(&key)
+        .await?
+        .and_then(Entry::from_value);
+    let freshness = entry.as_ref().map_or(Freshness::Expired, |entry| {
+        Freshness::new(entry.age(), cache.max_age, &cache.stale)
+    });
+
+    match (entry, freshness) {
+        (Some(entry), Freshness::Fresh) => {
+            add_cache_status(io, ctx, CacheStatus::Hit);
+            Ok(entry.value)
+        }
+        (Some(entry), Freshness::Revalidate) => {
+            revalidate(cache, key, tags, ctx);
+            add_cache_status(io, ctx, CacheStatus::Stale);
+            Ok(entry.value)
+        }
+        (entry, freshness) => {
+            let ttl = entry_ttl(cache.max_age, &cache.stale);
+            match eval_io(io, ctx).await {
+                Ok(value) => {
+                    let entry = Entry::new(value.clone()).into_value();
+                    store(ctx.request_ctx, key, &tags, entry, ttl).await?;
+                    add_cache_status(io, ctx, CacheStatus::Miss);
+                    Ok(value)
+                }
+                Err(err) => match entry {
+                    Some(entry) if freshness == Freshness::StaleIfError => {
+                        tracing::warn!("Serving the stale value of the cache: {}", err);
+                        add_cache_status(io, ctx, CacheStatus::StaleIfError);
+                        Ok(entry.value)
+                    }
+                    _ => Err(err),
+                },
+            }
+        }
+    }
+}
+
+/// Records how the value was resolved. The values served by the cache are
+/// traced as well, since they don't call the upstream.
+fn add_cache_status<Ctx>(io: &IO, ctx: &EvalContext<'_, Ctx>, status: CacheStatus)
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    ctx.request_ctx.add_cache_status(status);
+    if status != CacheStatus::Miss {
+        trace_cached(io, ctx, status);
+    }
+}
+
+/// Refreshes the entry in the background. The request isn't available once
+/// the stale value is returned, so the refresh is evaluated with a copy of the
+/// values it depends on.
+fn revalidate<Ctx>(cache: &Cache, key: IoId, tags: Vec<String>, ctx: &EvalContext<'_, Ctx>)
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    if !cache.revalidating.start(key.clone()) {
+        return;
+    }
+
+    let revalidating = cache.revalidating.clone();
+    let io = cache.io.deref().clone();
+    let ttl = entry_ttl(cache.max_age, &cache.stale);
+    let req_ctx = ctx.request_ctx.detached();
+    let graphql_ctx = ctx.to_owned_resolver_context();
+
+    let task = async move {
+        let mut ctx = EvalContext::new(&req_ctx, &graphql_ctx);
+        let result = match eval_io(&io, &mut ctx).await {
+            Ok(value) => {
+                let entry = Entry::new(value).into_value();
+                store(&req_ctx, key.clone(), &tags, entry, ttl).await
+            }
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = result {
+            tracing::warn!("Failed to revalidate the value of the cache: {}", err);
+        }
+        revalidating.finish(&key);
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::spawn(Box::pin(task));
+    #[cfg(target_arch = "wasm32")]
+    async_std::task::spawn_local(Box::pin(task));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use hyper::body::Bytes;
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::http::{RequestTemplate, Response};
+    use crate::core::ir::model::{IO, IR};
+    use crate::core::ir::EmptyResolverContext;
+    use crate::core::HttpIO;
+
+    struct Upstream {
+        hits: AtomicUsize,
+        fail: AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpIO for Upstream {
+        async fn execute(&self, _: reqwest::Request) -> anyhow::Result<Response<Bytes>> {
+            let hit = self.hits.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.fail.load(Ordering::SeqCst) {
+                anyhow::bail!("upstream is unavailable");
+            }
+
+            let body = json!({"hit": hit});
+            Ok(Response::default().body(Bytes::from(body.to_string())))
+        }
+    }
+
+    fn non_zero(value: u64) -> NonZeroU64 {
+        NonZeroU64::new(value).unwrap()
+    }
+
+    fn setup(fail: bool) -> (RequestContext, Arc<Upstream>) {
+        let upstream =
+            Arc::new(Upstream { hits: AtomicUsize::new(0), fail: AtomicBool::new(fail) });
+        let mut runtime = crate::core::runtime::test::init(None);
+        runtime.http = upstream.clone();
+
+        let mut req_ctx = RequestContext::new(runtime);
+        req_ctx.server.enable_cache_status = true;
+
+        (req_ctx, upstream)
+    }
+
+    fn cache(stale: Stale) -> Cache {
+        let io = IO::Http {
+            req_template: RequestTemplate::new("http://upstream.local/users").unwrap(),
+            group_by: None,
+            dl_id: None,
+            is_list: false,
+            dedupe: false,
+            hook: None,
+            auth: None,
+        };
+        match Cache::wrap(non_zero(1000), vec![], stale, IR::IO(io)) {
+            IR::Cache(cache) => cache,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Stores an entry that has been expired for the given duration.
+    async fn store_expired(req_ctx: &RequestContext, cache: &Cache, expired_for: i64) {
+        let ctx = EvalContext::new(req_ctx, &EmptyResolverContext {});
+        let key = entry_key(&cache.io.cache_key(&ctx).unwrap());
+        let entry = Entry {
+            value: ConstValue::from_json(json!({"hit": 0})).unwrap(),
+            stored_at: now() - cache.max_age.get() as i64 - expired_for,
+        };
+        req_ctx
+            .runtime
+            .cache
+            .set(key, entry.into_value(), non_zero(60_000))
+            .await
+            .unwrap();
+    }
+
+    async fn eval(req_ctx: &RequestContext, cache: &Cache) -> Result<ConstValue, Error> {
+        let mut ctx = EvalContext::new(req_ctx, &EmptyResolverContext {});
+        eval_cache(cache, &mut ctx).await
+    }
+
+    #[test]
+    fn test_freshness() {
+        let stale = Stale {
+            while_revalidate: Some(non_zero(100)),
+            if_error: Some(non_zero(500)),
+        };
+        let max_age = non_zero(1000);
+
+        assert_eq!(Freshness::new(999, max_age, &stale), Freshness::Fresh);
+        assert_eq!(Freshness::new(1099, max_age, &stale), Freshness::Revalidate);
+        assert_eq!(
+            Freshness::new(1499, max_age, &stale),
+            Freshness::StaleIfError
+        );
+        assert_eq!(Freshness::new(1500, max_age, &stale), Freshness::Expired);
+        assert_eq!(
+            Freshness::new(1000, max_age, &Stale::default()),
+            Freshness::Expired
+        );
+    }
+
+    #[test]
+    fn test_entry_ttl() {
+        let stale = Stale {
+            while_revalidate: Some(non_zero(100)),
+            if_error: Some(non_zero(500)),
+        };
+
+        assert_eq!(entry_ttl(non_zero(1000), &stale), non_zero(1500));
+        assert_eq!(entry_ttl(non_zero(1000), &Stale::default()), non_zero(1000));
+    }
+
+    #[tokio::test]
+    async fn test_stale_while_revalidate() {
+        let (req_ctx, upstream) = setup(false);
+        let cache = cache(Stale { while_revalidate: Some(non_zero(60_000)), if_error: None });
+        store_expired(&req_ctx, &cache, 10).await;
+
+        let actual = eval(&req_ctx, &cache).await.unwrap();
+        assert_eq!(actual, ConstValue::from_json(json!({"hit": 0})).unwrap());
+
+        cache.revalidating.idle().await;
+        assert_eq!(upstream.hits.load(Ordering::SeqCst), 1);
+
+        let actual = eval(&req_ctx, &cache).await.unwrap();
+        assert_eq!(actual, ConstValue::from_json(json!({"hit": 1})).unwrap());
+        assert_eq!(
+            req_ctx.cache_status(),
+            Some(
+                ConstValue::from_json(json!({"hit": 1, "miss": 0, "stale": 1, "staleIfError": 0}))
+                    .unwrap()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_if_error() {
+        let (req_ctx, upstream) = setup(true);
+        let cache = cache(Stale { while_revalidate: None, if_error: Some(non_zero(60_000)) });
+        store_expired(&req_ctx, &cache, 10).await;
+
+        let actual = eval(&req_ctx, &cache).await.unwrap();
+        assert_eq!(actual, ConstValue::from_json(json!({"hit": 0})).unwrap());
+        assert_eq!(upstream.hits.load(Ordering::SeqCst), 1);
+
+        upstream.fail.store(false, Ordering::SeqCst);
+        let actual = eval(&req_ctx, &cache).await.unwrap();
+        assert_eq!(actual, ConstValue::from_json(json!({"hit": 2})).unwrap());
+        assert_eq!(
+            req_ctx.cache_status(),
+            Some(
+                ConstValue::from_json(json!({"hit": 0, "miss": 1, "stale": 0, "staleIfError": 1}))
+                    .unwrap()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_if_error_expired() {
+        let (req_ctx, _) = setup(true);
+        let cache = cache(Stale { while_revalidate: None, if_error: Some(non_zero(100)) });
+        store_expired(&req_ctx, &cache, 200).await;
+
+        assert!(eval(&req_ctx, &cache).await.is_err());
+    }
+}
@@ -5,7 +5,10 @@ use std::sync::Arc;
 use async_graphql::{ServerError, Value};
 use http::header::HeaderMap;
 
-use super::{GraphQLOperationContext, RelatedFields, ResolverContextLike, SelectionField};
+use super::{
+    GraphQLOperationContext, OwnedResolverContext, RelatedFields, ResolverContextLike,
+    SelectionField,
+};
 use crate::core::document::print_directives;
 use crate::core::http::RequestContext;
 
@@ -56,6 +59,31 @@ impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
         self.graphql_ctx.value()
     }
 
+    pub fn field(&self) -> Option<SelectionField> {
+        self.graphql_ctx.field()
+    }
+
+    /// Copies the value and the arguments of the field, including the
+    /// overridden ones, into a context that doesn't borrow the GraphQL request.
+    pub fn to_owned_resolver_context(&self) -> OwnedResolverContext {
+        let value = match self.graphql_ctx_value.as_ref() {
+            Some(value) => Some(value.as_ref().clone()),
+            None => self.graphql_ctx.value().cloned(),
+        };
+        let args = match self.graphql_ctx_args.as_deref() {
+            Some(Value::Object(args)) => Some(args.clone()),
+            Some(_) => None,
+            None => self.graphql_ctx.args().cloned(),
+        };
+
+        OwnedResolverContext {
+            value,
+            args,
+            field: self.graphql_ctx.field(),
+            is_query: self.graphql_ctx.is_query(),
+        }
+    }
+
     pub fn path_arg<T: AsRef<str>>(&self, path: &[T]) -> Option<Cow<'a, Value>> {
         // TODO: add unit tests for this
         if let Some(args) = self.graphql_ctx_args.as_ref() {
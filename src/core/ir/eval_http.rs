@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
 use async_graphql::from_value;
+use reqwest::header::HeaderMap;
 use reqwest::Request;
 use tailcall_valid::Validator;
 
 use super::model::DataLoaderId;
 use super::request::DynamicRequest;
 use super::{EvalContext, ResolverContextLike};
+use crate::core::blueprint::UpstreamAuth;
 use crate::core::data_loader::{DataLoader, Loader};
 use crate::core::grpc::protobuf::ProtobufOperation;
 use crate::core::grpc::request::execute_grpc_request;
@@ -173,6 +175,18 @@ pub async fn execute_request_with_dl<
         .unwrap_or_default())
 }
 
+/// Adds the credentials required by the upstream to the outgoing request.
+pub async fn authorize<Ctx: ResolverContextLike + Sync>(
+    ctx: &EvalContext<'_, Ctx>,
+    auth: &UpstreamAuth,
+    headers: &mut HeaderMap,
+) -> Result<(), Error> {
+    ctx.request_ctx
+        .oauth2_tokens
+        .authorize(&ctx.request_ctx.runtime, auth, ctx, headers)
+        .await
+}
+
 pub fn set_headers<Ctx: ResolverContextLike>(
     ctx: &EvalContext<'_, Ctx>,
     res: &Response<async_graphql::Value>,
@@ -1,23 +1,27 @@
 use async_graphql_value::ConstValue;
 
 use super::eval_http::{
-    execute_grpc_request_with_dl, execute_raw_grpc_request, execute_raw_request,
+    authorize, execute_grpc_request_with_dl, execute_raw_grpc_request, execute_raw_request,
     execute_request_with_dl, parse_graphql_response, set_headers, EvalHttp, WorkerContext,
 };
 use super::model::{CacheKey, IO};
-use super::{DynamicRequest, EvalContext, ResolverContextLike};
+use super::{CacheStatus, DynamicRequest, EvalContext, ResolverContextLike};
 use crate::core::config::GraphQLOperationType;
 use crate::core::data_loader::DataLoader;
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc;
 use crate::core::grpc::data_loader::GrpcDataLoader;
-use crate::core::http::DataLoaderRequest;
+use crate::core::http::{DataLoaderRequest, IoCall};
 use crate::core::ir::Error;
 
 pub async fn eval_io<Ctx>(io: &IO, ctx: &mut EvalContext<'_, Ctx>) -> Result<ConstValue, Error>
 where
     Ctx: ResolverContextLike + Sync,
 {
+    if let Some(query_plan) = ctx.request_ctx.query_plan.as_ref() {
+        query_plan.add_resolved();
+    }
+
     // Note: Handled the case separately for performance reasons. It avoids cache
     // key generation when it's not required
     let dedupe = io.dedupe();
@@ -44,12 +48,110 @@ async fn eval_io_inner<Ctx>(io: &IO, ctx: &mut EvalContext<'_, Ctx>) -> Result<C
 where
     Ctx: ResolverContextLike + Sync,
 {
+    let Some(query_plan) = ctx.request_ctx.query_plan.clone() else {
+        return execute_io(io, ctx).await;
+    };
+
+    let call = describe_io(io, ctx);
+    let started_at = query_plan.elapsed();
+    let result = execute_io(io, ctx).await;
+    query_plan.add_call(
+        IoCall {
+            error: result.as_ref().err().map(|err| err.to_string()),
+            ..call
+        },
+        started_at,
+    );
+
+    result
+}
+
+/// Records the value served by `@cache` instead of calling the upstream, for
+/// the `queryPlan` extension.
+pub(super) fn trace_cached<Ctx>(io: &IO, ctx: &EvalContext<'_, Ctx>, status: CacheStatus)
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    if let Some(query_plan) = ctx.request_ctx.query_plan.as_ref() {
+        let call = IoCall { cache: Some(status.as_str()), ..describe_io(io, ctx) };
+        query_plan.add_call(call, query_plan.elapsed());
+    }
+}
+
+/// Describes the call made by the IO for the `queryPlan` extension. The
+/// request is rendered again, which is only done while tracing.
+fn describe_io<Ctx>(io: &IO, ctx: &EvalContext<'_, Ctx>) -> IoCall
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    let field = ctx.field().map(|field| field.name().to_string());
+    let batch_queries = ctx.request_ctx.upstream.batch.is_some() && ctx.is_query();
+
     match io {
-        IO::Http { req_template, dl_id, hook, .. } => {
+        IO::Http { req_template, dl_id, .. } => IoCall {
+            field,
+            kind: "http",
+            method: Some(req_template.method.to_string()),
+            url: req_template
+                .to_request(ctx)
+                .ok()
+                .map(|request| request.request().url().to_string()),
+            data_loader: dl_id
+                .filter(|_| ctx.request_ctx.is_batching_enabled())
+                .map(|id| format!("http#{}", id.as_usize())),
+            ..Default::default()
+        },
+        IO::GraphQL { req_template, dl_id, .. } => IoCall {
+            field,
+            kind: "graphQL",
+            method: Some("POST".to_string()),
+            url: Some(req_template.url.clone()),
+            data_loader: dl_id
+                .filter(|_| {
+                    batch_queries
+                        && matches!(req_template.operation_type, GraphQLOperationType::Query)
+                })
+                .map(|id| format!("graphQL#{}", id.as_usize())),
+            ..Default::default()
+        },
+        IO::Grpc { req_template, dl_id, .. } => IoCall {
+            field,
+            kind: "grpc",
+            method: Some(req_template.operation.method.full_name().to_string()),
+            url: req_template
+                .render(ctx)
+                .ok()
+                .map(|rendered| rendered.url.to_string()),
+            data_loader: dl_id
+                .filter(|_| {
+                    batch_queries
+                        && matches!(req_template.operation_type, GraphQLOperationType::Query)
+                })
+                .map(|id| format!("grpc#{}", id.as_usize())),
+            ..Default::default()
+        },
+        IO::Js { name } => IoCall {
+            field,
+            kind: "js",
+            function: Some(name.clone()),
+            ..Default::default()
+        },
+    }
+}
+
+async fn execute_io<Ctx>(io: &IO, ctx: &mut EvalContext<'_, Ctx>) -> Result<ConstValue, Error>
+where
+    Ctx: ResolverContextLike + Sync,
+{
+    match io {
+        IO::Http { req_template, dl_id, hook, auth, .. } => {
             let event_worker = &ctx.request_ctx.runtime.cmd_worker;
             let js_worker = &ctx.request_ctx.runtime.worker;
             let eval_http = EvalHttp::new(ctx, req_template, dl_id);
-            let request = eval_http.init_request()?;
+            let mut request = eval_http.init_request()?;
+            if let Some(auth) = auth {
+                authorize(ctx, auth, request.request_mut().headers_mut()).await?;
+            }
             let response = match (&event_worker, js_worker, hook) {
                 (Some(worker), Some(js_worker), Some(hook)) => {
                     let worker_ctx = WorkerContext::new(worker, js_worker, hook);
@@ -60,8 +162,11 @@ where
 
             Ok(response.body)
         }
-        IO::GraphQL { req_template, field_name, dl_id, .. } => {
-            let req = req_template.to_request(ctx)?;
+        IO::GraphQL { req_template, field_name, dl_id, auth, .. } => {
+            let mut req = req_template.to_request(ctx)?;
+            if let Some(auth) = auth {
+                authorize(ctx, auth, req.headers_mut()).await?;
+            }
             let request = DynamicRequest::new(req);
             let res = if ctx.request_ctx.upstream.batch.is_some()
                 && matches!(req_template.operation_type, GraphQLOperationType::Query)
@@ -76,8 +181,11 @@ where
             set_headers(ctx, &res);
             parse_graphql_response(ctx, res, field_name)
         }
-        IO::Grpc { req_template, dl_id, hook, .. } => {
-            let rendered = req_template.render(ctx)?;
+        IO::Grpc { req_template, dl_id, hook, auth, .. } => {
+            let mut rendered = req_template.render(ctx)?;
+            if let Some(auth) = auth {
+                authorize(ctx, auth, &mut rendered.headers).await?;
+            }
             let worker = &ctx.request_ctx.runtime.worker;
 
             let res = if ctx.request_ctx.upstream.batch.is_some() &&
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::num::NonZeroU64;
+use std::sync::Arc;
 
 use async_graphql::Value;
 use strum_macros::Display;
 
 use super::discriminator::Discriminator;
+use super::eval_cache::Revalidating;
 use super::{EvalContext, ResolverContextLike};
-use crate::core::blueprint::{Auth, DynamicValue};
+use crate::core::blueprint::{Auth, DynamicValue, MockGenerator, UpstreamAuth};
 use crate::core::config::group_by::GroupBy;
 use crate::core::graphql::{self};
+use crate::core::mustache::Mustache;
 use crate::core::worker_hooks::WorkerHooks;
 use crate::core::{grpc, http};
 
@@ -23,6 +26,9 @@ pub enum IR {
     Path(Box<IR>, Vec<String>),
     ContextPath(Vec<String>),
     Protect(Auth, Box<IR>),
+    /// Purges the cache entries with the rendered tags once the inner IR
+    /// succeeds
+    Invalidate(Vec<Mustache>, Box<IR>),
     Map(Map),
     Pipe(Box<IR>, Box<IR>),
     /// Merges the result of multiple IRs together
@@ -32,6 +38,35 @@ pub enum IR {
     Entity(HashMap<String, IR>),
     /// Apollo Federation _service resolver
     Service(String),
+    /// Synthetic data served in mock mode instead of calling the upstream
+    Mock(Mock),
+}
+
+/// Resolver of the fields in mock mode.
+#[derive(Clone, Debug)]
+pub struct Mock {
+    pub generator: Arc<MockGenerator>,
+    /// Path of the field, e.g. `Query.users`.
+    pub path: String,
+    pub type_of: crate::core::Type,
+    /// Key of the value of the field in its parent, for the fields without a
+    /// resolver, which keep the value of the parents that aren't generated.
+    pub key: Option<String>,
+}
+
+impl Mock {
+    pub fn resolve(&self, args: Option<&Value>, parent: Option<&Value>) -> Value {
+        let value = match (self.key.as_deref(), parent) {
+            (Some(key), Some(Value::Object(parent))) => parent.get(key),
+            _ => None,
+        };
+        match value {
+            Some(value) if !value.is_null() => value.clone(),
+            _ => self
+                .generator
+                .generate(&self.path, &self.type_of, args, parent),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -50,6 +85,7 @@ pub enum IO {
         is_list: bool,
         dedupe: bool,
         hook: Option<WorkerHooks>,
+        auth: Option<UpstreamAuth>,
     },
     GraphQL {
         req_template: graphql::RequestTemplate,
@@ -57,6 +93,7 @@ pub enum IO {
         batch: bool,
         dl_id: Option<DataLoaderId>,
         dedupe: bool,
+        auth: Option<UpstreamAuth>,
     },
     Grpc {
         req_template: grpc::RequestTemplate,
@@ -64,6 +101,7 @@ pub enum IO {
         dl_id: Option<DataLoaderId>,
         dedupe: bool,
         hook: Option<WorkerHooks>,
+        auth: Option<UpstreamAuth>,
     },
     Js {
         name: String,
@@ -114,7 +152,26 @@ pub trait CacheKey<Ctx> {
 #[derive(Clone, Debug)]
 pub struct Cache {
     pub max_age: NonZeroU64,
+    pub tags: Vec<Mustache>,
+    pub stale: Stale,
     pub io: Box<IO>,
+    pub revalidating: Revalidating,
+}
+
+/// Durations, in milliseconds, after the expiry of a cached value for which it
+/// can still be served.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stale {
+    /// The value is served while it's refreshed in the background.
+    pub while_revalidate: Option<NonZeroU64>,
+    /// The value is served when refreshing it fails.
+    pub if_error: Option<NonZeroU64>,
+}
+
+impl Stale {
+    pub fn is_empty(&self) -> bool {
+        self.while_revalidate.is_none() && self.if_error.is_none()
+    }
 }
 
 impl Cache {
@@ -122,9 +179,15 @@ impl Cache {
     /// Wraps an expression with the cache primitive.
     /// Performance DFS on the cache on the expression and identifies all the IO
     /// nodes. Then wraps each IO node with the cache primitive.
-    pub fn wrap(max_age: NonZeroU64, expr: IR) -> IR {
+    pub fn wrap(max_age: NonZeroU64, tags: Vec<Mustache>, stale: Stale, expr: IR) -> IR {
         expr.modify(&mut move |expr| match expr {
-            IR::IO(io) => Some(IR::Cache(Cache { max_age, io: Box::new(io.to_owned()) })),
+            IR::IO(io) => Some(IR::Cache(Cache {
+                max_age,
+                tags: tags.clone(),
+                stale,
+                io: Box::new(io.to_owned()),
+                revalidating: Revalidating::default(),
+            })),
             _ => None,
         })
     }
@@ -136,9 +199,10 @@ impl IR {
         match self {
             IR::IO(io) => io_modifier(io),
             IR::Cache(cache) => io_modifier(&mut cache.io),
-            IR::Discriminate(_, ir) | IR::Protect(_, ir) | IR::Path(ir, _) => {
-                ir.modify_io(io_modifier)
-            }
+            IR::Discriminate(_, ir)
+            | IR::Protect(_, ir)
+            | IR::Invalidate(_, ir)
+            | IR::Path(ir, _) => ir.modify_io(io_modifier),
             IR::Pipe(ir1, ir2) => {
                 ir1.modify_io(io_modifier);
                 ir2.modify_io(io_modifier);
@@ -178,15 +242,22 @@ impl IR {
                     IR::ContextPath(path) => IR::ContextPath(path),
                     IR::Dynamic(_) => expr,
                     IR::IO(_) => expr,
-                    IR::Cache(Cache { io, max_age }) => {
+                    IR::Cache(Cache { io, max_age, tags, stale, revalidating }) => {
                         let expr = *IR::IO(*io).modify_box(modifier);
                         match expr {
-                            IR::IO(io) => IR::Cache(Cache { io: Box::new(io), max_age }),
+                            IR::IO(io) => IR::Cache(Cache {
+                                io: Box::new(io),
+                                max_age,
+                                tags,
+                                stale,
+                                revalidating,
+                            }),
                             expr => expr,
                         }
                     }
                     IR::Path(expr, path) => IR::Path(expr.modify_box(modifier), path),
                     IR::Protect(auth, expr) => IR::Protect(auth, expr.modify_box(modifier)),
+                    IR::Invalidate(tags, expr) => IR::Invalidate(tags, expr.modify_box(modifier)),
                     IR::Map(Map { input, map }) => {
                         IR::Map(Map { input: input.modify_box(modifier), map })
                     }
@@ -199,6 +270,7 @@ impl IR {
                             .collect(),
                     ),
                     IR::Service(sdl) => IR::Service(sdl),
+                    IR::Mock(_) => expr,
                     IR::Merge(vec) => {
                         IR::Merge(vec.into_iter().map(|ir| ir.modify(modifier)).collect())
                     }
@@ -36,6 +36,36 @@ impl ResolverContextLike for EmptyResolverContext {
     fn add_error(&self, _: ServerError) {}
 }
 
+/// Resolver context that owns the value and the arguments of the field, so that
+/// it can be used after the GraphQL request is completed.
+#[derive(Clone)]
+pub struct OwnedResolverContext {
+    pub value: Option<Value>,
+    pub args: Option<IndexMap<Name, Value>>,
+    pub field: Option<SelectionField>,
+    pub is_query: bool,
+}
+
+impl ResolverContextLike for OwnedResolverContext {
+    fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    fn args(&self) -> Option<&IndexMap<Name, Value>> {
+        self.args.as_ref()
+    }
+
+    fn field(&self) -> Option<SelectionField> {
+        self.field.clone()
+    }
+
+    fn is_query(&self) -> bool {
+        self.is_query
+    }
+
+    fn add_error(&self, _: ServerError) {}
+}
+
 #[derive(Clone)]
 pub struct ResolverContext<'a> {
     inner: Arc<async_graphql::dynamic::ResolverContext<'a>>,
@@ -69,7 +99,7 @@ impl ResolverContextLike for ResolverContext<'_> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SelectionField {
     name: String,
     args: Vec<(String, String)>,
@@ -12,6 +12,7 @@ use crate::core::ir::TypedValue;
 use crate::core::jit;
 use crate::core::jit::synth::Synth;
 use crate::core::json::{JsonLike, JsonLikeList};
+use crate::core::metrics;
 
 type SharedStore<Output, Error> = Arc<Mutex<Store<Result<Output, Positioned<Error>>>>>;
 
@@ -78,7 +79,7 @@ where
             let ctx = Context::new(field, self.request);
             // TODO: with_args should be called on inside iter_field on any level, not only
             // for root fields
-            self.execute(&ctx).await
+            self.execute(&ctx, self.request.plan().root_name()).await
         }))
         .await;
     }
@@ -93,18 +94,24 @@ where
         // Has to be an Object, we don't do anything while executing if its a Scalar
         join_all(field.iter().map(|child| {
             let ctx = ctx.with_value_and_field(value, child);
-            async move { self.execute(&ctx).await }
+            async move { self.execute(&ctx, field.type_of.name()).await }
         }))
         .await;
 
         Ok(())
     }
 
-    async fn execute<'b>(&'b self, ctx: &'b Context<'b, Input, Output>) -> Result<(), Error> {
+    async fn execute<'b>(
+        &'b self,
+        ctx: &'b Context<'b, Input, Output>,
+        parent_type: &'b str,
+    ) -> Result<(), Error> {
         let field = ctx.field();
 
         if let Some(ir) = &field.ir {
+            let timer = metrics::Timer::start();
             let result = self.ir_exec.execute(ir, ctx).await;
+            metrics::record_resolver(timer, parent_type, &field.name, result.is_err());
 
             if let Ok(value) = &result {
                 self.iter_field(ctx, value).await?;
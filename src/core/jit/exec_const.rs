@@ -96,7 +96,24 @@ impl ConstValueExecutor {
         let store = exe.store().await;
         let synth = Synth::new(&plan, store, vars);
 
-        let resp: Response<serde_json_borrow::Value> = exe.execute(&synth).await;
+        let mut resp: Response<serde_json_borrow::Value> = exe.execute(&synth).await;
+        let cache_status = req_ctx.cache_status();
+        if let Some(cache_status) = cache_status.as_ref() {
+            resp.extensions.push((
+                "cacheStatus".to_string(),
+                serde_json_borrow::Value::clone_from(cache_status),
+            ));
+        }
+        let query_plan = req_ctx
+            .query_plan
+            .as_ref()
+            .map(|query_plan| query_plan.to_value(Some(plan.explain())));
+        if let Some(query_plan) = query_plan.as_ref() {
+            resp.extensions.push((
+                "queryPlan".to_string(),
+                serde_json_borrow::Value::clone_from(query_plan),
+            ));
+        }
 
         if is_introspection_query {
             let async_req = async_graphql::Request::from(request).only_introspection();
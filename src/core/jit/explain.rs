@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use serde_json::{json, Map, Value};
+
+use super::{Field, OperationPlan};
+
+impl<Input: Display> OperationPlan<Input> {
+    /// Describes the plan for the `queryPlan` extension of the response: the
+    /// fields with their resolvers and the flags that decide how the operation
+    /// is executed.
+    pub fn explain(&self) -> Value {
+        let mut plan = Map::new();
+        plan.insert(
+            "operationType".into(),
+            json!(self.operation_type.to_string()),
+        );
+        plan.insert("isDedupe".into(), json!(self.is_dedupe));
+        plan.insert("isConst".into(), json!(self.is_const));
+        plan.insert("isProtected".into(), json!(self.is_protected));
+        if let Some(ttl) = self.min_cache_ttl {
+            plan.insert("minCacheTtl".into(), json!(ttl.get()));
+        }
+        if let Some(ttl) = self.response_cache_ttl {
+            plan.insert("responseCacheTtl".into(), json!(ttl.get()));
+        }
+        if let Some(before) = self.before.as_ref() {
+            plan.insert("before".into(), json!(before.to_string()));
+        }
+        plan.insert(
+            "selection".into(),
+            Value::Array(self.selection.iter().map(explain_field).collect()),
+        );
+
+        Value::Object(plan)
+    }
+}
+
+fn explain_field<Input: Display>(field: &Field<Input>) -> Value {
+    let mut value = Map::new();
+    value.insert("name".into(), json!(field.output_name));
+    if field.name != field.output_name {
+        value.insert("field".into(), json!(field.name));
+    }
+    value.insert("type".into(), json!(format!("{:?}", field.type_of)));
+    if let Some(type_condition) = field.type_condition.as_ref() {
+        value.insert("on".into(), json!(type_condition));
+    }
+    let args = field
+        .args
+        .iter()
+        .filter_map(|arg| Some((arg.name.clone(), json!(arg.value.as_ref()?.to_string()))))
+        .collect::<Map<_, _>>();
+    if !args.is_empty() {
+        value.insert("args".into(), Value::Object(args));
+    }
+    if let Some(ir) = field.ir.as_ref() {
+        value.insert("resolver".into(), json!(ir.to_string()));
+    }
+    if !field.selection.is_empty() {
+        value.insert(
+            "selection".into(),
+            Value::Array(field.selection.iter().map(explain_field).collect()),
+        );
+    }
+
+    Value::Object(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql_value::ConstValue;
+    use serde_json::json;
+    use tailcall_valid::Validator;
+
+    use crate::core::blueprint::Blueprint;
+    use crate::core::config::{Config, ConfigModule};
+    use crate::core::jit::{OperationPlan, Request};
+
+    const CONFIG: &str = include_str!("fixtures/jsonplaceholder-mutation.graphql");
+
+    fn plan(query: &str) -> OperationPlan<async_graphql_value::Value> {
+        let config = ConfigModule::from(Config::from_sdl(CONFIG).to_result().unwrap());
+        let blueprint = Blueprint::try_from(&config).unwrap();
+        Request::<ConstValue>::new(query)
+            .create_plan(&blueprint)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_explain() {
+        let actual = plan("query { posts { id title user { name } } }").explain();
+
+        assert_eq!(actual["operationType"], json!("query"));
+        let posts = &actual["selection"][0];
+        assert_eq!(posts["name"], json!("posts"));
+        assert_eq!(posts["type"], json!("[Post]"));
+        assert_eq!(posts["resolver"], json!("Http"));
+        assert_eq!(posts["selection"][2]["name"], json!("user"));
+        assert!(posts["selection"][2]["resolver"].is_string());
+        assert!(posts["selection"][0].get("resolver").is_none());
+    }
+}
@@ -1,18 +1,23 @@
 use std::collections::BTreeMap;
 use std::future::Future;
 use std::hash::{Hash, Hasher};
+use std::num::NonZeroU64;
 use std::sync::Arc;
 
-use async_graphql::{BatchRequest, Value};
+use async_graphql::{BatchRequest, Name, Value};
 use async_graphql_value::{ConstValue, Extensions};
 use futures_util::stream::FuturesOrdered;
 use futures_util::StreamExt;
+use http::HeaderMap;
+use indexmap::IndexMap;
 use tailcall_hasher::TailcallHasher;
 
-use super::{AnyResponse, BatchResponse, Response};
+use super::response_cache::cache_key;
+use super::{AnyResponse, BatchResponse, OperationPlan, Response};
 use crate::core::app_context::AppContext;
 use crate::core::async_graphql_hyper::OperationId;
 use crate::core::http::RequestContext;
+use crate::core::ir::model::IoId;
 use crate::core::jit::{self, ConstValueExecutor, OPHash, Pos, Positioned};
 
 #[derive(Clone)]
@@ -20,6 +25,8 @@ pub struct JITExecutor {
     app_ctx: Arc<AppContext>,
     req_ctx: Arc<RequestContext>,
     operation_id: OperationId,
+    // Values of the request headers that vary the cached responses.
+    vary: HeaderMap,
 }
 
 impl JITExecutor {
@@ -28,7 +35,20 @@ impl JITExecutor {
         req_ctx: Arc<RequestContext>,
         operation_id: OperationId,
     ) -> Self {
-        Self { app_ctx, req_ctx, operation_id }
+        Self { app_ctx, req_ctx, operation_id, vary: HeaderMap::new() }
+    }
+
+    /// Keeps the values of the request headers that are part of the key of the
+    /// cached responses.
+    pub fn vary(mut self, headers: &HeaderMap) -> Self {
+        if let Some(response_cache) = self.app_ctx.blueprint.server.response_cache.as_ref() {
+            for name in response_cache.vary.iter() {
+                for value in headers.get_all(name) {
+                    self.vary.append(name.clone(), value.clone());
+                }
+            }
+        }
+        self
     }
 
     #[inline(always)]
@@ -61,6 +81,103 @@ impl JITExecutor {
         out.unwrap_or_default()
     }
 
+    /// Returns the key and the time to live of the cached response, if the
+    /// response of the operation can be cached. The cache is bypassed while the
+    /// `cacheStatus` extension is enabled or the request is traced, so that
+    /// they describe the execution of each request. The headers forwarded to
+    /// the upstream are part of the key, so that the response of a user is
+    /// never served to another.
+    fn response_cache_key(
+        &self,
+        plan: &OperationPlan<async_graphql_value::Value>,
+        request: &jit::Request<ConstValue>,
+    ) -> Option<(IoId, NonZeroU64)> {
+        let server = &self.app_ctx.blueprint.server;
+        if server.response_cache.is_none() || server.enable_cache_status || self.is_traced() {
+            return None;
+        }
+
+        let ttl = plan.response_cache_ttl?;
+        let mut headers = self.vary.clone();
+        for (name, value) in self.req_ctx.allowed_headers.iter() {
+            headers.append(name.clone(), value.clone());
+        }
+
+        Some((cache_key(plan, &request.variables, &headers), ttl))
+    }
+
+    async fn get_cached_response(&self, key: &IoId) -> Option<AnyResponse<Vec<u8>>> {
+        let value = match self.app_ctx.runtime.cache.get(key).await {
+            Ok(value) => value?,
+            Err(err) => {
+                tracing::warn!("Failed to read the cached response: {}", err);
+                return None;
+            }
+        };
+
+        let ConstValue::Object(value) = value else {
+            return None;
+        };
+        let Some(ConstValue::String(body)) = value.get("body") else {
+            return None;
+        };
+        if let Some(ConstValue::Number(max_age)) = value.get("maxAge") {
+            if let Some(max_age) = max_age.as_i64() {
+                self.req_ctx.set_min_max_age(max_age as i32);
+            }
+        }
+        if let Some(ConstValue::Boolean(false)) = value.get("public") {
+            self.req_ctx.set_cache_public_false();
+        }
+
+        Some(AnyResponse {
+            body: Arc::new(body.clone().into_bytes()),
+            ..Default::default()
+        })
+    }
+
+    async fn set_cached_response(
+        &self,
+        key: IoId,
+        response: &AnyResponse<Vec<u8>>,
+        ttl: NonZeroU64,
+    ) {
+        let Ok(body) = String::from_utf8(response.body.as_ref().clone()) else {
+            return;
+        };
+
+        let mut value = IndexMap::new();
+        value.insert(Name::new("body"), ConstValue::String(body));
+        if let Some(max_age) = self.req_ctx.get_min_max_age() {
+            value.insert(Name::new("maxAge"), ConstValue::from(max_age));
+        }
+        if let Some(public) = self.req_ctx.is_cache_public() {
+            value.insert(Name::new("public"), ConstValue::Boolean(public));
+        }
+
+        // the response is tagged with the tags of its cached values, so that
+        // `@invalidates` and the cache purge endpoint purge it along with them.
+        let cache = &self.app_ctx.runtime.cache;
+        let tags = self.req_ctx.cache_tags();
+        let result = async {
+            if !tags.is_empty() {
+                cache.tag(&key, &tags, ttl).await?;
+            }
+            cache.set(key, ConstValue::Object(value), ttl).await
+        }
+        .await;
+        if let Err(err) = result {
+            tracing::warn!("Failed to cache the response: {}", err);
+        }
+    }
+
+    /// Traced requests are always executed, instead of sharing the response
+    /// of another request: the const, dedupe and response caches are bypassed
+    /// so that the `queryPlan` extension describes their own execution.
+    fn is_traced(&self) -> bool {
+        self.req_ctx.query_plan.is_some()
+    }
+
     #[inline(always)]
     fn req_hash(request: &async_graphql::Request) -> OPHash {
         let mut hasher = TailcallHasher::default();
@@ -78,8 +195,10 @@ impl JITExecutor {
         let hash = Self::req_hash(&request);
 
         async move {
-            if let Some(response) = self.app_ctx.const_execution_cache.get(&hash) {
-                return response.clone();
+            if !self.is_traced() {
+                if let Some(response) = self.app_ctx.const_execution_cache.get(&hash) {
+                    return response.clone();
+                }
             }
 
             let jit_request = jit::Request::from(request);
@@ -103,14 +222,27 @@ impl JITExecutor {
             let is_const = exec.plan.is_const;
             let is_protected = exec.plan.is_protected;
 
-            let response = if exec.plan.can_dedupe() {
+            let response_cache_key = self.response_cache_key(&exec.plan, &jit_request);
+            if let Some((key, _)) = response_cache_key.as_ref() {
+                if let Some(response) = self.get_cached_response(key).await {
+                    return response;
+                }
+            }
+
+            let response = if exec.plan.can_dedupe() && !self.is_traced() {
                 self.dedupe_and_exec(exec, jit_request).await
             } else {
                 self.exec(exec, jit_request).await
             };
 
+            if let Some((key, ttl)) = response_cache_key {
+                if response.is_ok {
+                    self.set_cached_response(key, &response, ttl).await;
+                }
+            }
+
             // Cache the response if it's constant and not wrapped with protected.
-            if is_const && !is_protected {
+            if is_const && !is_protected && !self.is_traced() {
                 self.app_ctx
                     .const_execution_cache
                     .insert(hash, response.clone());
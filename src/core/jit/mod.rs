@@ -10,8 +10,10 @@ use store::*;
 mod context;
 mod error;
 mod exec_const;
+mod explain;
 mod request;
 mod response;
+mod response_cache;
 
 // NOTE: Only used in tests and benchmarks
 mod builder;
@@ -44,6 +44,10 @@ impl<Value> Variables<Value> {
         self.0
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.0.iter()
+    }
+
     pub fn insert(&mut self, key: String, value: Value) {
         self.0.insert(key, value);
     }
@@ -313,6 +317,9 @@ pub struct OperationPlan<Input> {
     pub is_const: bool,
     pub is_protected: bool,
     pub min_cache_ttl: Option<NonZeroU64>,
+    /// Time to live of the whole response, if it can be cached. See
+    /// [super::transform::CheckResponseCache].
+    pub response_cache_ttl: Option<NonZeroU64>,
     pub selection: Vec<Field<Input>>,
     pub before: Option<IR>,
 }
@@ -338,6 +345,7 @@ impl<Input> OperationPlan<Input> {
             is_const: self.is_const,
             is_protected: self.is_protected,
             min_cache_ttl: self.min_cache_ttl,
+            response_cache_ttl: self.response_cache_ttl,
             before: self.before,
         })
     }
@@ -365,6 +373,7 @@ impl<Input> OperationPlan<Input> {
             is_const: false,
             is_protected: false,
             min_cache_ttl: None,
+            response_cache_ttl: None,
             before: Default::default(),
         }
     }
@@ -50,6 +50,7 @@ impl Request<ConstValue> {
             .pipe(transform::AuthPlanner::new())
             .pipe(transform::CheckDedupe::new())
             .pipe(transform::CheckCache::new())
+            .pipe(transform::CheckResponseCache::new())
             .pipe(transform::GraphQL::new())
             .transform(plan)
             .to_result()
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use async_graphql_value::ConstValue;
+use http::HeaderMap;
+use tailcall_hasher::TailcallHasher;
+
+use super::{OperationPlan, Variables};
+use crate::core::ir::model::IoId;
+
+/// Computes the key of the cached response of the operation. The document is
+/// normalized by hashing the plan instead of the query, so that the queries
+/// that differ only in formatting, comments or unused fragments share the
+/// response. The headers are the ones the response depends on: the `vary`
+/// headers and the headers forwarded to the upstream, e.g. `Authorization`.
+pub fn cache_key<Input: Display>(
+    plan: &OperationPlan<Input>,
+    variables: &Variables<ConstValue>,
+    headers: &HeaderMap,
+) -> IoId {
+    let mut hasher = TailcallHasher::default();
+    let state = &mut hasher;
+
+    "response".hash(state);
+    plan.root_name.hash(state);
+    for field in plan.iter_dfs() {
+        field.name.hash(state);
+        field.output_name.hash(state);
+        field.type_condition.hash(state);
+        field.skip.as_ref().map(|skip| skip.as_str()).hash(state);
+        field
+            .include
+            .as_ref()
+            .map(|include| include.as_str())
+            .hash(state);
+        for arg in field.args.iter() {
+            arg.to_string().hash(state);
+        }
+        for directive in field.directives.iter() {
+            directive.name.hash(state);
+            for (name, value) in directive.arguments.iter() {
+                name.hash(state);
+                value.to_string().hash(state);
+            }
+        }
+        // keeps the nesting of the fields in the hash
+        field.selection.len().hash(state);
+    }
+
+    let variables = variables.iter().collect::<BTreeMap<_, _>>();
+    for (name, value) in variables {
+        name.hash(state);
+        value.to_string().hash(state);
+    }
+
+    // the order of the headers depends on the client
+    let mut headers = headers
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_bytes()))
+        .collect::<Vec<_>>();
+    headers.sort();
+    for (name, value) in headers {
+        name.hash(state);
+        value.hash(state);
+    }
+
+    IoId::new(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql_value::ConstValue;
+    use http::HeaderValue;
+
+    use super::*;
+    use crate::core::blueprint::Blueprint;
+    use crate::core::config::{Config, ConfigModule};
+    use crate::core::jit::Request;
+
+    const CONFIG: &str = include_str!("./fixtures/jsonplaceholder-mutation.graphql");
+
+    fn key(query: &str, variables: &[(&str, ConstValue)], vary: &HeaderMap) -> IoId {
+        let config = Config::from_sdl(CONFIG).to_result().unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+        let request = Request::<ConstValue>::new(query);
+        let plan = request.create_plan(&blueprint).unwrap();
+        let variables = variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+
+        cache_key(&plan, &variables, vary)
+    }
+
+    #[test]
+    fn test_formatting_is_normalized() {
+        let vary = HeaderMap::new();
+        let a = key("query { users { id name } }", &[], &vary);
+        let b = key(
+            "# all the users\nquery {\n  users {\n    id\n    name\n  }\n}",
+            &[],
+            &vary,
+        );
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_selection_and_variables_are_part_of_key() {
+        let vary = HeaderMap::new();
+        let query = "query($id: ID!) { user(id: $id) { id } }";
+        let a = key(query, &[("id", ConstValue::from(1))], &vary);
+        let b = key(query, &[("id", ConstValue::from(2))], &vary);
+        let c = key("query { users { id } }", &[], &vary);
+        let d = key("query { users { name } }", &[], &vary);
+
+        assert_ne!(a, b);
+        assert_ne!(c, d);
+    }
+
+    #[test]
+    fn test_vary_headers_are_part_of_key() {
+        let query = "query { users { id } }";
+        let mut en = HeaderMap::new();
+        en.insert("accept-language", HeaderValue::from_static("en"));
+        let mut fr = HeaderMap::new();
+        fr.insert("accept-language", HeaderValue::from_static("fr"));
+
+        assert_ne!(key(query, &[], &en), key(query, &[], &fr));
+    }
+
+    #[test]
+    fn test_headers_order_is_normalized() {
+        let query = "query { users { id } }";
+        let mut a = HeaderMap::new();
+        a.insert("authorization", HeaderValue::from_static("Bearer a"));
+        a.insert("accept-language", HeaderValue::from_static("en"));
+        let mut b = HeaderMap::new();
+        b.insert("accept-language", HeaderValue::from_static("en"));
+        b.insert("authorization", HeaderValue::from_static("Bearer a"));
+
+        assert_eq!(key(query, &[], &a), key(query, &[], &b));
+    }
+}
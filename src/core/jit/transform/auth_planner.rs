@@ -61,7 +61,8 @@ pub fn update_ir(ir: &mut IR, vec: &mut Vec<Auth>) {
         | IR::ContextPath(_)
         | IR::Map(_)
         | IR::Entity(_)
-        | IR::Service(_) => {}
+        | IR::Service(_)
+        | IR::Mock(_) => {}
         IR::Path(ir, _) => {
             update_ir(ir, vec);
         }
@@ -75,7 +76,7 @@ pub fn update_ir(ir: &mut IR, vec: &mut Vec<Auth>) {
             update_ir(ir1, vec);
             update_ir(ir2, vec);
         }
-        IR::Discriminate(_, ir) => {
+        IR::Discriminate(_, ir) | IR::Invalidate(_, ir) => {
             update_ir(ir, vec);
         }
         IR::Merge(irs) => {
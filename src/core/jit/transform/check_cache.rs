@@ -16,13 +16,15 @@ impl<A> CheckCache<A> {
     }
 }
 
+/// Returns the least `maxAge` of the IR, if all of its IO is cached.
 #[inline]
-fn check_cache(ir: &IR) -> Option<NonZeroU64> {
+pub fn check_cache(ir: &IR) -> Option<NonZeroU64> {
     match ir {
         IR::IO(_) => None,
         IR::Cache(cache) => Some(cache.max_age),
         IR::Path(ir, _) => check_cache(ir),
         IR::Protect(_, ir) => check_cache(ir),
+        IR::Invalidate(_, ir) => check_cache(ir),
         IR::Pipe(ir, ir1) => match (check_cache(ir), check_cache(ir1)) {
             (Some(age1), Some(age2)) => Some(age1.min(age2)),
             _ => None,
@@ -30,7 +32,7 @@ fn check_cache(ir: &IR) -> Option<NonZeroU64> {
         IR::Merge(vec) => vec.iter().map(check_cache).min().unwrap_or_default(),
         IR::Discriminate(_, ir) => check_cache(ir),
         IR::Entity(hash_map) => hash_map.values().map(check_cache).min().unwrap_or_default(),
-        IR::Dynamic(_) | IR::ContextPath(_) | IR::Map(_) | IR::Service(_) => None,
+        IR::Dynamic(_) | IR::ContextPath(_) | IR::Map(_) | IR::Service(_) | IR::Mock(_) => None,
     }
 }
 
@@ -23,12 +23,14 @@ pub fn is_const(ir: &IR) -> bool {
         IR::Path(ir, _) => is_const(ir),
         IR::ContextPath(_) => false,
         IR::Protect(_, ir) => is_const(ir),
+        IR::Invalidate(_, _) => false,
         IR::Map(map) => is_const(&map.input),
         IR::Pipe(ir, ir1) => is_const(ir) && is_const(ir1),
         IR::Merge(vec) => vec.iter().all(is_const),
         IR::Discriminate(_, ir) => is_const(ir),
         IR::Entity(hash_map) => hash_map.values().all(is_const),
         IR::Service(_) => true,
+        IR::Mock(_) => false,
     }
 }
 
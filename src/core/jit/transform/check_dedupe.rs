@@ -20,6 +20,7 @@ fn check_dedupe(ir: &IR) -> bool {
         IR::Cache(cache) => cache.io.dedupe(),
         IR::Path(ir, _) => check_dedupe(ir),
         IR::Protect(_, ir) => check_dedupe(ir),
+        IR::Invalidate(_, ir) => check_dedupe(ir),
         IR::Pipe(ir, ir1) => check_dedupe(ir) && check_dedupe(ir1),
         IR::Merge(vec) => vec.iter().all(check_dedupe),
         IR::Discriminate(_, ir) => check_dedupe(ir),
@@ -28,6 +29,7 @@ fn check_dedupe(ir: &IR) -> bool {
         IR::ContextPath(_) => true,
         IR::Map(_) => true,
         IR::Service(_) => true,
+        IR::Mock(_) => true,
     }
 }
 
@@ -23,12 +23,14 @@ pub fn is_protected(ir: &IR) -> bool {
         IR::Path(ir, _) => is_protected(ir),
         IR::ContextPath(_) => false,
         IR::Protect(_, _) => true,
+        IR::Invalidate(_, ir) => is_protected(ir),
         IR::Map(map) => is_protected(&map.input),
         IR::Pipe(ir, ir1) => is_protected(ir) || is_protected(ir1),
         IR::Merge(vec) => vec.iter().all(is_protected),
         IR::Discriminate(_, ir) => is_protected(ir),
         IR::Entity(hash_map) => hash_map.values().any(is_protected),
         IR::Service(_) => false,
+        IR::Mock(_) => false,
     }
 }
 
@@ -0,0 +1,52 @@
+use std::convert::Infallible;
+use std::marker::PhantomData;
+use std::num::NonZeroU64;
+
+use tailcall_valid::Valid;
+
+use super::{check_cache, is_const};
+use crate::core::jit::OperationPlan;
+use crate::core::Transform;
+
+/// A transformer that sets the time to live of the whole response. The
+/// response can be cached only for the queries whose resolvers are all either
+/// constant or cached, and that don't have any `@protected` field. It must
+/// run after [super::AuthPlanner], which moves the protection of the fields
+/// into `plan.before`.
+pub struct CheckResponseCache<A>(PhantomData<A>);
+impl<A> CheckResponseCache<A> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A> Transform for CheckResponseCache<A> {
+    type Value = OperationPlan<A>;
+    type Error = Infallible;
+
+    fn transform(&self, mut plan: Self::Value) -> Valid<Self::Value, Self::Error> {
+        if !plan.is_query() || plan.is_introspection_query || plan.before.is_some() {
+            plan.response_cache_ttl = None;
+            return Valid::succeed(plan);
+        }
+
+        let mut ttl: Option<NonZeroU64> = None;
+        for ir in plan.iter_dfs().filter_map(|field| field.ir.as_ref()) {
+            if is_const(ir) {
+                continue;
+            }
+
+            match check_cache(ir) {
+                Some(max_age) => ttl = Some(ttl.map_or(max_age, |ttl| ttl.min(max_age))),
+                None => {
+                    ttl = None;
+                    break;
+                }
+            }
+        }
+
+        plan.response_cache_ttl = ttl;
+
+        Valid::succeed(plan)
+    }
+}
@@ -3,6 +3,7 @@ mod check_cache;
 mod check_const;
 mod check_dedupe;
 mod check_protected;
+mod check_response_cache;
 mod graphql;
 mod input_resolver;
 mod skip;
@@ -12,6 +13,7 @@ pub use check_cache::*;
 pub use check_const::*;
 pub use check_dedupe::*;
 pub use check_protected::*;
+pub use check_response_cache::*;
 pub use graphql::*;
 pub use input_resolver::*;
 pub use skip::*;
@@ -0,0 +1,174 @@
+//! Metrics that are recorded while executing the GraphQL requests. They are
+//! exported with the meter provider configured by `@telemetry`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use async_graphql::parser::types::OperationType;
+use once_cell::sync::Lazy;
+use opentelemetry::metrics::{Counter, Histogram, Unit};
+use opentelemetry::KeyValue;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static GRAPHQL_OPERATION_DURATION: Lazy<Histogram<f64>> = Lazy::new(|| {
+    opentelemetry::global::meter("graphql")
+        .f64_histogram("graphql.operation.duration")
+        .with_description("Duration of the GraphQL operations")
+        .with_unit(Unit::new("s"))
+        .init()
+});
+
+static GRAPHQL_RESOLVER_DURATION: Lazy<Histogram<f64>> = Lazy::new(|| {
+    opentelemetry::global::meter("graphql")
+        .f64_histogram("graphql.resolver.duration")
+        .with_description("Duration of the field resolvers")
+        .with_unit(Unit::new("s"))
+        .init()
+});
+
+static DATALOADER_BATCH_SIZE: Lazy<Histogram<u64>> = Lazy::new(|| {
+    opentelemetry::global::meter("dataloader")
+        .u64_histogram("dataloader.batch.size")
+        .with_description("Number of keys loaded in a single batch")
+        .init()
+});
+
+static DEDUPE_HIT_COUNT: Lazy<Counter<u64>> = Lazy::new(|| {
+    opentelemetry::global::meter("dataloader")
+        .u64_counter("dedupe.hit.count")
+        .with_description("Number of operations served by an in-flight or completed duplicate")
+        .init()
+});
+
+/// Enables recording of the metrics. It's called once the meter provider is
+/// set, so that the hot paths don't pay for the metrics that aren't exported.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Measures the time elapsed since it was started. The clock is read only
+/// when the metrics are enabled, because `Instant` isn't available on every
+/// target that runs the server.
+pub struct Timer(Option<Instant>);
+
+impl Timer {
+    pub fn start() -> Self {
+        Self(is_enabled().then(Instant::now))
+    }
+
+    fn elapsed(&self) -> Option<f64> {
+        self.0.map(|started_at| started_at.elapsed().as_secs_f64())
+    }
+}
+
+/// Records the duration of the operation. The operations aren't labelled with
+/// their name, which is set by the clients, so that they can't create an
+/// unbounded number of series.
+pub fn record_operation(timer: Timer, operation_type: Option<OperationType>, is_error: bool) {
+    let Some(duration) = timer.elapsed() else {
+        return;
+    };
+
+    GRAPHQL_OPERATION_DURATION.record(duration, &operation_attributes(operation_type, is_error));
+}
+
+fn operation_attributes(operation_type: Option<OperationType>, is_error: bool) -> [KeyValue; 2] {
+    [
+        KeyValue::new(
+            "graphql.operation.type",
+            operation_type.map(|ty| ty.to_string()).unwrap_or_default(),
+        ),
+        KeyValue::new("error", is_error),
+    ]
+}
+
+/// Records the duration of the resolver. The type and field names come from
+/// the schema, so their number is bounded.
+pub fn record_resolver(timer: Timer, type_name: &str, field_name: &str, is_error: bool) {
+    let Some(duration) = timer.elapsed() else {
+        return;
+    };
+
+    GRAPHQL_RESOLVER_DURATION.record(
+        duration,
+        &resolver_attributes(type_name, field_name, is_error),
+    );
+}
+
+fn resolver_attributes(type_name: &str, field_name: &str, is_error: bool) -> [KeyValue; 3] {
+    [
+        KeyValue::new("graphql.type.name", type_name.to_string()),
+        KeyValue::new("graphql.field.name", field_name.to_string()),
+        KeyValue::new("error", is_error),
+    ]
+}
+
+pub fn record_batch_size(loader: &'static str, size: usize) {
+    if !is_enabled() {
+        return;
+    }
+
+    DATALOADER_BATCH_SIZE.record(size as u64, &[KeyValue::new("dataloader.name", loader)]);
+}
+
+pub fn record_dedupe_hit() {
+    if !is_enabled() {
+        return;
+    }
+
+    DEDUPE_HIT_COUNT.add(1, &[]);
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::{Key, Value};
+
+    use super::*;
+
+    fn keys(attributes: &[KeyValue]) -> Vec<&str> {
+        attributes.iter().map(|kv| kv.key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_timer_disabled() {
+        // the metrics are only enabled by the cli, once the exporter is set
+        assert!(!is_enabled());
+        assert_eq!(Timer::start().elapsed(), None);
+    }
+
+    #[test]
+    fn test_operation_attributes() {
+        let attributes = operation_attributes(Some(OperationType::Mutation), true);
+
+        assert_eq!(keys(&attributes), ["graphql.operation.type", "error"]);
+        assert_eq!(attributes[0].value, Value::from("mutation"));
+        assert_eq!(attributes[1].value, Value::from(true));
+    }
+
+    #[test]
+    fn test_operation_attributes_without_type() {
+        let attributes = operation_attributes(None, false);
+
+        assert_eq!(attributes[0].value, Value::from(""));
+        assert!(!attributes
+            .iter()
+            .any(|kv| kv.key == Key::from_static_str("graphql.operation.name")));
+    }
+
+    #[test]
+    fn test_resolver_attributes() {
+        let attributes = resolver_attributes("User", "posts", false);
+
+        assert_eq!(
+            keys(&attributes),
+            ["graphql.type.name", "graphql.field.name", "error"]
+        );
+        assert_eq!(attributes[0].value, Value::from("User"));
+        assert_eq!(attributes[1].value, Value::from("posts"));
+    }
+}
@@ -6,6 +6,7 @@ pub mod async_graphql_hyper;
 mod auth;
 pub mod blueprint;
 pub mod cache;
+pub mod codegen;
 pub mod config;
 mod counter;
 pub mod data_loader;
@@ -25,6 +26,7 @@ pub mod jit;
 pub mod json;
 mod lift;
 pub mod merge_right;
+pub mod metrics;
 pub mod mustache;
 pub mod path;
 pub mod primitive;
@@ -94,6 +96,21 @@ pub trait Cache: Send + Sync {
         ttl: NonZeroU64,
     ) -> Result<(), cache::Error>;
     async fn get<'a>(&'a self, key: &'a Self::Key) -> Result<Option<Self::Value>, cache::Error>;
+    async fn delete<'a>(&'a self, key: &'a Self::Key) -> Result<(), cache::Error>;
+
+    /// Associates the tags with the entry, so that it's removed once any of
+    /// the tags is purged. The association expires along with the entry,
+    /// after the `ttl` of the entry.
+    async fn tag<'a>(
+        &'a self,
+        key: &'a Self::Key,
+        tags: &'a [String],
+        ttl: NonZeroU64,
+    ) -> Result<(), cache::Error>;
+
+    /// Removes all the entries associated with the tag and returns the number
+    /// of removed entries.
+    async fn purge_by_tag<'a>(&'a self, tag: &'a str) -> Result<usize, cache::Error>;
 
     fn hit_rate(&self) -> Option<f64>;
 }
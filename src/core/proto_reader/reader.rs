@@ -95,6 +95,35 @@ impl ProtoReader {
         Ok(metadata)
     }
 
+    /// Returns the paths of the local files a proto file is read from, i.e. the
+    /// file itself and the files it imports, directly or not.
+    pub async fn local_paths<T: AsRef<str>>(
+        &self,
+        path: T,
+        proto_paths: Option<&[String]>,
+    ) -> anyhow::Result<Vec<String>> {
+        let metadata = self.read(path.as_ref(), proto_paths).await?;
+        let parent_dir = Path::new(path.as_ref()).parent();
+        let proto_paths =
+            proto_paths.map(|paths| paths.iter().map(PathBuf::from).collect::<Vec<_>>());
+
+        // the file itself comes last and is named after the path it was read from
+        let mut files = metadata.descriptor_set.file;
+        files.pop();
+
+        let mut paths = vec![path.as_ref().to_string()];
+        for file in files {
+            if GoogleFileResolver::new().open_file(file.name()).is_ok() {
+                continue;
+            }
+            let path = Self::resolve_path(file.name(), parent_dir, proto_paths.as_deref());
+            if !path.starts_with("http") {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
     /// Used as a helper file to resolve dependencies proto files
     async fn resolve_dependencies<F>(
         &self,
@@ -276,6 +305,24 @@ mod test_proto_config {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_local_paths() -> Result<()> {
+        let test_dir = Path::new(protobuf::SELF);
+
+        let runtime = crate::core::runtime::test::init(None);
+        let reader = ProtoReader::init(ResourceReader::<Cached>::cached(runtime.clone()), runtime);
+        let paths = reader.local_paths(protobuf::NESTED_0, None).await?;
+
+        assert_eq!(paths[0], protobuf::NESTED_0);
+        for name in ["nested1.proto", "news.proto", "cycle.proto"] {
+            let path = test_dir.join(name).to_string_lossy().to_string();
+            assert!(paths.contains(&path), "{path} isn't watched");
+        }
+        assert!(!paths.iter().any(|path| path.contains("google/protobuf/")));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_proto_no_pkg() -> Result<()> {
         let runtime = crate::core::runtime::test::init(None);
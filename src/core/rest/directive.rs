@@ -21,6 +21,12 @@ pub(crate) struct Rest {
     pub query: BTreeMap<String, String>,
     #[serde(default, skip_serializing_if = "is_default")]
     pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub unwrap: Option<String>,
 }
 
 impl TryFrom<&Directive> for Rest {
@@ -63,6 +69,15 @@ impl TryFrom<&Directive> for Rest {
                         rest.body = Some(v.to_string());
                     }
                 }
+                "prefix" => {
+                    rest.prefix = serde_json::from_str(v.node.to_string().as_str())?;
+                }
+                "status" => {
+                    rest.status = serde_json::from_str(v.node.to_string().as_str())?;
+                }
+                "unwrap" => {
+                    rest.unwrap = serde_json::from_str(v.node.to_string().as_str())?;
+                }
                 _ => {}
             };
         }
@@ -215,6 +230,24 @@ mod tests {
         pretty_assertions::assert_eq!(directives, vec![true; 5]);
     }
 
+    #[test]
+    fn test_directive_to_rest_response_options() {
+        let query = generate_query_with_directive(
+            r#"method: POST, path: "/foo/$a", prefix: "/v1", status: 201, unwrap: "data.value""#,
+            DEFAULT_QUERY_PARAM,
+        );
+        let directive = query_to_directive(&query);
+        let actual = Rest::try_from(&directive).unwrap();
+        let expected = Rest::default()
+            .path("/foo/$a".to_string())
+            .method(Some(Method::POST))
+            .prefix(Some("/v1".to_string()))
+            .status(Some(201))
+            .unwrap(Some("data.value".to_string()));
+
+        pretty_assertions::assert_eq!(actual, expected);
+    }
+
     #[test]
     #[should_panic(expected = "Path not provided in the directive")]
     fn directive_to_rest_without_path() {
@@ -4,16 +4,17 @@ use async_graphql::parser::types::{Directive, DocumentOperations, ExecutableDocu
 use async_graphql::{Positioned, Variables};
 use async_graphql_value::{ConstValue, Name};
 use derive_setters::Setters;
+use http::StatusCode;
 
 use super::directive::Rest;
 use super::partial_request::PartialRequest;
 use super::path::{Path, Segment};
 use super::query_params::QueryParams;
 use super::type_map::TypeMap;
-use super::{Request, Result};
+use super::{Error, Request, Result};
 use crate::core::async_graphql_hyper::GraphQLRequest;
 use crate::core::directive::DirectiveCodec;
-use crate::core::http::Method;
+use crate::core::http::{Method, API_URL_PREFIX};
 use crate::core::rest::typed_variables::{UrlParamType, N};
 
 /// An executable Http Endpoint created from a GraphQL query
@@ -25,6 +26,9 @@ pub struct Endpoint {
     // Can use persisted queries for better performance
     query_params: QueryParams,
     body: Option<String>,
+    prefix: String,
+    status: StatusCode,
+    unwrap: Option<Vec<String>>,
     pub doc: ExecutableDocument,
 }
 
@@ -37,6 +41,27 @@ impl Endpoint {
     pub fn get_path(&self) -> &Path {
         &self.path
     }
+
+    pub fn get_query_params(&self) -> &QueryParams {
+        &self.query_params
+    }
+
+    pub fn get_body(&self) -> Option<&String> {
+        self.body.as_ref()
+    }
+
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn get_status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn get_unwrap(&self) -> Option<&[String]> {
+        self.unwrap.as_deref()
+    }
+
     pub fn try_new(operations: &str) -> Result<Vec<Self>> {
         let doc = async_graphql::parser::parse_query(operations)?;
         let mut endpoints = Vec::new();
@@ -66,11 +91,27 @@ impl Endpoint {
 
             if let Some(rest) = rest {
                 let rest = rest?;
+                let status = match rest.status {
+                    Some(status) => StatusCode::from_u16(status)
+                        .ok()
+                        .filter(|status| status.is_success())
+                        .ok_or(Error::InvalidStatus(status))?,
+                    None => StatusCode::OK,
+                };
                 let endpoint = Self {
                     method: rest.method.unwrap_or_default(),
                     path: Path::parse(&type_map, &rest.path)?,
                     query_params: QueryParams::try_from_map(&type_map, rest.query)?,
                     body: rest.body,
+                    prefix: normalize_prefix(rest.prefix.as_deref().unwrap_or(API_URL_PREFIX)),
+                    status,
+                    unwrap: rest.unwrap.map(|unwrap| {
+                        unwrap
+                            .split('.')
+                            .filter(|key| !key.is_empty())
+                            .map(String::from)
+                            .collect()
+                    }),
                     doc: ExecutableDocument {
                         operations: DocumentOperations::Single(op.clone()),
                         fragments: doc.fragments.clone(),
@@ -136,36 +177,58 @@ impl Endpoint {
         directives.retain(|v| v.node.name.node != name)
     }
 
-    pub fn matches<'a>(&'a self, request: &Request) -> Option<PartialRequest<'a>> {
-        let query_params = request
-            .uri()
-            .query()
-            .map(|query| serde_urlencoded::from_str(query).unwrap_or_else(|_| BTreeMap::new()))
-            .unwrap_or_default();
-
-        let mut variables = Variables::default();
-
+    /// Returns `None` when the request isn't meant for this endpoint and an
+    /// error when it is, but its path or query params can't be coerced to
+    /// the types of the operation's variables.
+    pub fn matches<'a>(&'a self, request: &Request) -> Option<Result<PartialRequest<'a>>> {
         // Method
         if self.method.clone().to_hyper() != request.method() {
             return None;
         }
 
+        // Prefix
+        let path = request.uri().path().strip_prefix(self.prefix.as_str())?;
+        if !path.is_empty() && !path.starts_with('/') {
+            return None;
+        }
+
         // Path
-        let path = self.path.matches(request.uri().path())?;
+        let path = self.path.matches(path)?;
 
-        // Query
-        let query = self.query_params.matches(query_params)?;
+        let query_params = request
+            .uri()
+            .query()
+            .map(|query| serde_urlencoded::from_str(query).unwrap_or_else(|_| BTreeMap::new()))
+            .unwrap_or_default();
 
-        // TODO: Too much cloning is happening via merge_variables
-        variables = merge_variables(variables, path);
-        variables = merge_variables(variables, query);
+        let variables = path.and_then(|path| {
+            // Query
+            let query = self.query_params.matches(query_params)?;
 
-        Some(PartialRequest {
+            // TODO: Too much cloning is happening via merge_variables
+            Ok(merge_variables(path, query))
+        });
+
+        Some(variables.map(|variables| PartialRequest {
             body: self.body.as_ref(),
             doc: &self.doc,
             variables,
             path: &self.path,
-        })
+            prefix: &self.prefix,
+            status: self.status,
+            unwrap: self.unwrap.as_deref(),
+        }))
+    }
+}
+
+/// Prefixes start with a `/` and never end with one, so that they can be
+/// joined with the path of the endpoint.
+fn normalize_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("/{prefix}")
     }
 }
 
@@ -244,6 +307,24 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_endpoint_status() {
+        let query = r#"
+            query ($a: Int) @rest(method: POST, path: "/foo/$a", status: 201) { value }
+        "#;
+        let endpoint = &Endpoint::try_new(query).unwrap()[0];
+        assert_eq!(endpoint.status, StatusCode::CREATED);
+
+        let query = r#"
+            query ($a: Int) @rest(method: POST, path: "/foo/$a", status: 404) { value }
+        "#;
+        let actual = Endpoint::try_new(query).map(|_| ()).unwrap_err();
+        assert_eq!(
+            actual.to_string(),
+            "Invalid status: 404, expected a 2xx status code"
+        );
+    }
+
     #[test]
     fn test_endpoint() {
         let endpoint = &Endpoint::try_new(TEST_QUERY).unwrap()[0];
@@ -300,19 +381,25 @@ mod tests {
                 .body(Body::empty())?)
         }
 
-        fn test_matches(query: &str, method: Method, uri: &str) -> Option<Variables> {
+        fn test_matches(query: &str, method: Method, uri: &str) -> Option<Result<Variables>> {
             let endpoint = &mut Endpoint::try_new(query).unwrap()[0];
             let request = test_request(method, uri).unwrap();
 
-            endpoint.matches(&request).map(|req| req.variables)
+            endpoint
+                .matches(&request)
+                .map(|req| req.map(|req| req.variables))
+        }
+
+        fn test_matches_ok(query: &str, method: Method, uri: &str) -> Option<Variables> {
+            test_matches(query, method, uri).map(|req| req.unwrap())
         }
 
         #[test]
         fn test_valid() {
-            let actual = test_matches(
+            let actual = test_matches_ok(
                 TEST_QUERY,
                 Method::POST,
-                "http://localhost:8080/foo/1?b=b&c=true&d=1.25",
+                "http://localhost:8080/api/foo/1?b=b&c=true&d=1.25",
             );
             let expected = &btreemap! {
                 Name::new("a") => ConstValue::from(1),
@@ -325,17 +412,17 @@ mod tests {
 
         #[test]
         fn test_path_not_match() {
-            let actual = test_matches(
+            let actual = test_matches_ok(
                 TEST_QUERY,
                 Method::POST,
-                "http://localhost:8080/bar/1?b=b&c=true",
+                "http://localhost:8080/api/bar/1?b=b&c=true",
             );
 
             assert_eq!(actual, None);
-            let actual = test_matches(
+            let actual = test_matches_ok(
                 TEST_QUERY,
                 Method::POST,
-                "http://localhost:8080/foo/1/nested?b=b&c=true",
+                "http://localhost:8080/api/foo/1/nested?b=b&c=true",
             );
 
             assert_eq!(actual, None);
@@ -346,20 +433,30 @@ mod tests {
             let actual = test_matches(
                 TEST_QUERY,
                 Method::POST,
-                "http://localhost:8080/foo/a?b=b&c=true",
-            );
-            pretty_assertions::assert_eq!(actual, None)
+                "http://localhost:8080/api/foo/a?b=b&c=true",
+            )
+            .unwrap()
+            .unwrap_err();
+            assert_eq!(
+                actual.to_string(),
+                "Invalid value `a` for param `a`: Parse Integer Error: invalid digit found in string"
+            )
         }
 
         #[test]
         fn test_query_params_optional() {
-            let actual = test_matches(TEST_QUERY, Method::POST, "http://localhost:8080/foo/1");
+            let actual =
+                test_matches_ok(TEST_QUERY, Method::POST, "http://localhost:8080/api/foo/1");
             let expected = &btreemap! {
                 Name::new("a") => ConstValue::from(1),
             };
             pretty_assertions::assert_eq!(actual.as_deref(), Some(expected));
 
-            let actual = test_matches(TEST_QUERY, Method::POST, "http://localhost:8080/foo/1/?b=b");
+            let actual = test_matches_ok(
+                TEST_QUERY,
+                Method::POST,
+                "http://localhost:8080/api/foo/1/?b=b",
+            );
             let expected = &btreemap! {
                 Name::new("a") => ConstValue::from(1),
                 Name::new("b") => ConstValue::from("b"),
@@ -372,17 +469,41 @@ mod tests {
             let actual = test_matches(
                 TEST_QUERY,
                 Method::POST,
-                "http://localhost:8080/foo/1?b=b&c=c",
-            );
-            assert_eq!(actual, None)
+                "http://localhost:8080/api/foo/1?b=b&c=c",
+            )
+            .unwrap()
+            .unwrap_err();
+            assert_eq!(
+                actual.to_string(),
+                "Invalid value `c` for param `c`: Parse Boolean Error: provided string was not `true` or `false`"
+            )
+        }
+
+        #[test]
+        fn test_prefix() {
+            let query = r#"
+                query ($a: Int) @rest(method: GET, path: "/foo/$a", prefix: "/v1/") {
+                  value
+                }
+            "#;
+
+            let actual = test_matches_ok(query, Method::GET, "http://localhost:8080/v1/foo/1");
+            let expected = &btreemap! { Name::new("a") => ConstValue::from(1) };
+            pretty_assertions::assert_eq!(actual.as_deref(), Some(expected));
+
+            let actual = test_matches_ok(query, Method::GET, "http://localhost:8080/api/foo/1");
+            assert_eq!(actual, None);
+
+            let actual = test_matches_ok(query, Method::GET, "http://localhost:8080/v10/foo/1");
+            assert_eq!(actual, None);
         }
 
         #[test]
         fn test_method_not_match() {
-            let actual = test_matches(
+            let actual = test_matches_ok(
                 TEST_QUERY,
                 Method::GET,
-                "http://localhost:8080/foo/1?b=b&c=true",
+                "http://localhost:8080/api/foo/1?b=b&c=true",
             );
             assert_eq!(actual, None)
         }
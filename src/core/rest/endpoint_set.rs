@@ -1,11 +1,17 @@
 use std::sync::Arc;
 
+use async_graphql::parser::types::ExecutableDocument;
+use convert_case::{Case, Casing};
 use tailcall_valid::Validator;
 
 use super::endpoint::Endpoint;
+use super::expose;
+use super::openapi::OpenApi;
 use super::partial_request::PartialRequest;
+use super::path::Segment;
 use super::{Request, Result};
 use crate::core::blueprint::Blueprint;
+use crate::core::config::Config;
 use crate::core::http::RequestContext;
 use crate::core::macros::MergeRight;
 use crate::core::rest::operation::OperationQuery;
@@ -53,6 +59,17 @@ impl EndpointSet<Unchecked> {
         Ok(set)
     }
 
+    /// Derives the endpoints of the root fields exposed with `@restExpose` or
+    /// `@server(autoRest: true)`.
+    pub fn from_config(config: &Config) -> Result<EndpointSet<Unchecked>> {
+        let operations = expose::operations(config)?;
+        if operations.is_empty() {
+            return Ok(EndpointSet::default());
+        }
+
+        Self::try_new(&operations)
+    }
+
     pub fn extend(&mut self, other: EndpointSet<Unchecked>) {
         self.endpoints.extend(other.endpoints);
     }
@@ -83,7 +100,55 @@ impl EndpointSet<Unchecked> {
 }
 
 impl EndpointSet<Checked> {
-    pub fn matches(&self, request: &Request) -> Option<PartialRequest> {
-        self.endpoints.iter().find_map(|e| e.matches(request))
+    /// Finds the endpoint for the request. An endpoint that accepts the
+    /// params of the request wins over one that fails to coerce them, whose
+    /// error is only returned when no other endpoint matches.
+    pub fn matches(&self, request: &Request) -> Option<Result<PartialRequest>> {
+        let mut invalid = None;
+        for endpoint in self.endpoints.iter() {
+            match endpoint.matches(request) {
+                Some(Ok(partial_request)) => return Some(Ok(partial_request)),
+                Some(Err(err)) if invalid.is_none() => invalid = Some(Err(err)),
+                _ => {}
+            }
+        }
+        invalid
+    }
+
+    /// Returns the operations of the endpoints, named after the operation or,
+    /// when it's anonymous, after the method and the path of the endpoint,
+    /// e.g. `GetUsersId` for `GET /users/$id`.
+    pub fn operations(&self) -> Vec<(String, &ExecutableDocument)> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let name = endpoint
+                    .doc
+                    .operations
+                    .iter()
+                    .next()
+                    .and_then(|(name, _)| name)
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| {
+                        let path = endpoint
+                            .get_path()
+                            .segments
+                            .iter()
+                            .map(|segment| match segment {
+                                Segment::Literal(literal) => literal.as_str(),
+                                Segment::Param(param) => param.name(),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        format!("{} {path}", endpoint.get_method())
+                    });
+                (name.to_case(Case::Pascal), &endpoint.doc)
+            })
+            .collect()
+    }
+
+    /// Describes the endpoints as an OpenAPI 3 document.
+    pub fn to_openapi(&self, blueprint: &Blueprint) -> serde_json::Value {
+        OpenApi::new(blueprint).generate(&self.endpoints)
     }
 }
@@ -38,6 +38,22 @@ pub enum Error {
     #[debug("Undefined param : {key} in {input}")]
     UndefinedParam { key: String, input: String },
 
+    #[error("Invalid value `{value}` for param `{key}`: {reason}")]
+    #[debug("Invalid value `{value}` for param `{key}`: {reason}")]
+    InvalidParam {
+        key: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("Invalid status: {}, expected a 2xx status code", _0)]
+    InvalidStatus(u16),
+
+    #[error("Can't expose {field} as a REST endpoint: {reason}")]
+    #[debug("Can't expose {field} as a REST endpoint: {reason}")]
+    #[from(ignore)]
+    Expose { field: String, reason: String },
+
     #[error("Validation Error : {}", _0)]
     Validation(ValidationError<std::string::String>),
 
@@ -0,0 +1,242 @@
+use super::{Error, Result};
+use crate::core::config::{Config, Field, RestExpose};
+use crate::core::http::Method;
+
+/// Depth of the selection set generated for the output type of a field.
+const DEFAULT_DEPTH: usize = 2;
+
+/// Argument types that can be parsed from the path or the query string.
+const PARAM_TYPES: &[&str] = &["String", "Int", "Float", "Boolean", "ID"];
+
+/// Builds the `@rest` operations of the root fields that are exposed with
+/// `@restExpose`, or of all of them with `@server(autoRest: true)`.
+pub fn operations(config: &Config) -> Result<String> {
+    let auto_rest = config.server.enable_auto_rest();
+    let default = RestExpose::default();
+    let roots = [
+        ("query", Method::GET, config.schema.query.as_deref()),
+        ("mutation", Method::POST, config.schema.mutation.as_deref()),
+    ];
+
+    let mut operations = vec![];
+    for (kind, method, root) in roots {
+        let Some((root, type_of)) = root.and_then(|root| Some((root, config.find_type(root)?)))
+        else {
+            continue;
+        };
+
+        for (name, field) in type_of.fields.iter() {
+            let expose = match field.rest_expose.as_ref() {
+                Some(expose) => expose,
+                None if auto_rest => &default,
+                None => continue,
+            };
+            if is_omitted(field) {
+                continue;
+            }
+
+            match operation(config, kind, &method, root, name, field, expose) {
+                Ok(operation) => operations.push(operation),
+                Err(reason) if field.rest_expose.is_some() => {
+                    return Err(Error::Expose { field: format!("{root}.{name}"), reason });
+                }
+                Err(reason) => {
+                    tracing::debug!("Skipping the REST endpoint of {root}.{name}: {reason}");
+                }
+            }
+        }
+    }
+
+    Ok(operations.join("\n"))
+}
+
+fn operation(
+    config: &Config,
+    kind: &str,
+    method: &Method,
+    root: &str,
+    name: &str,
+    field: &Field,
+    expose: &RestExpose,
+) -> std::result::Result<String, String> {
+    let field_name = public_name(name, field);
+    let mut path = expose
+        .path
+        .clone()
+        .unwrap_or_else(|| format!("/{field_name}"));
+    let path_params = path
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix('$'))
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let mut variables = vec![];
+    let mut arguments = vec![];
+    let mut query = vec![];
+    let mut body = None;
+    for (arg_name, arg) in field.args.iter() {
+        let required = !arg.type_of.is_nullable();
+        let is_param = !arg.type_of.is_list() && PARAM_TYPES.contains(&arg.type_of.name().as_str());
+
+        if path_params.contains(arg_name) {
+            // the type of the param is checked while parsing the path
+        } else if is_param {
+            if required && expose.path.is_none() {
+                path.push_str(&format!("/${arg_name}"));
+            } else {
+                query.push(format!("{arg_name}: ${arg_name}"));
+            }
+        } else if *method == Method::POST && body.is_none() {
+            body = Some(arg_name);
+        } else if required {
+            return Err(format!(
+                "the argument `{arg_name}` can't be read from the path, the query or the body"
+            ));
+        } else {
+            continue;
+        }
+
+        variables.push(format!("${arg_name}: {:?}", arg.type_of));
+        arguments.push(format!("{arg_name}: ${arg_name}"));
+    }
+
+    let depth = expose.depth.unwrap_or(DEFAULT_DEPTH);
+    let selection = selection(config, field.type_of.name(), depth).ok_or_else(|| {
+        format!(
+            "no field of `{}` can be selected within a depth of {depth}",
+            field.type_of.name()
+        )
+    })?;
+
+    let mut rest = format!("method: {method}, path: \"{path}\"");
+    if !query.is_empty() {
+        rest.push_str(&format!(", query: {{{}}}", query.join(", ")));
+    }
+    if let Some(body) = body {
+        rest.push_str(&format!(", body: ${body}"));
+    }
+
+    Ok(format!(
+        "{kind} {root}_{name}{} @rest({rest}) {{ {field_name}{}{selection} }}",
+        parenthesized(&variables),
+        parenthesized(&arguments),
+    ))
+}
+
+/// Selects the fields of the type up to the given depth. Fields that require
+/// arguments are skipped, and so are objects that end up without any field.
+fn selection(config: &Config, type_name: &str, depth: usize) -> Option<String> {
+    if config.find_union(type_name).is_some() {
+        return Some(" { __typename }".to_string());
+    }
+
+    // scalars and enums don't need a selection
+    let Some(type_of) = config
+        .find_type(type_name)
+        .filter(|type_of| !type_of.scalar())
+    else {
+        return Some(String::new());
+    };
+
+    if depth == 0 {
+        return None;
+    }
+
+    let fields = type_of
+        .fields
+        .iter()
+        .filter(|(_, field)| !is_omitted(field))
+        .filter(|(_, field)| field.args.values().all(|arg| arg.type_of.is_nullable()))
+        .filter_map(|(name, field)| {
+            let selection = selection(config, field.type_of.name(), depth - 1)?;
+            Some(format!("{}{selection}", public_name(name, field)))
+        })
+        .collect::<Vec<_>>();
+
+    (!fields.is_empty()).then(|| format!(" {{ {} }}", fields.join(" ")))
+}
+
+fn is_omitted(field: &Field) -> bool {
+    field.omit.is_some()
+        || field
+            .modify
+            .as_ref()
+            .and_then(|modify| modify.omit)
+            .unwrap_or(false)
+}
+
+fn public_name<'a>(name: &'a str, field: &'a Field) -> &'a str {
+    field
+        .modify
+        .as_ref()
+        .and_then(|modify| modify.name.as_deref())
+        .unwrap_or(name)
+}
+
+fn parenthesized(items: &[String]) -> String {
+    if items.is_empty() {
+        String::new()
+    } else {
+        format!("({})", items.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tailcall_valid::Validator;
+
+    use super::*;
+    use crate::core::rest::EndpointSet;
+
+    const CONFIG: &str = include_str!("../jit/fixtures/jsonplaceholder-mutation.graphql");
+
+    fn config(sdl: &str) -> Config {
+        Config::from_sdl(sdl).to_result().unwrap()
+    }
+
+    #[test]
+    fn test_auto_rest() {
+        let sdl = CONFIG.replace("@server(", "@server(autoRest: true, ");
+        let actual = operations(&config(&sdl)).unwrap();
+
+        assert!(actual.contains(
+            r#"query Query_user($id: ID!) @rest(method: GET, path: "/user/$id") { user(id: $id) { address { city street suite zipcode } email id name phone todo { completed id title userId } username website } }"#
+        ));
+        assert!(actual.contains(
+            r#"mutation Mutation_createUser($user: InputUser!) @rest(method: POST, path: "/createUser", body: $user) { createUser(user: $user) {"#
+        ));
+        assert!(actual.contains(r#"getUserIdOrEmail(id: $id) { __typename }"#));
+
+        let endpoints = EndpointSet::try_new(&actual).unwrap();
+        assert_eq!(endpoints.get_endpoints().len(), 7);
+    }
+
+    #[test]
+    fn test_rest_expose() {
+        let sdl = CONFIG.replace(
+            "users: [User] @http",
+            "users: [User] @restExpose(path: \"/all-users\", depth: 1) @http",
+        );
+        let actual = operations(&config(&sdl)).unwrap();
+
+        assert_eq!(
+            actual,
+            r#"query Query_users @rest(method: GET, path: "/all-users") { users { email id name phone username website } }"#
+        );
+    }
+
+    #[test]
+    fn test_rest_expose_fails() {
+        let sdl = CONFIG.replace(
+            "user(id: ID!): User @http",
+            "user(id: ID!): User @restExpose(depth: 0) @http",
+        );
+        let actual = operations(&config(&sdl)).unwrap_err();
+
+        assert_eq!(
+            actual.to_string(),
+            "Can't expose Query.user as a REST endpoint: no field of `User` can be selected within a depth of 0"
+        );
+    }
+}
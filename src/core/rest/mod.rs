@@ -2,6 +2,8 @@ mod directive;
 mod endpoint;
 mod endpoint_set;
 pub mod error;
+mod expose;
+mod openapi;
 mod operation;
 mod partial_request;
 mod path;
@@ -10,6 +12,8 @@ mod type_map;
 mod typed_variables;
 
 pub use endpoint_set::{Checked, EndpointSet, Unchecked};
+pub use openapi::OPENAPI_PATH;
+pub use partial_request::PartialRequest;
 
 type Request = http::Request<hyper::Body>;
 pub use error::{Error, Result};
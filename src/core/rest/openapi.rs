@@ -0,0 +1,496 @@
+use std::collections::{BTreeMap, HashMap};
+
+use async_graphql::parser::types::{
+    BaseType, ExecutableDocument, OperationDefinition, SelectionSet, Type as VariableType,
+};
+use serde_json::{json, Map, Value};
+
+use super::endpoint::Endpoint;
+use super::path::Segment;
+use crate::core::blueprint::{Blueprint, Definition, SelectionWalker};
+use crate::core::scalar::Scalar;
+use crate::core::Type;
+
+/// Path, relative to the default REST prefix, that serves the generated
+/// document when `@server(openapi: true)` is set.
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Builds an OpenAPI 3 document for a set of `@rest` endpoints.
+///
+/// Parameters are described from the variable definitions of the operation
+/// and responses from its selection set, resolved over the blueprint types.
+/// Enums and input objects are emitted once under `components.schemas` and
+/// referenced from everywhere else.
+pub struct OpenApi<'a> {
+    walker: SelectionWalker<'a>,
+    components: BTreeMap<String, Value>,
+}
+
+impl<'a> OpenApi<'a> {
+    pub fn new(blueprint: &'a Blueprint) -> Self {
+        Self {
+            walker: SelectionWalker::new(blueprint),
+            components: BTreeMap::new(),
+        }
+    }
+
+    pub fn generate(mut self, endpoints: &[Endpoint]) -> Value {
+        let mut paths = BTreeMap::<String, Map<String, Value>>::new();
+
+        for endpoint in endpoints {
+            let Some(operation) = first_operation(&endpoint.doc) else {
+                continue;
+            };
+            let item = self.operation(endpoint, operation);
+            paths
+                .entry(path_template(endpoint))
+                .or_default()
+                .insert(endpoint.get_method().to_string().to_lowercase(), item);
+        }
+
+        json!({
+            "openapi": OPENAPI_VERSION,
+            "info": { "title": "Tailcall REST API", "version": "1.0.0" },
+            "paths": paths,
+            "components": { "schemas": self.components },
+        })
+    }
+
+    fn operation(&mut self, endpoint: &Endpoint, operation: &OperationDefinition) -> Value {
+        let variables = operation
+            .variable_definitions
+            .iter()
+            .map(|var| (var.node.name.node.as_str(), &var.node))
+            .collect::<HashMap<_, _>>();
+
+        let mut parameters = vec![];
+        for segment in endpoint.get_path().segments.iter() {
+            if let Segment::Param(param) = segment {
+                let schema = variables
+                    .get(param.name())
+                    .map(|var| self.variable_type(&var.var_type.node))
+                    .unwrap_or_else(|| json!({}));
+                parameters.push(json!({
+                    "name": param.name(),
+                    "in": "path",
+                    "required": true,
+                    "schema": schema,
+                }));
+            }
+        }
+        for (key, param) in endpoint.get_query_params().params() {
+            let var = variables.get(param.name());
+            let required = var.map_or(false, |var| {
+                !var.var_type.node.nullable && var.default_value.is_none()
+            });
+            let schema = var
+                .map(|var| self.variable_type(&var.var_type.node))
+                .unwrap_or_else(|| json!({}));
+            parameters.push(json!({
+                "name": key,
+                "in": "query",
+                "required": required,
+                "schema": schema,
+            }));
+        }
+
+        let mut item = Map::new();
+        if !parameters.is_empty() {
+            item.insert("parameters".to_string(), json!(parameters));
+        }
+
+        if let Some(var) = endpoint
+            .get_body()
+            .and_then(|body| variables.get(body.as_str()))
+        {
+            item.insert(
+                "requestBody".to_string(),
+                json!({
+                    "required": !var.var_type.node.nullable,
+                    "content": {
+                        "application/json": { "schema": self.variable_type(&var.var_type.node) }
+                    },
+                }),
+            );
+        }
+
+        let schema = self
+            .walker
+            .root(operation.ty)
+            .map(|root| {
+                let selection = &operation.selection_set.node;
+                match endpoint.get_unwrap() {
+                    Some(unwrap) => self.unwrapped_response(root, selection, &endpoint.doc, unwrap),
+                    None => self.response(root, selection, &endpoint.doc),
+                }
+            })
+            .unwrap_or_else(|| json!({}));
+        let status = endpoint.get_status();
+
+        item.insert(
+            "responses".to_string(),
+            json!({
+                status.as_str(): {
+                    "description": "Successful response",
+                    "content": { "application/json": { "schema": schema } },
+                },
+                "400": { "description": "The params or the body of the request are invalid" },
+                "500": { "description": "The operation returned errors" },
+            }),
+        );
+
+        Value::Object(item)
+    }
+
+    /// Mirrors `GraphQLResponse::into_rest_response`, which unwraps the data
+    /// of operations that select a single root field.
+    fn response(
+        &mut self,
+        root: &str,
+        selection: &SelectionSet,
+        doc: &ExecutableDocument,
+    ) -> Value {
+        let schema = self.selection(root, selection, doc);
+        let single = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .filter(|properties| properties.len() == 1)
+            .and_then(|properties| properties.values().next().cloned());
+
+        single.unwrap_or(schema)
+    }
+
+    /// Follows the `unwrap` path of the endpoint through the `{data}`
+    /// envelope of the response.
+    fn unwrapped_response(
+        &mut self,
+        root: &str,
+        selection: &SelectionSet,
+        doc: &ExecutableDocument,
+        unwrap: &[String],
+    ) -> Value {
+        let data = self.selection(root, selection, doc);
+        let envelope = json!({ "type": "object", "properties": { "data": data } });
+
+        unwrap
+            .iter()
+            .try_fold(&envelope, |schema, key| match schema.get("items") {
+                Some(items) if key.parse::<usize>().is_ok() => Some(items),
+                _ => schema.get("properties")?.get(key),
+            })
+            .cloned()
+            .unwrap_or_else(|| json!({}))
+    }
+
+    fn selection(
+        &mut self,
+        type_name: &str,
+        selection: &SelectionSet,
+        doc: &ExecutableDocument,
+    ) -> Value {
+        // fields selected through fragments on a different type (e.g. a union
+        // member) only show up for some values, so they are never required.
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for field in self.walker.fields(type_name, selection, doc) {
+            let (schema, is_nullable) = match field.definition {
+                Some(definition) => {
+                    let of_type = &definition.of_type;
+                    let schema = self.output_type(of_type, field.selection, doc);
+                    (schema, of_type.is_nullable())
+                }
+                None => (json!({ "type": "string" }), false),
+            };
+            if field.always && !is_nullable {
+                required.push(field.key.clone());
+            }
+            properties.insert(field.key, schema);
+        }
+
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = json!(required);
+        }
+        schema
+    }
+
+    fn output_type(
+        &mut self,
+        of_type: &Type,
+        selection: &SelectionSet,
+        doc: &ExecutableDocument,
+    ) -> Value {
+        let schema = match of_type {
+            Type::Named { name, .. } => {
+                if selection.items.is_empty() {
+                    self.named_type(name)
+                } else {
+                    self.selection(name, selection, doc)
+                }
+            }
+            Type::List { of_type, .. } => {
+                json!({ "type": "array", "items": self.output_type(of_type, selection, doc) })
+            }
+        };
+        nullable(schema, of_type.is_nullable())
+    }
+
+    fn variable_type(&mut self, of_type: &VariableType) -> Value {
+        let schema = match &of_type.base {
+            BaseType::Named(name) => self.named_type(name.as_str()),
+            BaseType::List(of_type) => {
+                json!({ "type": "array", "items": self.variable_type(of_type) })
+            }
+        };
+        nullable(schema, of_type.nullable)
+    }
+
+    fn input_type(&mut self, of_type: &Type) -> Value {
+        let schema = match of_type {
+            Type::Named { name, .. } => self.named_type(name),
+            Type::List { of_type, .. } => {
+                json!({ "type": "array", "items": self.input_type(of_type) })
+            }
+        };
+        nullable(schema, of_type.is_nullable())
+    }
+
+    /// Schema of a type that is not expanded through a selection set:
+    /// scalars are inlined while enums and input objects are referenced.
+    fn named_type(&mut self, name: &str) -> Value {
+        if let Some(schema) = scalar(name) {
+            return schema;
+        }
+
+        match self.walker.definition(name) {
+            Some(Definition::Enum(def)) => {
+                if !self.components.contains_key(name) {
+                    let values = def
+                        .enum_values
+                        .iter()
+                        .map(|v| v.name.as_str())
+                        .collect::<Vec<_>>();
+                    let mut schema = json!({ "type": "string", "enum": values });
+                    if let Some(description) = &def.description {
+                        schema["description"] = json!(description);
+                    }
+                    self.components.insert(name.to_string(), schema);
+                }
+                reference(name)
+            }
+            Some(Definition::InputObject(def)) => {
+                if !self.components.contains_key(name) {
+                    // reserve the slot first so recursive inputs terminate
+                    self.components.insert(name.to_string(), Value::Null);
+
+                    let mut properties = Map::new();
+                    let mut required = vec![];
+                    for field in def.fields.iter() {
+                        let mut schema = self.input_type(&field.of_type);
+                        if let Some(description) = &field.description {
+                            schema["description"] = json!(description);
+                        }
+                        if let Some(default) = &field.default_value {
+                            schema["default"] = default.clone();
+                        }
+                        if !field.of_type.is_nullable() && field.default_value.is_none() {
+                            required.push(field.name.clone());
+                        }
+                        properties.insert(field.name.clone(), schema);
+                    }
+
+                    let mut schema = json!({ "type": "object", "properties": properties });
+                    if !required.is_empty() {
+                        schema["required"] = json!(required);
+                    }
+                    if let Some(description) = &def.description {
+                        schema["description"] = json!(description);
+                    }
+                    self.components.insert(name.to_string(), schema);
+                }
+                reference(name)
+            }
+            Some(Definition::Scalar(def)) => {
+                serde_json::to_value(def.scalar.schema()).unwrap_or_default()
+            }
+            // objects without a selection set can't be described
+            _ => json!({}),
+        }
+    }
+}
+
+fn first_operation(doc: &ExecutableDocument) -> Option<&OperationDefinition> {
+    doc.operations.iter().next().map(|(_, op)| &op.node)
+}
+
+/// Converts the `$name` params of the endpoint path to `{name}` templates.
+fn path_template(endpoint: &Endpoint) -> String {
+    let path = endpoint
+        .get_path()
+        .segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Literal(literal) => literal.clone(),
+            Segment::Param(param) => format!("{{{}}}", param.name()),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    format!("{}/{path}", endpoint.get_prefix())
+}
+
+fn scalar(name: &str) -> Option<Value> {
+    let schema = match name {
+        "Int" => json!({ "type": "integer", "format": "int32" }),
+        "Float" => json!({ "type": "number", "format": "double" }),
+        "String" | "ID" => json!({ "type": "string" }),
+        "Boolean" => json!({ "type": "boolean" }),
+        name => serde_json::to_value(Scalar::find(name)?.schema()).ok()?,
+    };
+    Some(schema)
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// OpenAPI 3.0 ignores siblings of `$ref`, so nullable references are
+/// wrapped with `allOf`.
+fn nullable(schema: Value, is_nullable: bool) -> Value {
+    if !is_nullable {
+        schema
+    } else if schema.get("$ref").is_some() {
+        json!({ "allOf": [schema], "nullable": true })
+    } else if let Value::Object(mut schema) = schema {
+        if !schema.is_empty() {
+            schema.insert("nullable".to_string(), json!(true));
+        }
+        Value::Object(schema)
+    } else {
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tailcall_valid::Validator;
+
+    use super::*;
+    use crate::core::config::{Config, ConfigModule};
+
+    const CONFIG: &str = include_str!("../jit/fixtures/jsonplaceholder-mutation.graphql");
+
+    fn generate(operations: &str) -> Value {
+        let config = Config::from_sdl(CONFIG).to_result().unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+        let endpoints = Endpoint::try_new(operations).unwrap();
+
+        OpenApi::new(&blueprint).generate(&endpoints)
+    }
+
+    #[test]
+    fn test_path_and_query_params() {
+        let doc = generate(
+            r#"
+            query ($id: Int!, $verbose: Boolean) @rest(method: GET, path: "/users/$id", query: {verbose: $verbose}) {
+              user(id: $id) { id name }
+            }
+            "#,
+        );
+        let operation = &doc["paths"]["/api/users/{id}"]["get"];
+
+        assert_eq!(
+            operation["parameters"],
+            json!([
+                {
+                    "name": "id",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer", "format": "int32" },
+                },
+                {
+                    "name": "verbose",
+                    "in": "query",
+                    "required": false,
+                    "schema": { "type": "boolean", "nullable": true },
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_response_follows_selection() {
+        let doc = generate(
+            r#"
+            query ($id: Int!) @rest(method: GET, path: "/users/$id") {
+              user(id: $id) { id fullName: name address { city } }
+            }
+            "#,
+        );
+        let schema = &doc["paths"]["/api/users/{id}"]["get"]["responses"]["200"]["content"]
+            ["application/json"]["schema"];
+
+        assert_eq!(
+            schema,
+            &json!({
+                "type": "object",
+                "nullable": true,
+                "properties": {
+                    "id": { "type": "string" },
+                    "fullName": { "type": "string" },
+                    "address": {
+                        "type": "object",
+                        "nullable": true,
+                        "properties": { "city": { "type": "string", "nullable": true } },
+                    },
+                },
+                "required": ["id", "fullName"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_options() {
+        let doc = generate(
+            r#"
+            mutation ($user: InputUser!) @rest(method: POST, path: "/users", prefix: "/v1", status: 201, unwrap: "data.createUser.address") {
+              createUser(user: $user) { id address { city } }
+            }
+            "#,
+        );
+        let responses = &doc["paths"]["/v1/users"]["post"]["responses"];
+
+        assert_eq!(
+            responses["201"]["content"]["application/json"]["schema"],
+            json!({
+                "type": "object",
+                "nullable": true,
+                "properties": { "city": { "type": "string", "nullable": true } },
+            })
+        );
+    }
+
+    #[test]
+    fn test_request_body_references_input() {
+        let doc = generate(
+            r#"
+            mutation ($user: InputUser!) @rest(method: POST, path: "/users", body: $user) {
+              createUser(user: $user) { id }
+            }
+            "#,
+        );
+        let operation = &doc["paths"]["/api/users"]["post"];
+
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"],
+            json!({ "$ref": "#/components/schemas/InputUser" })
+        );
+        assert_eq!(
+            doc["components"]["schemas"]["InputUser"]["required"],
+            json!(["id", "name", "username", "email"])
+        );
+    }
+}
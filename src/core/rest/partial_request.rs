@@ -1,6 +1,7 @@
 use async_graphql::parser::types::ExecutableDocument;
 use async_graphql::{Name, Variables};
 use async_graphql_value::ConstValue;
+use http::StatusCode;
 
 use super::path::Path;
 use super::{Request, Result};
@@ -13,6 +14,9 @@ pub struct PartialRequest<'a> {
     pub doc: &'a ExecutableDocument,
     pub variables: Variables,
     pub path: &'a Path,
+    pub prefix: &'a str,
+    pub status: StatusCode,
+    pub unwrap: Option<&'a [String]>,
 }
 
 impl PartialRequest<'_> {
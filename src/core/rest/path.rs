@@ -50,8 +50,10 @@ impl Path {
         Ok(Self { segments, pattern: input.to_string() })
     }
 
-    pub fn matches(&self, path: &str) -> Option<Variables> {
-        let mut variables = Variables::default();
+    /// Returns `None` when the request path doesn't have the shape of the
+    /// pattern and an error when one of its params can't be coerced.
+    pub fn matches(&self, path: &str) -> Option<Result<Variables>> {
+        let mut params = Vec::new();
         let mut req_segments = path.split('/').filter(|s| !s.is_empty());
         for (segment, req_segment) in self.segments.iter().zip(&mut req_segments) {
             match segment {
@@ -60,10 +62,7 @@ impl Path {
                         return None;
                     }
                 }
-                Segment::Param(t_var) => {
-                    let tpe = t_var.to_value(req_segment).ok()?;
-                    variables.insert(Name::new(t_var.name()), tpe);
-                }
+                Segment::Param(t_var) => params.push((t_var, req_segment)),
             }
         }
 
@@ -72,6 +71,16 @@ impl Path {
             return None;
         }
 
-        Some(variables)
+        let mut variables = Variables::default();
+        for (t_var, req_segment) in params {
+            match t_var.coerce(t_var.name(), req_segment) {
+                Ok(value) => {
+                    variables.insert(Name::new(t_var.name()), value);
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        Some(Ok(variables))
     }
 }
@@ -20,6 +20,10 @@ impl From<Vec<(&str, TypedVariable)>> for QueryParams {
 }
 
 impl QueryParams {
+    pub fn params(&self) -> &[(String, TypedVariable)] {
+        &self.params
+    }
+
     pub fn try_from_map(q: &TypeMap, map: BTreeMap<String, String>) -> Result<Self> {
         let mut params = Vec::new();
         for (k, v) in map {
@@ -32,14 +36,14 @@ impl QueryParams {
         Ok(Self { params })
     }
 
-    pub fn matches(&self, query_params: BTreeMap<String, String>) -> Option<Variables> {
+    pub fn matches(&self, query_params: BTreeMap<String, String>) -> Result<Variables> {
         let mut variables = Variables::default();
         for (key, t_var) in &self.params {
             if let Some(query_param) = query_params.get(key) {
-                let value = t_var.to_value(query_param).ok()?;
+                let value = t_var.coerce(key, query_param)?;
                 variables.insert(Name::new(t_var.name()), value);
             }
         }
-        Some(variables)
+        Ok(variables)
     }
 }
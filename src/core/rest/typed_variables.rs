@@ -40,7 +40,7 @@ impl TryFrom<&Type> for UrlParamType {
     fn try_from(value: &Type) -> Result<Self> {
         match &value.base {
             BaseType::Named(name) => match name.as_str() {
-                "String" => Ok(Self::String),
+                "String" | "ID" => Ok(Self::String),
                 "Int" => Ok(Self::Number(N::Int)),
                 "Boolean" => Ok(Self::Boolean),
                 "Float" => Ok(Self::Number(N::Float)),
@@ -73,6 +73,16 @@ impl TypedVariable {
         self.type_of.to_value(value)
     }
 
+    /// Converts the raw value of the `key` param, reporting the param and
+    /// its value when the conversion fails.
+    pub fn coerce(&self, key: &str, value: &str) -> Result<ConstValue> {
+        self.to_value(value).map_err(|err| Error::InvalidParam {
+            key: key.to_string(),
+            value: value.to_string(),
+            reason: err.to_string(),
+        })
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
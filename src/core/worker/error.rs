@@ -56,6 +56,10 @@ pub enum Error {
     )]
     FunctionValueParseError(String, String),
 
+    #[debug("Script timed out after {}ms", _0)]
+    #[from(ignore)]
+    Timeout(u64),
+
     #[debug("Error : {}", _0)]
     Anyhow(Arc<anyhow::Error>),
 }
@@ -107,6 +111,7 @@ impl Display for Error {
             Error::DeserializeFailed(error) => write!(f, "Deserialize Failed: {}", error),
             Error::GlobalThisNotInitialised(error) => write!(f, "globalThis not initialized: {}", error),
             Error::FunctionValueParseError(error, name) => write!(f, "Error: {}\nUnable to parse value from js function: {} maybe because it's not returning a string?", error, name),
+            Error::Timeout(timeout) => write!(f, "Script timed out after {}ms", timeout),
             Error::Anyhow(msg) => write!(f, "Error: {}", msg),
         }
     }
@@ -43,9 +43,10 @@ fn main() -> anyhow::Result<()> {
         Ok(_) => {}
         Err(error) => {
             // Ensure all errors are converted to Errata before being printed.
+            let exit_code = tailcall::cli::exit_code(&error);
             let cli_error: Errata = error.into();
             tracing::error!("{}", cli_error.color(true));
-            std::process::exit(exitcode::CONFIG);
+            std::process::exit(exit_code);
         }
     }
     Ok(())
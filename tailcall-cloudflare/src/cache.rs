@@ -25,6 +25,43 @@ impl CloudflareChronoCache {
             .map_err(|e| cache::Error::Kv(e.to_string()))
     }
 }
+
+/// Each tagged key is stored as its own empty entry under this prefix, so
+/// that concurrent requests tagging keys don't overwrite each other. The tag
+/// is hex encoded, so that the prefix of a tag, e.g. `user`, doesn't match the
+/// entries of the tags it's a prefix of, e.g. `user:1`.
+fn tag_prefix(tag: &str) -> String {
+    let tag = tag
+        .bytes()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("tag:{tag}:")
+}
+
+async fn get_tagged_keys(kv_store: &KvStore, tag: &str) -> Result<Vec<String>, cache::Error> {
+    let prefix = tag_prefix(tag);
+    let mut keys = vec![];
+    let mut cursor = None;
+    loop {
+        let mut list = kv_store.list().prefix(prefix.clone());
+        if let Some(cursor) = cursor {
+            list = list.cursor(cursor);
+        }
+        let page = list
+            .execute()
+            .await
+            .map_err(|e| cache::Error::Kv(e.to_string()))?;
+        keys.extend(
+            page.keys
+                .into_iter()
+                .filter_map(|key| key.name.strip_prefix(&prefix).map(ToString::to_string)),
+        );
+        if page.list_complete {
+            return Ok(keys);
+        }
+        cursor = page.cursor;
+    }
+}
 // TODO: Needs fix
 #[async_trait::async_trait]
 impl Cache for CloudflareChronoCache {
@@ -64,7 +101,79 @@ impl Cache for CloudflareChronoCache {
         .await
     }
 
+    async fn delete<'a>(&'a self, key: &'a IoId) -> Result<(), cache::Error> {
+        let kv_store = self.get_kv()?;
+        let key = key.as_u64().to_string();
+        async_std::task::spawn_local(async move {
+            kv_store
+                .delete(&key)
+                .await
+                .map_err(|e| cache::Error::Kv(e.to_string()))
+        })
+        .await
+    }
+
+    async fn tag<'a>(
+        &'a self,
+        key: &'a IoId,
+        tags: &'a [String],
+        ttl: NonZeroU64,
+    ) -> Result<(), cache::Error> {
+        let kv_store = self.get_kv()?;
+        let key = key.as_u64().to_string();
+        let tags = tags.to_vec();
+        let ttl = ttl.get();
+        async_std::task::spawn_local(async move {
+            for tag in tags {
+                kv_store
+                    .put(&format!("{}{key}", tag_prefix(&tag)), "")
+                    .map_err(|e| cache::Error::Kv(e.to_string()))?
+                    .expiration_ttl(ttl)
+                    .execute()
+                    .await
+                    .map_err(|e| cache::Error::Kv(e.to_string()))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn purge_by_tag<'a>(&'a self, tag: &'a str) -> Result<usize, cache::Error> {
+        let kv_store = self.get_kv()?;
+        let tag = tag.to_string();
+        async_std::task::spawn_local(async move {
+            let prefix = tag_prefix(&tag);
+            let keys = get_tagged_keys(&kv_store, &tag).await?;
+            for key in keys.iter() {
+                for key in [key.clone(), format!("{prefix}{key}")] {
+                    kv_store
+                        .delete(&key)
+                        .await
+                        .map_err(|e| cache::Error::Kv(e.to_string()))?;
+                }
+            }
+            Ok(keys.len())
+        })
+        .await
+    }
+
     fn hit_rate(&self) -> Option<f64> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_prefix() {
+        let user = tag_prefix("user");
+        let user_1 = tag_prefix("user:1");
+        let marker = format!("{user_1}42");
+
+        assert!(!marker.starts_with(&user));
+        assert!(marker.starts_with(&user_1));
+        assert_ne!(user, user_1);
+    }
+}
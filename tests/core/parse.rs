@@ -285,14 +285,14 @@ impl ExecutionSpec {
 
         let http_worker: Option<Arc<dyn WorkerIO<Event, Command>>> =
             if let Some(script) = script.clone() {
-                Some(javascript::init_worker_io(script))
+                Some(javascript::init_worker_io(script, http.clone()))
             } else {
                 None
             };
 
         let worker: Option<Arc<dyn WorkerIO<ConstValue, ConstValue>>> = if let Some(script) = script
         {
-            Some(javascript::init_worker_io(script))
+            Some(javascript::init_worker_io(script, http.clone()))
         } else {
             None
         };
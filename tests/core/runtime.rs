@@ -74,6 +74,11 @@ pub fn create_runtime(
     let file = TestFileIO::init();
     let env = Env::init(env);
 
+    let cmd_worker = script
+        .clone()
+        .map(|script| init_worker_io::<Event, Command>(script, http.clone()));
+    let worker = script.map(|script| init_worker_io::<Value, Value>(script, http.clone()));
+
     TargetRuntime {
         http,
         http2_only: http2,
@@ -81,13 +86,7 @@ pub fn create_runtime(
         file: Arc::new(file),
         cache: Arc::new(InMemoryCache::default()),
         extensions: Arc::new(vec![]),
-        cmd_worker: match &script {
-            Some(script) => Some(init_worker_io::<Event, Command>(script.to_owned())),
-            None => None,
-        },
-        worker: match &script {
-            Some(script) => Some(init_worker_io::<Value, Value>(script.to_owned())),
-            None => None,
-        },
+        cmd_worker,
+        worker,
     }
 }
@@ -138,6 +138,11 @@ pub mod test {
         let file = TestFileIO::init();
         let env = TestEnvIO::init();
 
+        let cmd_worker = script
+            .clone()
+            .map(|script| init_worker_io::<Event, Command>(script, http.clone()));
+        let worker = script.map(|script| init_worker_io::<Value, Value>(script, http.clone()));
+
         TargetRuntime {
             http,
             http2_only: http2,
@@ -145,14 +150,8 @@ pub mod test {
             file: Arc::new(file),
             cache: Arc::new(InMemoryCache::default()),
             extensions: Arc::new(vec![]),
-            cmd_worker: match &script {
-                Some(script) => Some(init_worker_io::<Event, Command>(script.to_owned())),
-                None => None,
-            },
-            worker: match &script {
-                Some(script) => Some(init_worker_io::<Value, Value>(script.to_owned())),
-                None => None,
-            },
+            cmd_worker,
+            worker,
         }
     }
 }